@@ -1,5 +1,6 @@
 use csv::Writer;
 use itertools::{Either, Itertools};
+use rand::{Rng, SeedableRng, rngs::StdRng, seq::SliceRandom};
 use rayon::prelude::*;
 use std::{collections::HashMap, env, fs, path::PathBuf, time::Duration};
 use tracing_subscriber::{Layer, layer::SubscriberExt, util::SubscriberInitExt};
@@ -10,6 +11,7 @@ use automata::core::word::{OmegaWord, ReducedOmegaWord};
 use automata::core::{Color, Void, math, upw};
 use automata::representation::CollectTs;
 use automata::ts::Deterministic;
+use automata::ts::TSBuilder;
 use automata::ts::run::InfiniteObserver;
 use automata::{
     DTS, TransitionSystem,
@@ -23,6 +25,88 @@ use automata_learning::passive::{
 };
 use tracing::{info, warn};
 
+/// The acceptance-condition family of a learning task. Persisted as the `aut_type` row of a
+/// task's `settings.txt` so that dispatch onto the matching `sprout(...)` instantiation can read
+/// it back directly instead of guessing it from a substring of the task's directory name.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum TaskKind {
+    Buchi,
+    Parity,
+    CoBuchi,
+    GeneralizedBuchi,
+    Rabin,
+    Streett,
+}
+
+impl TaskKind {
+    /// The `aut_type` value written to and read from `settings.txt`.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            TaskKind::Buchi => "dba",
+            TaskKind::Parity => "dpa",
+            TaskKind::CoBuchi => "co-buchi",
+            TaskKind::GeneralizedBuchi => "generalized-buchi",
+            TaskKind::Rabin => "rabin",
+            TaskKind::Streett => "streett",
+        }
+    }
+
+    /// Parses the `aut_type` value stored in `settings.txt` back into a [`TaskKind`].
+    pub fn parse(raw: &str) -> Option<Self> {
+        match raw {
+            "dba" => Some(TaskKind::Buchi),
+            "dpa" => Some(TaskKind::Parity),
+            "co-buchi" => Some(TaskKind::CoBuchi),
+            "generalized-buchi" => Some(TaskKind::GeneralizedBuchi),
+            "rabin" => Some(TaskKind::Rabin),
+            "streett" => Some(TaskKind::Streett),
+            _ => None,
+        }
+    }
+}
+
+/// Reads a headerless `key,value` CSV at `path` into a map.
+fn load_kv_csv(path: &std::path::Path) -> HashMap<String, String> {
+    let mut rdr = csv::ReaderBuilder::new()
+        .has_headers(false)
+        .from_path(path)
+        .unwrap_or_else(|_| panic!("no csv found at {path:?}"));
+    rdr.records()
+        .filter_map(|r| r.ok())
+        .filter_map(|r| Some((r.get(0)?.to_string(), r.get(1)?.to_string())))
+        .collect()
+}
+
+/// Reads the `key,value` rows of a task's `settings.txt` into a map.
+fn load_settings(dir: &std::path::Path) -> HashMap<String, String> {
+    load_kv_csv(&dir.join("settings.txt"))
+}
+
+/// Reads and parses the [`TaskKind`] of the task stored at `dir`.
+pub fn load_task_kind(dir: &std::path::Path) -> TaskKind {
+    let settings = load_settings(dir);
+    let raw = settings
+        .get("aut_type")
+        .expect("settings.txt has no aut_type entry");
+    TaskKind::parse(raw).unwrap_or_else(|| panic!("unrecognized aut_type {raw:?}"))
+}
+
+/// Lists the task directories directly under `data/tasks`.
+fn list_task_dirs() -> Vec<PathBuf> {
+    let mut task_dirs = vec![];
+    let entries = fs::read_dir("data/tasks").expect("No learning tasks available");
+    for entry in entries.flatten() {
+        if let Ok(file_type) = entry.file_type() {
+            if file_type.is_dir() {
+                task_dirs.push(entry.path());
+            }
+        } else {
+            warn!("Couldn't get file type for {:?}", entry.path());
+        }
+    }
+    task_dirs
+}
+
 fn main() {
     // initialize logger
     tracing_subscriber::registry()
@@ -45,6 +129,8 @@ fn main() {
         let test_size = 10000;
         let num_sets = 5;
         let lambda = 0.95;
+        let noise_levels = vec![0.0];
+        let noise_seed = 0xf1a9;
         generate_tasks(
             automata_sizes,
             automata_per_size,
@@ -52,29 +138,30 @@ fn main() {
             test_size,
             num_sets,
             lambda,
+            noise_levels,
+            noise_seed,
         );
     }
     if args.contains(&"sprout".to_string()) {
         info!("Running sprout learner on all tasks");
         run_sprout();
     }
+    if args.contains(&"sprout-cv".to_string()) {
+        let k_folds = 5;
+        let seed = 0x5eed;
+        info!("Running sprout learner in {k_folds}-fold cross-validation mode");
+        run_sprout_cv(k_folds, seed);
+    }
+    if args.contains(&"aggregate".to_string()) {
+        info!("Aggregating per-task results into a cross-task experiment report");
+        aggregate_results();
+    }
     info!("Done");
 }
 
 pub fn run_sprout() {
     // load task directories
-    let mut task_dirs = vec![];
-    let entries = fs::read_dir("data/tasks").expect("No learning tasks available");
-
-    for entry in entries.flatten() {
-        if let Ok(file_type) = entry.file_type() {
-            if file_type.is_dir() {
-                task_dirs.push(entry.path());
-            }
-        } else {
-            warn!("Couldn't get file type for {:?}", entry.path());
-        }
-    }
+    let task_dirs = list_task_dirs();
     task_dirs
         .clone()
         // .into_iter()
@@ -89,102 +176,531 @@ pub fn run_sprout() {
                 return;
             }
 
-            if dir.to_string_lossy().contains("dba") {
-                let time = std::time::Instant::now();
-                info!(
-                    "starting DBA learner for task {i} {:?}",
-                    dir.to_string_lossy()
-                );
-                match sprout(sample, BuchiCondition) {
-                    Ok(learned) => {
-                        let elapsed = time.elapsed();
-                        info!(
-                            "task {i} \"{:?}\" learning took {} ms",
-                            dir.to_string_lossy(),
-                            elapsed.as_millis()
-                        );
-                        export_automaton(
-                            format!("{}/learned.hoa", dir.to_str().unwrap()),
-                            &learned,
-                        );
-                        export_sprout_result(dir, &learned, elapsed);
-                    }
-                    Err(SproutError::Threshold {
-                        thres: _thres,
-                        aut: learned,
-                    }) => {
-                        let elapsed = time.elapsed();
-                        info!(
-                            "task {i} \"{:?}\" exceeded threshold after {} ms",
-                            dir.to_string_lossy(),
-                            elapsed.as_millis()
-                        );
-                        export_automaton(
-                            format!("{}/learned.hoa", dir.to_str().unwrap()),
-                            &learned,
-                        );
-                        export_sprout_result(dir, &learned, elapsed);
-                    }
-                    Err(SproutError::Timeout { aut: partial }) => {
-                        let elapsed = time.elapsed();
-                        info!(
-                            "exceeded timeout on task {i} with partial ts of size {}: {:?}",
-                            partial.size(),
-                            dir.to_string_lossy()
-                        );
-                        export_sprout_timeout(dir, partial, elapsed);
-                    }
+            match load_task_kind(dir) {
+                TaskKind::CoBuchi
+                | TaskKind::GeneralizedBuchi
+                | TaskKind::Rabin
+                | TaskKind::Streett => {
+                    warn!(
+                        "task {i} {:?} requests an acceptance family with no sprout(...) \
+                         instantiation available yet, skipping",
+                        dir.to_string_lossy()
+                    );
+                    return;
                 }
-            } else {
-                let time = std::time::Instant::now();
-                info!(
-                    "starting DPA learner for task {i} {:?}",
-                    dir.to_string_lossy()
-                );
-                match sprout(sample, MinEvenParityCondition) {
-                    Ok(learned) => {
-                        let elapsed = time.elapsed();
-                        info!(
-                            "task {i} \"{:?}\" learning took {} ms",
-                            dir.to_string_lossy(),
-                            elapsed.as_millis()
-                        );
-                        export_automaton(
-                            format!("{}/learned.hoa", dir.to_str().unwrap()),
-                            &learned,
-                        );
-                        export_sprout_result(dir, &learned, elapsed);
-                    }
-                    Err(SproutError::Threshold {
-                        thres: _thres,
-                        aut: learned,
-                    }) => {
-                        let elapsed = time.elapsed();
-                        info!(
-                            "task {i} \"{:?}\" exceeded threshold after {} ms",
-                            dir.to_string_lossy(),
-                            elapsed.as_millis()
-                        );
-                        export_automaton(
-                            format!("{}/learned.hoa", dir.to_str().unwrap()),
-                            &learned,
-                        );
-                        export_sprout_result(dir, &learned, elapsed);
+                TaskKind::Buchi => {
+                    let time = std::time::Instant::now();
+                    info!(
+                        "starting DBA learner for task {i} {:?}",
+                        dir.to_string_lossy()
+                    );
+                    match sprout(sample, BuchiCondition) {
+                        Ok(learned) => {
+                            let elapsed = time.elapsed();
+                            info!(
+                                "task {i} \"{:?}\" learning took {} ms",
+                                dir.to_string_lossy(),
+                                elapsed.as_millis()
+                            );
+                            export_automaton(
+                                format!("{}/learned.hoa", dir.to_str().unwrap()),
+                                &learned,
+                            );
+                            export_sprout_result(dir, &learned, elapsed, false);
+                        }
+                        Err(SproutError::Threshold {
+                            thres: _thres,
+                            aut: learned,
+                        }) => {
+                            let elapsed = time.elapsed();
+                            info!(
+                                "task {i} \"{:?}\" exceeded threshold after {} ms",
+                                dir.to_string_lossy(),
+                                elapsed.as_millis()
+                            );
+                            export_automaton(
+                                format!("{}/learned.hoa", dir.to_str().unwrap()),
+                                &learned,
+                            );
+                            export_sprout_result(dir, &learned, elapsed, true);
+                        }
+                        Err(SproutError::Timeout { aut: partial }) => {
+                            let elapsed = time.elapsed();
+                            info!(
+                                "exceeded timeout on task {i} with partial ts of size {}: {:?}",
+                                partial.size(),
+                                dir.to_string_lossy()
+                            );
+                            export_sprout_timeout(dir, partial, elapsed);
+                        }
                     }
-                    Err(SproutError::Timeout { aut: partial }) => {
-                        let elapsed = time.elapsed();
-                        info!(
-                            "exceeded timeout on task {i} with partial ts of size {}: {:?}",
-                            partial.size(),
-                            dir.to_string_lossy()
-                        );
-                        export_sprout_timeout(dir, partial, elapsed);
+                }
+                TaskKind::Parity => {
+                    let time = std::time::Instant::now();
+                    info!(
+                        "starting DPA learner for task {i} {:?}",
+                        dir.to_string_lossy()
+                    );
+                    match sprout(sample, MinEvenParityCondition) {
+                        Ok(learned) => {
+                            let elapsed = time.elapsed();
+                            info!(
+                                "task {i} \"{:?}\" learning took {} ms",
+                                dir.to_string_lossy(),
+                                elapsed.as_millis()
+                            );
+                            export_automaton(
+                                format!("{}/learned.hoa", dir.to_str().unwrap()),
+                                &learned,
+                            );
+                            export_sprout_result(dir, &learned, elapsed, false);
+                        }
+                        Err(SproutError::Threshold {
+                            thres: _thres,
+                            aut: learned,
+                        }) => {
+                            let elapsed = time.elapsed();
+                            info!(
+                                "task {i} \"{:?}\" exceeded threshold after {} ms",
+                                dir.to_string_lossy(),
+                                elapsed.as_millis()
+                            );
+                            export_automaton(
+                                format!("{}/learned.hoa", dir.to_str().unwrap()),
+                                &learned,
+                            );
+                            export_sprout_result(dir, &learned, elapsed, true);
+                        }
+                        Err(SproutError::Timeout { aut: partial }) => {
+                            let elapsed = time.elapsed();
+                            info!(
+                                "exceeded timeout on task {i} with partial ts of size {}: {:?}",
+                                partial.size(),
+                                dir.to_string_lossy()
+                            );
+                            export_sprout_timeout(dir, partial, elapsed);
+                        }
                     }
                 }
             }
         });
 }
 
+/// Runs sprout on every task in `data/tasks` in `k`-fold cross-validation mode: the task's
+/// training and test words are pooled back together, shuffled once with a seed derived from
+/// `seed`, and assigned round-robin (shuffled index `i` goes to fold `i % k`) into `k` roughly
+/// equal folds. For each fold `f`, sprout is trained on the union of the other `k - 1` folds and
+/// scored on fold `f`, giving a per-fold `scored_correct%`/`pos_correct%`/`neg_correct%`. The raw
+/// per-fold rows together with the mean and sample standard deviation across folds are written to
+/// `result.csv`, replacing the single fixed train/test split result that `run_sprout` produces.
+pub fn run_sprout_cv(k: usize, seed: u64) {
+    let task_dirs = list_task_dirs();
+
+    task_dirs.into_par_iter().enumerate().for_each(|(i, dir)| {
+        if dir.join("result.csv").exists() {
+            info!("already done for task {i} {:?}", dir.to_string_lossy());
+            return;
+        }
+
+        let kind = load_task_kind(&dir);
+        if matches!(
+            kind,
+            TaskKind::CoBuchi | TaskKind::GeneralizedBuchi | TaskKind::Rabin | TaskKind::Streett
+        ) {
+            warn!(
+                "task {i} {:?} requests an acceptance family with no sprout(...) instantiation \
+                 available yet, skipping",
+                dir.to_string_lossy()
+            );
+            return;
+        }
+
+        let pool = load_pool(&dir);
+        let folds = kfold_partition(pool, k, seed.wrapping_add(i as u64));
+
+        let time = std::time::Instant::now();
+        info!(
+            "starting {}-fold cross-validation for task {i} {:?}",
+            k,
+            dir.to_string_lossy()
+        );
+        let fold_stats: Vec<FoldStats> = (0..k)
+            .map(|f| {
+                let test_fold = &folds[f];
+                let (train_pos, train_neg): (Vec<_>, Vec<_>) = folds
+                    .iter()
+                    .enumerate()
+                    .filter(|(j, _)| *j != f)
+                    .flat_map(|(_, fold)| fold.iter().cloned())
+                    .partition_map(|(w, label)| {
+                        if label {
+                            Either::Left(w)
+                        } else {
+                            Either::Right(w)
+                        }
+                    });
+                let alphabet = CharAlphabet::of_size(2);
+                let sample = OmegaSample::new_omega_from_pos_neg(alphabet, train_pos, train_neg);
+
+                if kind == TaskKind::Buchi {
+                    match sprout(sample, BuchiCondition) {
+                        Ok(learned) => score_pool(&learned, test_fold),
+                        Err(SproutError::Threshold { aut: learned, .. }) => {
+                            score_pool(&learned, test_fold)
+                        }
+                        Err(SproutError::Timeout { .. }) => FoldStats::default(),
+                    }
+                } else {
+                    match sprout(sample, MinEvenParityCondition) {
+                        Ok(learned) => score_pool(&learned, test_fold),
+                        Err(SproutError::Threshold { aut: learned, .. }) => {
+                            score_pool(&learned, test_fold)
+                        }
+                        Err(SproutError::Timeout { .. }) => FoldStats::default(),
+                    }
+                }
+            })
+            .collect();
+        let elapsed = time.elapsed();
+        info!(
+            "task {i} \"{:?}\" {}-fold cross-validation took {} ms",
+            dir.to_string_lossy(),
+            k,
+            elapsed.as_millis()
+        );
+
+        export_cv_result(&dir, &fold_stats, elapsed);
+    });
+}
+
+/// Loads the full pool of labelled words backing a task, as the union of `train.csv` and
+/// `test.csv`, so that it can be re-partitioned for cross-validation.
+fn load_pool(dir: &std::path::Path) -> Vec<(ReducedOmegaWord<char>, bool)> {
+    let mut pool = vec![];
+    for file in ["train.csv", "test.csv"] {
+        let (pos, neg) = load_set(dir, file.to_string());
+        pool.extend(pos.into_iter().map(|w| (w, true)));
+        pool.extend(neg.into_iter().map(|w| (w, false)));
+    }
+    pool
+}
+
+/// Partitions `pool` into `k` roughly equal folds: `pool` is shuffled once with an RNG seeded
+/// from `seed`, and the word at shuffled position `i` is assigned to fold `i % k`.
+fn kfold_partition<T>(mut pool: Vec<T>, k: usize, seed: u64) -> Vec<Vec<T>> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    pool.shuffle(&mut rng);
+    let mut folds: Vec<Vec<T>> = (0..k).map(|_| Vec::new()).collect();
+    for (i, item) in pool.into_iter().enumerate() {
+        folds[i % k].push(item);
+    }
+    folds
+}
+
+/// The scoring statistics of a single cross-validation fold.
+#[derive(Debug, Default, Clone, Copy)]
+struct FoldStats {
+    learned_aut_size: usize,
+    scored_correct_pct: f64,
+    pos_correct_pct: f64,
+    neg_correct_pct: f64,
+}
+
+/// Scores `learned` against the held-out `fold` and computes its [`FoldStats`].
+fn score_pool<Z, C>(
+    learned: &InfiniteWordAutomaton<CharAlphabet, Z, Void, C, true>,
+    fold: &[(ReducedOmegaWord<char>, bool)],
+) -> FoldStats
+where
+    Z: Semantics<DTS<CharAlphabet, Void, C>, true, Output = bool>,
+    Z::Observer: InfiniteObserver<DTS<CharAlphabet, Void, C>>,
+    C: Color,
+{
+    let pos_count = fold.iter().filter(|(_, label)| *label).count();
+    let neg_count = fold.len() - pos_count;
+    let pos_correct = fold
+        .iter()
+        .filter(|(_, label)| *label)
+        .filter(|(w, _)| learned.accepts(w))
+        .count();
+    let neg_correct = fold
+        .iter()
+        .filter(|(_, label)| !*label)
+        .filter(|(w, _)| !learned.accepts(w))
+        .count();
+    FoldStats {
+        learned_aut_size: learned.size(),
+        scored_correct_pct: (pos_correct + neg_correct) as f64 / fold.len() as f64,
+        pos_correct_pct: if pos_count == 0 {
+            f64::NAN
+        } else {
+            pos_correct as f64 / pos_count as f64
+        },
+        neg_correct_pct: if neg_count == 0 {
+            f64::NAN
+        } else {
+            neg_correct as f64 / neg_count as f64
+        },
+    }
+}
+
+/// Returns the mean and sample standard deviation (Bessel-corrected, `0.0` for fewer than two
+/// samples) of `xs`, ignoring any `NaN` entries.
+fn mean_std(xs: &[f64]) -> (f64, f64) {
+    let xs: Vec<f64> = xs.iter().copied().filter(|x| !x.is_nan()).collect();
+    if xs.is_empty() {
+        return (f64::NAN, f64::NAN);
+    }
+    let mean = xs.iter().sum::<f64>() / xs.len() as f64;
+    if xs.len() < 2 {
+        return (mean, 0.0);
+    }
+    let variance = xs.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (xs.len() - 1) as f64;
+    (mean, variance.sqrt())
+}
+
+/// Writes the raw per-fold rows and the mean/sample-standard-deviation summary of a
+/// cross-validation run to `result.csv`.
+fn export_cv_result(task_dir: &std::path::Path, folds: &[FoldStats], elapsed: Duration) {
+    let mut wtr = Writer::from_path(task_dir.join("result.csv")).expect("creating file failed");
+    wtr.write_record([
+        "fold",
+        "learned_aut_size",
+        "scored_correct%",
+        "pos_correct%",
+        "neg_correct%",
+    ])
+    .unwrap();
+    for (f, stats) in folds.iter().enumerate() {
+        wtr.write_record([
+            format!("{f}"),
+            format!("{}", stats.learned_aut_size),
+            format!("{}", stats.scored_correct_pct),
+            format!("{}", stats.pos_correct_pct),
+            format!("{}", stats.neg_correct_pct),
+        ])
+        .unwrap();
+    }
+
+    let (scored_mean, scored_std) = mean_std(
+        &folds
+            .iter()
+            .map(|s| s.scored_correct_pct)
+            .collect::<Vec<_>>(),
+    );
+    let (pos_mean, pos_std) =
+        mean_std(&folds.iter().map(|s| s.pos_correct_pct).collect::<Vec<_>>());
+    let (neg_mean, neg_std) =
+        mean_std(&folds.iter().map(|s| s.neg_correct_pct).collect::<Vec<_>>());
+    wtr.write_record(["scored_correct%_mean", &format!("{scored_mean}")])
+        .unwrap();
+    wtr.write_record(["scored_correct%_std", &format!("{scored_std}")])
+        .unwrap();
+    wtr.write_record(["pos_correct%_mean", &format!("{pos_mean}")])
+        .unwrap();
+    wtr.write_record(["pos_correct%_std", &format!("{pos_std}")])
+        .unwrap();
+    wtr.write_record(["neg_correct%_mean", &format!("{neg_mean}")])
+        .unwrap();
+    wtr.write_record(["neg_correct%_std", &format!("{neg_std}")])
+        .unwrap();
+    wtr.write_record(["time_ms", &format!("{}", elapsed.as_millis())])
+        .unwrap();
+    wtr.flush().unwrap();
+}
+
+/// A task's outcome as parsed from its `settings.txt`/`result.csv`, the unit that
+/// [`aggregate_results`] groups and summarizes across the whole sweep of `automata_sizes`,
+/// `train_sizes` and `num_sets`.
+#[derive(Debug, Clone)]
+struct TaskOutcome {
+    aut_type: TaskKind,
+    aut_size: usize,
+    train_size: usize,
+    /// `None` if the task timed out before a test-set score could be computed.
+    scored_correct_pct: Option<f64>,
+    /// Ratio of the learned automaton's size to the target `aut_size`, `None` on timeout.
+    size_ratio: Option<f64>,
+    timed_out: bool,
+    thresholded: bool,
+    time_ms: u64,
+}
+
+/// Parses the [`TaskOutcome`] of the task stored at `dir`. Returns `None` (with a warning) if the
+/// task has not produced a `result.csv` yet, so that `aggregate_results` can skip it.
+fn load_task_outcome(dir: &std::path::Path) -> Option<TaskOutcome> {
+    if !dir.join("result.csv").exists() {
+        warn!(
+            "task {:?} has no result.csv yet, skipping in aggregate",
+            dir.to_string_lossy()
+        );
+        return None;
+    }
+
+    let settings = load_settings(dir);
+    let aut_type = TaskKind::parse(settings.get("aut_type")?)?;
+    let aut_size: usize = settings.get("aut_size")?.parse().ok()?;
+    let train_size: usize = settings.get("train_size")?.parse().ok()?;
+
+    let result = load_kv_csv(&dir.join("result.csv"));
+    let timed_out =
+        result.contains_key("abort_automaton_size") && !result.contains_key("learned_aut_size");
+    let scored_correct_pct = result.get("scored_correct%").and_then(|s| s.parse().ok());
+    let size_ratio = result
+        .get("learned_aut_size")
+        .and_then(|s| s.parse::<f64>().ok())
+        .map(|learned| learned / aut_size as f64);
+    let thresholded = result.get("thresholded").is_some_and(|s| s == "true");
+    let time_ms = result.get("time_ms").and_then(|s| s.parse().ok()).unwrap_or(0);
+
+    Some(TaskOutcome {
+        aut_type,
+        aut_size,
+        train_size,
+        scored_correct_pct,
+        size_ratio,
+        timed_out,
+        thresholded,
+        time_ms,
+    })
+}
+
+/// Returns the (ignoring `NaN`) minimum and maximum of `xs`, or `(NaN, NaN)` if `xs` has no
+/// non-`NaN` entries.
+fn min_max(xs: &[f64]) -> (f64, f64) {
+    let xs: Vec<f64> = xs.iter().copied().filter(|x| !x.is_nan()).collect();
+    if xs.is_empty() {
+        return (f64::NAN, f64::NAN);
+    }
+    (
+        xs.iter().copied().fold(f64::INFINITY, f64::min),
+        xs.iter().copied().fold(f64::NEG_INFINITY, f64::max),
+    )
+}
+
+/// Returns the (ignoring `NaN`) median of `xs`, or `NaN` if `xs` has no non-`NaN` entries.
+fn median(xs: &[f64]) -> f64 {
+    let mut xs: Vec<f64> = xs.iter().copied().filter(|x| !x.is_nan()).collect();
+    if xs.is_empty() {
+        return f64::NAN;
+    }
+    xs.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    let mid = xs.len() / 2;
+    if xs.len() % 2 == 0 {
+        (xs[mid - 1] + xs[mid]) / 2.0
+    } else {
+        xs[mid]
+    }
+}
+
+/// Walks `data/tasks`, parses every task's `settings.txt`/`result.csv` into a [`TaskOutcome`] and
+/// writes two reports:
+///
+/// - `data/aggregate_summary.csv`: one row per `(aut_type, aut_size, train_size)` group with the
+///   sample count, mean/std/min/max of `scored_correct%` and of the learned-vs-target automaton
+///   size ratio, the timeout and threshold counts, and the median `time_ms`.
+/// - `data/learning_curve.csv`: a tidy long-format CSV with one row per completed task
+///   (`aut_type`, `aut_size`, `train_size`, `scored_correct%`), suitable for plotting accuracy as
+///   a function of `train_size` for each `aut_size`.
+pub fn aggregate_results() {
+    let task_dirs = list_task_dirs();
+    let outcomes: Vec<TaskOutcome> = task_dirs
+        .iter()
+        .filter_map(|dir| load_task_outcome(dir))
+        .collect();
+
+    let mut groups: std::collections::BTreeMap<(TaskKind, usize, usize), Vec<&TaskOutcome>> =
+        std::collections::BTreeMap::new();
+    for outcome in outcomes.iter() {
+        groups
+            .entry((outcome.aut_type, outcome.aut_size, outcome.train_size))
+            .or_default()
+            .push(outcome);
+    }
+
+    let mut summary =
+        Writer::from_path("data/aggregate_summary.csv").expect("creating file failed");
+    summary
+        .write_record([
+            "aut_type",
+            "aut_size",
+            "train_size",
+            "n",
+            "scored_correct%_mean",
+            "scored_correct%_std",
+            "scored_correct%_min",
+            "scored_correct%_max",
+            "size_ratio_mean",
+            "size_ratio_std",
+            "size_ratio_min",
+            "size_ratio_max",
+            "timeout_count",
+            "threshold_count",
+            "time_ms_median",
+        ])
+        .unwrap();
+    for ((kind, aut_size, train_size), tasks) in groups.iter() {
+        let scored: Vec<f64> = tasks.iter().filter_map(|t| t.scored_correct_pct).collect();
+        let ratios: Vec<f64> = tasks.iter().filter_map(|t| t.size_ratio).collect();
+        let (scored_mean, scored_std) = mean_std(&scored);
+        let (scored_min, scored_max) = min_max(&scored);
+        let (ratio_mean, ratio_std) = mean_std(&ratios);
+        let (ratio_min, ratio_max) = min_max(&ratios);
+        let timeout_count = tasks.iter().filter(|t| t.timed_out).count();
+        let threshold_count = tasks.iter().filter(|t| t.thresholded).count();
+        let time_median = median(
+            &tasks
+                .iter()
+                .map(|t| t.time_ms as f64)
+                .collect::<Vec<_>>(),
+        );
+        summary
+            .write_record([
+                kind.as_str().to_string(),
+                format!("{aut_size}"),
+                format!("{train_size}"),
+                format!("{}", tasks.len()),
+                format!("{scored_mean}"),
+                format!("{scored_std}"),
+                format!("{scored_min}"),
+                format!("{scored_max}"),
+                format!("{ratio_mean}"),
+                format!("{ratio_std}"),
+                format!("{ratio_min}"),
+                format!("{ratio_max}"),
+                format!("{timeout_count}"),
+                format!("{threshold_count}"),
+                format!("{time_median}"),
+            ])
+            .unwrap();
+    }
+    summary.flush().unwrap();
+
+    let mut curve = Writer::from_path("data/learning_curve.csv").expect("creating file failed");
+    curve
+        .write_record(["aut_type", "aut_size", "train_size", "scored_correct%"])
+        .unwrap();
+    let mut curve_rows = 0;
+    for outcome in outcomes.iter().filter(|o| o.scored_correct_pct.is_some()) {
+        curve_rows += 1;
+        curve
+            .write_record([
+                outcome.aut_type.as_str().to_string(),
+                format!("{}", outcome.aut_size),
+                format!("{}", outcome.train_size),
+                format!("{}", outcome.scored_correct_pct.unwrap()),
+            ])
+            .unwrap();
+    }
+    curve.flush().unwrap();
+
+    info!(
+        "wrote aggregate_summary.csv ({} groups) and learning_curve.csv ({} rows)",
+        groups.len(),
+        curve_rows
+    );
+}
+
 /// Generate a sample of ultimately periodic words by loading the training set from
 /// the learning task located in the given dircetory.
 pub fn load_sample(dir: PathBuf) -> OmegaSample {
@@ -205,7 +721,185 @@ pub fn load_sample(dir: PathBuf) -> OmegaSample {
     OmegaSample::new_omega_from_pos_neg(alphabet, pos_words, neg_words)
 }
 
+/// A curated parameterized family of canonical structured ω-languages (e.g. "a occurs infinitely
+/// often", counting/threshold properties), built by [`explore_states`] rather than
+/// `generate_random_dba`/`generate_random_dpa`, and mixed into [`generate_tasks`] alongside the
+/// random automata so the benchmark can report accuracy separately on structured vs random
+/// targets.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StructuredFamily {
+    /// `GF a`: infinitely many occurrences of `a`.
+    InfOftenA,
+    /// `FG b`: eventually only `b`s occur from some point on — the canonical language that no
+    /// deterministic Büchi automaton can recognize.
+    EventuallyAlwaysB,
+    /// Between every two consecutive `b`s, an even number of `a`s occurs.
+    EvenAsBetweenBs,
+    /// `GF (a^n)`: infinitely often, a run of at least `n` consecutive `a`s occurs.
+    ConsecutiveAsThreshold(usize),
+}
+
+impl StructuredFamily {
+    /// The `family` value persisted to a task's `settings.txt`.
+    pub fn tag(self) -> String {
+        match self {
+            StructuredFamily::InfOftenA => "inf-often-a".to_string(),
+            StructuredFamily::EventuallyAlwaysB => "eventually-always-b".to_string(),
+            StructuredFamily::EvenAsBetweenBs => "even-as-between-bs".to_string(),
+            StructuredFamily::ConsecutiveAsThreshold(n) => format!("consecutive-as-threshold-{n}"),
+        }
+    }
+}
+
+/// Explores the deterministic state space of a residual function starting from `initial`,
+/// labelling every discovered transition with the color of the state it leads into, and returns
+/// the resulting edge list in the `(from, symbol, color, to)` shape [`TSBuilder::with_transitions`]
+/// expects. This mirrors the automaton-from-predicate construction used by digit-DP style
+/// automaton DSLs: a state is any residual value, `step` computes its successor on a symbol, and
+/// `color` assigns the acceptance color of a state.
+fn explore_states<S, C>(
+    alphabet: &CharAlphabet,
+    initial: S,
+    mut step: impl FnMut(&S, char) -> S,
+    mut color: impl FnMut(&S) -> C,
+) -> Vec<(usize, char, C, usize)>
+where
+    S: Eq + std::hash::Hash + Clone,
+{
+    let symbols: Vec<char> = alphabet.universe().collect();
+    let mut ids = HashMap::new();
+    ids.insert(initial.clone(), 0usize);
+    let mut queue = std::collections::VecDeque::from([initial]);
+    let mut edges = vec![];
+    while let Some(state) = queue.pop_front() {
+        let from = ids[&state];
+        for &sym in &symbols {
+            let succ = step(&state, sym);
+            let to = if let Some(&id) = ids.get(&succ) {
+                id
+            } else {
+                let id = ids.len();
+                ids.insert(succ.clone(), id);
+                queue.push_back(succ.clone());
+                id
+            };
+            edges.push((from, sym, color(&succ), to));
+        }
+    }
+    edges
+}
+
+/// `GF a`: infinitely many occurrences of `a`. A two-state DBA whose states track whether the
+/// symbol just read was `a`, with every edge colored by that same fact.
+fn build_inf_often_a(alphabet: &CharAlphabet) -> DBA {
+    let edges = explore_states(alphabet, false, |_, sym| sym == 'a', |saw_a: &bool| *saw_a);
+    TSBuilder::without_state_colors()
+        .with_transitions(edges)
+        .into_dba(0)
+}
+
+/// `FG b`: eventually only `b`s occur. A DPA whose states track whether the symbol just read was
+/// `b`, colored `2` on `b` and `1` otherwise, so that under min-even parity a run accepts iff it
+/// settles on reading only `b`s from some point on.
+fn build_eventually_always_b(alphabet: &CharAlphabet) -> DPA {
+    let edges = explore_states(
+        alphabet,
+        false,
+        |_, sym| sym == 'b',
+        |last_was_b: &bool| if *last_was_b { 2 } else { 1 },
+    );
+    TSBuilder::without_state_colors()
+        .with_transitions(edges)
+        .into_dpa(0)
+}
+
+/// Between every two consecutive `b`s, an even number of `a`s occurs. A safety property, built as
+/// a DBA whose states track the parity of `a`s seen since the last `b` (`Some(None)` before the
+/// first `b`), with a non-accepting trap entered the moment an odd run is closed off by a `b`.
+fn build_even_as_between_bs(alphabet: &CharAlphabet) -> DBA {
+    let edges = explore_states(
+        alphabet,
+        Some(None),
+        |state: &Option<Option<bool>>, sym| match state {
+            None => None,
+            Some(None) => Some((sym == 'b').then_some(false)),
+            Some(Some(odd)) => {
+                if sym == 'b' {
+                    if *odd { None } else { Some(Some(false)) }
+                } else {
+                    Some(Some(!odd))
+                }
+            }
+        },
+        |state: &Option<Option<bool>>| state.is_some(),
+    );
+    TSBuilder::without_state_colors()
+        .with_transitions(edges)
+        .into_dba(0)
+}
+
+/// `GF (a^n)`: infinitely often, a run of at least `n` consecutive `a`s occurs. A DBA whose states
+/// count the current run length of `a`s, capped at `n`, coloring an edge the moment the count
+/// reaches `n`.
+fn build_consecutive_as_threshold(alphabet: &CharAlphabet, n: usize) -> DBA {
+    let edges = explore_states(
+        alphabet,
+        0usize,
+        move |run: &usize, sym| if sym == 'a' { (*run + 1).min(n) } else { 0 },
+        move |run: &usize| *run == n,
+    );
+    TSBuilder::without_state_colors()
+        .with_transitions(edges)
+        .into_dba(0)
+}
+
+/// The curated library of Büchi-recognizable structured families over a `num_symbols`-letter
+/// alphabet. `aut_size` only scales [`StructuredFamily::ConsecutiveAsThreshold`], whose state
+/// count grows with its threshold, so it lands in the same size bucket as the random DBAs it is
+/// benchmarked against.
+fn structured_dba_families(aut_size: usize, num_symbols: usize) -> Vec<(StructuredFamily, DBA)> {
+    let alphabet = CharAlphabet::of_size(num_symbols);
+    let threshold = aut_size.max(1);
+    vec![
+        (StructuredFamily::InfOftenA, build_inf_often_a(&alphabet)),
+        (
+            StructuredFamily::EvenAsBetweenBs,
+            build_even_as_between_bs(&alphabet),
+        ),
+        (
+            StructuredFamily::ConsecutiveAsThreshold(threshold),
+            build_consecutive_as_threshold(&alphabet, threshold),
+        ),
+    ]
+}
+
+/// The curated library of structured families that are not deterministic-Büchi-recognizable,
+/// built as DPAs.
+fn structured_dpa_families(num_symbols: usize) -> Vec<(StructuredFamily, DPA)> {
+    let alphabet = CharAlphabet::of_size(num_symbols);
+    vec![(
+        StructuredFamily::EventuallyAlwaysB,
+        build_eventually_always_b(&alphabet),
+    )]
+}
+
 /// generate set of learning tasks for DBA and DPA.
+/// Derive a per-stream noise RNG seed from `noise_seed` and a couple of loop indices, following
+/// the same `seed.wrapping_add(i as u64)` convention [`run_sprout_cv`] uses to turn one base seed
+/// into many independent, reproducible streams without sharing a single `StdRng` across threads.
+fn noise_seed_for(noise_seed: u64, aut_size: usize, index: u64) -> u64 {
+    noise_seed
+        .wrapping_add((aut_size as u64).wrapping_mul(1_000_003))
+        .wrapping_add(index)
+}
+
+/// Generates, labels and exports every learning task of the sweep, one automaton at a time: each
+/// automaton is generated, its word sets are generated and labelled, the resulting task(s) are
+/// written to `data/tasks`, and everything is dropped before the next automaton is considered.
+/// Only HOA files and CSVs ever accumulate on disk; the in-memory working set at any instant is
+/// bounded by a single automaton and its word sets, regardless of how large the sweep
+/// (`automata_sizes`, `automata_per_size`, `train_sizes`, `num_sets`) is. Parallelism is over
+/// automaton indices (via `rayon`), not over a pre-collected vector of automata.
 pub fn generate_tasks(
     automata_sizes: Vec<usize>,
     automata_per_size: usize,
@@ -213,6 +907,8 @@ pub fn generate_tasks(
     test_size: usize,
     num_sets: usize,
     lambda: f64,
+    noise_levels: Vec<f64>,
+    noise_seed: u64,
 ) {
     // set parameters
     let num_symbols = 2;
@@ -220,113 +916,160 @@ pub fn generate_tasks(
     fs::create_dir_all("data/automata").unwrap();
     fs::create_dir_all("data/sets").unwrap();
 
-    // generate DBAs
-    info!("generating DBAs");
-    let mut dbas = HashMap::new();
-    for &size in automata_sizes.iter() {
-        let mut auts = vec![];
-        for i in 0..automata_per_size {
-            let dba = generate_dba(num_symbols, size, lambda);
-            export_automaton(aut_name(size, i, "dba".to_string()), &dba);
-            auts.push(dba);
-        }
-        dbas.insert(size, auts);
-    }
-
-    // generate DPAs
-    info!("generating DPAs");
-    let mut dpas = HashMap::new();
-    for &size in automata_sizes.iter() {
-        let mut auts = vec![];
-        for i in 0..automata_per_size {
-            let dpa = generate_dpa(num_symbols, size, num_prios, lambda);
-            export_automaton(aut_name(size, i, "dpa".to_string()), &dpa);
-            auts.push(dpa);
-        }
-        dpas.insert(size, auts);
-    }
+    info!("generating and labelling DBA/DPA tasks");
+    automata_sizes.par_iter().for_each(|&aut_size| {
+        (0..automata_per_size).into_par_iter().for_each(|aut_index| {
+            let mut noise_rng =
+                StdRng::seed_from_u64(noise_seed_for(noise_seed, aut_size, aut_index as u64));
 
-    // generate train and test sets
-    info!("generating word sets");
-    let mut sets_dba = HashMap::new();
-    let mut sets_dpa = HashMap::new();
-    for &aut_size in automata_sizes.iter() {
-        for &train_size in train_sizes.iter() {
-            let mut sets_of_size_dba = vec![];
-            let mut sets_of_size_dpa = vec![];
-            for i in 0..num_sets {
-                // DBA sets
+            let dba = generate_dba(num_symbols, aut_size, lambda);
+            export_automaton(aut_name(aut_size, aut_index, "dba".to_string()), &dba);
+            for &train_size in train_sizes.iter() {
                 let len_spoke = std::cmp::max(8, aut_size);
                 let len_cycle = std::cmp::max(8, aut_size);
-                let (train, test) =
-                    generate_set(num_symbols, len_spoke, len_cycle, train_size, test_size);
-                export_set(set_name(aut_size, train_size, i, true, "dba"), &train);
-                export_set(set_name(aut_size, train_size, i, false, "dba"), &test);
-                sets_of_size_dba.push((train, test));
-                // DPA sets
-                let len_spoke = 2 * ((aut_size as f64).log2().ceil() as usize) - 1;
-                let len_cycle = (2 * aut_size - len_spoke) * len_spoke;
-                let (train, test) =
-                    generate_set(num_symbols, len_spoke, len_cycle, train_size, test_size);
-                export_set(set_name(aut_size, train_size, i, true, "dpa"), &train);
-                export_set(set_name(aut_size, train_size, i, false, "dpa"), &test);
-                sets_of_size_dpa.push((train, test));
+                for set_index in 0..num_sets {
+                    let (tr, te) =
+                        generate_set(num_symbols, len_spoke, len_cycle, train_size, test_size);
+                    export_set(
+                        set_name(aut_size, aut_index, train_size, set_index, true, "dba"),
+                        &tr,
+                    );
+                    export_set(
+                        set_name(aut_size, aut_index, train_size, set_index, false, "dba"),
+                        &te,
+                    );
+                    let train = label_set(&dba, &tr);
+                    let test = label_set(&dba, &te);
+                    export_noise_sweep(
+                        |p| {
+                            task_name(aut_size, train_size, aut_index, set_index, "dba".to_string(), p)
+                        },
+                        TaskKind::Buchi,
+                        "random",
+                        &dba,
+                        &train,
+                        &test,
+                        &noise_levels,
+                        &mut noise_rng,
+                    );
+                }
             }
-            sets_dba.insert((aut_size, train_size), sets_of_size_dba);
-            sets_dpa.insert((aut_size, train_size), sets_of_size_dpa);
-        }
-    }
+            drop(dba);
 
-    // label dba sets
-    info!("labelling dba sets");
-    for &aut_size in automata_sizes.iter() {
-        for (aut_index, dba) in dbas[&aut_size].iter().enumerate() {
+            let dpa = generate_dpa(num_symbols, aut_size, num_prios, lambda);
+            export_automaton(aut_name(aut_size, aut_index, "dpa".to_string()), &dpa);
+            let len_spoke = 2 * ((aut_size as f64).log2().ceil() as usize) - 1;
+            let len_cycle = (2 * aut_size - len_spoke) * len_spoke;
             for &train_size in train_sizes.iter() {
-                for (set_index, (tr, te)) in sets_dba[&(aut_size, train_size)].iter().enumerate() {
-                    let train = label_set(dba, tr);
-                    let test = label_set(dba, te);
-                    // export as learning task
-                    export_task(
-                        task_name(
-                            aut_size,
-                            train_size,
-                            aut_index,
-                            set_index,
-                            "dba".to_string(),
-                        ),
-                        dba,
+                for set_index in 0..num_sets {
+                    let (tr, te) =
+                        generate_set(num_symbols, len_spoke, len_cycle, train_size, test_size);
+                    export_set(
+                        set_name(aut_size, aut_index, train_size, set_index, true, "dpa"),
+                        &tr,
+                    );
+                    export_set(
+                        set_name(aut_size, aut_index, train_size, set_index, false, "dpa"),
+                        &te,
+                    );
+                    let train = label_set(&dpa, &tr);
+                    let test = label_set(&dpa, &te);
+                    export_noise_sweep(
+                        |p| {
+                            task_name(aut_size, train_size, aut_index, set_index, "dpa".to_string(), p)
+                        },
+                        TaskKind::Parity,
+                        "random",
+                        &dpa,
                         &train,
                         &test,
+                        &noise_levels,
+                        &mut noise_rng,
+                    );
+                }
+            }
+        });
+    });
+
+    // Structured targets are curated per `aut_size`, not per automaton index, so they stream in a
+    // second pass: one family automaton is built at a time, its word sets are generated, labelled
+    // and exported, then dropped before the next family is considered.
+    info!("labelling structured family tasks");
+    automata_sizes.par_iter().for_each(|&aut_size| {
+        let mut noise_rng =
+            StdRng::seed_from_u64(noise_seed_for(noise_seed, aut_size, automata_per_size as u64));
+
+        for (family, dba) in structured_dba_families(aut_size, num_symbols) {
+            let len_spoke = std::cmp::max(8, aut_size);
+            let len_cycle = std::cmp::max(8, aut_size);
+            for &train_size in train_sizes.iter() {
+                for set_index in 0..num_sets {
+                    let (tr, te) =
+                        generate_set(num_symbols, len_spoke, len_cycle, train_size, test_size);
+                    let train = label_set(&dba, &tr);
+                    let test = label_set(&dba, &te);
+                    export_noise_sweep(
+                        |p| task_name(aut_size, train_size, 0, set_index, family.tag(), p),
+                        TaskKind::Buchi,
+                        &family.tag(),
+                        &dba,
+                        &train,
+                        &test,
+                        &noise_levels,
+                        &mut noise_rng,
                     );
                 }
             }
         }
-    }
 
-    // label dpa sets
-    info!("labelling dpa sets");
-    for &aut_size in automata_sizes.iter() {
-        for (aut_index, dpa) in dpas[&aut_size].iter().enumerate() {
+        for (family, dpa) in structured_dpa_families(num_symbols) {
+            let len_spoke = 2 * ((aut_size as f64).log2().ceil() as usize) - 1;
+            let len_cycle = (2 * aut_size - len_spoke) * len_spoke;
             for &train_size in train_sizes.iter() {
-                for (set_index, (tr, te)) in sets_dpa[&(aut_size, train_size)].iter().enumerate() {
-                    let train = label_set(dpa, tr);
-                    let test = label_set(dpa, te);
-                    // export as learning task
-                    export_task(
-                        task_name(
-                            aut_size,
-                            train_size,
-                            aut_index,
-                            set_index,
-                            "dpa".to_string(),
-                        ),
-                        dpa,
+                for set_index in 0..num_sets {
+                    let (tr, te) =
+                        generate_set(num_symbols, len_spoke, len_cycle, train_size, test_size);
+                    let train = label_set(&dpa, &tr);
+                    let test = label_set(&dpa, &te);
+                    export_noise_sweep(
+                        |p| task_name(aut_size, train_size, 0, set_index, family.tag(), p),
+                        TaskKind::Parity,
+                        &family.tag(),
+                        &dpa,
                         &train,
                         &test,
+                        &noise_levels,
+                        &mut noise_rng,
                     );
                 }
             }
         }
+    });
+}
+
+/// Exports one task per `p` in `noise_levels` for a single `(aut_size, train_size, set_index)`
+/// slot: the clean task at `p == 0.0` is exported as-is, and each nonzero `p` additionally flips
+/// `train`'s labels via [`flip_labels`], recording the pre-flip labels in `train_clean.csv` so
+/// `export_sprout_result` can later separate memorization of the noisy labels from generalization
+/// to the true target.
+#[allow(clippy::too_many_arguments)]
+fn export_noise_sweep<AUT: WriteHoa>(
+    name: impl Fn(f64) -> String,
+    kind: TaskKind,
+    family: &str,
+    aut: &AUT,
+    train: &[(ReducedOmegaWord<char>, bool)],
+    test: &[(ReducedOmegaWord<char>, bool)],
+    noise_levels: &[f64],
+    noise_rng: &mut StdRng,
+) {
+    for &p in noise_levels {
+        if p <= 0.0 {
+            export_task(name(p), kind, family, 0.0, aut, train, test, None);
+        } else {
+            let noisy_train = flip_labels(train, p, noise_rng);
+            export_task(name(p), kind, family, p, aut, &noisy_train, test, Some(train));
+        }
     }
 }
 
@@ -411,6 +1154,24 @@ where
         .collect()
 }
 
+/// Independently flips the boolean label of each word of `labelled` with probability `p`, used to
+/// study how `sprout` degrades under imperfect supervision. The test set is never passed through
+/// this function; `export_sprout_result` reports accuracy against the pre-flip labels too, so that
+/// memorization of the noisy labels can be told apart from generalization to the true target.
+pub fn flip_labels(
+    labelled: &[(ReducedOmegaWord<char>, bool)],
+    p: f64,
+    rng: &mut StdRng,
+) -> Vec<(ReducedOmegaWord<char>, bool)> {
+    labelled
+        .iter()
+        .map(|(w, label)| {
+            let label = if rng.random_bool(p) { !label } else { *label };
+            (w.clone(), label)
+        })
+        .collect()
+}
+
 /// Write the given automaton to the given `path` in HOA format
 pub fn export_automaton<AUT: WriteHoa>(file: String, aut: &AUT) {
     fs::write(file, aut.to_hoa()).expect("Unable to write file");
@@ -455,6 +1216,7 @@ pub fn load_set(
 /// Give filename for a set of omega words
 pub fn set_name(
     aut_size: usize,
+    aut_index: usize,
     set_size: usize,
     set_index: usize,
     train: bool,
@@ -462,7 +1224,7 @@ pub fn set_name(
 ) -> String {
     let class = if train { "train" } else { "test" };
     format!(
-        "data/sets/word_set__{acc_type}__aut_size={aut_size}__sample_size={set_size}__{set_index:0>2}_{class}.csv"
+        "data/sets/word_set__{acc_type}__aut_size={aut_size}__{aut_index:0>2}__sample_size={set_size}__{set_index:0>2}_{class}.csv"
     )
 }
 
@@ -480,12 +1242,25 @@ pub fn export_labelled_set(file: String, set: &[(ReducedOmegaWord<char>, bool)])
     wtr.flush().unwrap();
 }
 
-/// Write the given omega automata learning task to the given `path` in HOA format
+/// Write the given omega automata learning task to the given `path` in HOA format. `family` is
+/// persisted to `settings.txt` as `"random"` for targets drawn from
+/// `generate_random_dba`/`generate_random_dpa`, or the [`StructuredFamily::tag`] of a curated
+/// structured target, so the benchmark can report accuracy separately on structured vs random
+/// tasks.
+/// `label_noise` is the flip probability [`flip_labels`] was run with to produce `train` (`0.0`
+/// for clean training labels), persisted to `settings.txt` alongside `family`. When `train` was
+/// flipped, `clean_train` should hold the pre-flip labels; they are written to `train_clean.csv`
+/// so `export_sprout_result` can later score against them separately from the noisy labels sprout
+/// actually trained on.
 pub fn export_task<AUT: WriteHoa>(
     name: String,
+    kind: TaskKind,
+    family: &str,
+    label_noise: f64,
     aut: &AUT,
     train: &[(ReducedOmegaWord<char>, bool)],
     test: &[(ReducedOmegaWord<char>, bool)],
+    clean_train: Option<&[(ReducedOmegaWord<char>, bool)]>,
 ) {
     // remove old results if they exist
     let _ = fs::remove_dir_all(format!("data/tasks/{name}"));
@@ -494,9 +1269,15 @@ pub fn export_task<AUT: WriteHoa>(
     export_automaton(format!("data/tasks/{name}/aut.hoa"), aut);
     export_labelled_set(format!("data/tasks/{name}/train.csv"), train);
     export_labelled_set(format!("data/tasks/{name}/test.csv"), test);
+    if let Some(clean_train) = clean_train {
+        export_labelled_set(format!("data/tasks/{name}/train_clean.csv"), clean_train);
+    }
     export_settings(
         format!("data/tasks/{name}/settings.txt"),
         name,
+        kind,
+        family,
+        label_noise,
         aut.alphabet().size(),
         aut.size(),
         train.len(),
@@ -504,30 +1285,43 @@ pub fn export_task<AUT: WriteHoa>(
     );
 }
 
+/// `label_noise` is appended as a `__noise=` suffix when nonzero, so a clean task and its noisy
+/// variants (see [`flip_labels`]) get distinct directory names under `data/tasks`.
 pub fn task_name(
     aut_size: usize,
     set_size: usize,
     aut_index: usize,
     set_index: usize,
     acc_type: String,
+    label_noise: f64,
 ) -> String {
-    format!(
+    let base = format!(
         "{acc_type}_task__aut_size={aut_size:0>2}__sample_size={set_size:0>5}__{acc_type}{aut_index:0>2}__sample{set_index:0>2}"
-    )
+    );
+    if label_noise <= 0.0 {
+        base
+    } else {
+        format!("{base}__noise={label_noise:.2}")
+    }
 }
 
 pub fn export_settings(
     file: String,
     name: String,
+    kind: TaskKind,
+    family: &str,
+    label_noise: f64,
     num_symbols: usize,
     aut_size: usize,
     train_size: usize,
     test_size: usize,
 ) {
-    let acc_type = if name.contains("dba") { "dba" } else { "dpa" };
     let mut wtr = Writer::from_path(file).expect("creating file failed");
     wtr.write_record(["name", &name]).unwrap();
-    wtr.write_record(["aut_type", acc_type]).unwrap();
+    wtr.write_record(["aut_type", kind.as_str()]).unwrap();
+    wtr.write_record(["family", family]).unwrap();
+    wtr.write_record(["label_noise", &format!("{label_noise}")])
+        .unwrap();
     wtr.write_record(["num_symbols", &format!("{num_symbols}")])
         .unwrap();
     wtr.write_record(["aut_size", &format!("{aut_size}")])
@@ -557,6 +1351,7 @@ pub fn export_sprout_result<Z, C>(
     task_dir: &std::path::Path,
     learned: &InfiniteWordAutomaton<CharAlphabet, Z, Void, C, true>,
     elapsed: Duration,
+    thresholded: bool,
 ) where
     Z: Semantics<DTS<CharAlphabet, Void, C>, true, Output = bool>,
     Z::Observer: InfiniteObserver<DTS<CharAlphabet, Void, C>>,
@@ -620,6 +1415,25 @@ pub fn export_sprout_result<Z, C>(
     .unwrap();
     wtr.write_record(["time_ms", &format!("{}", elapsed.as_millis())])
         .unwrap();
+    wtr.write_record(["thresholded", &format!("{thresholded}")])
+        .unwrap();
+
+    // if the training labels were flipped with some probability (see `flip_labels`), also score
+    // against the pre-flip `train_clean.csv`, to tell memorization of the noisy labels apart from
+    // generalization to the true target
+    if task_dir.join("train_clean.csv").exists() {
+        let (clean_pos, clean_neg) = load_set(task_dir, "train_clean.csv".to_string());
+        let clean_count = clean_pos.len() + clean_neg.len();
+        let clean_correct = clean_pos.iter().filter(|w| learned.accepts(*w)).count()
+            + clean_neg.iter().filter(|w| !learned.accepts(*w)).count();
+        wtr.write_record(["train_clean_correct", &format!("{clean_correct}")])
+            .unwrap();
+        wtr.write_record([
+            "train_clean_correct%",
+            &format!("{}", clean_correct as f64 / clean_count as f64),
+        ])
+        .unwrap();
+    }
     wtr.flush().unwrap();
     info!(
         "exported sprout result in {} µs",
@@ -0,0 +1,177 @@
+//! An [`Oracle`] combinator that augments equivalence queries with a targeted
+//! neighborhood search around a seed set of words, using a Levenshtein automaton to
+//! enumerate words within a bounded edit distance.
+//!
+//! Plain minimal-representative enumeration (as used by [`super::DFAOracle`] and
+//! friends) explores the product automaton breadth-first from the empty word, which can
+//! take a long time to stumble onto a counterexample that is a small perturbation of a
+//! word the user already cares about (e.g. a sample word). Wrapping an oracle in
+//! [`FuzzingOracle`] instead tries every word within edit distance `d` of each seed first,
+//! falling back to the wrapped oracle's own equivalence search if none of them disagree.
+
+use automata::core::alphabet::Alphabet;
+use automata::core::word::FiniteWord;
+
+use super::{Counterexample, Hypothesis, Oracle};
+
+/// A nondeterministic Levenshtein automaton accepting exactly the words within edit
+/// distance `d` of a fixed word `w`. States are pairs `(i, e)`, `i` the index into `w`
+/// (`0..=w.len()`) and `e` the number of edits used so far (`0..=d`); `(i, e)` is
+/// accepting iff `i == w.len()`. From `(i, e)`:
+/// - matching `w[i]` goes to `(i + 1, e)` (no edit spent);
+/// - any symbol goes to `(i + 1, e + 1)` (substitution) and to `(i, e + 1)` (insertion of
+///   that symbol into the candidate word), provided `e < d`;
+/// - an epsilon-move to `(i + 1, e + 1)` models deleting `w[i]`, provided `e < d` and `i <
+///   w.len()`.
+struct LevenshteinAutomaton<'w, S> {
+    word: &'w [S],
+    d: usize,
+}
+
+impl<'w, S: PartialEq + Clone> LevenshteinAutomaton<'w, S> {
+    fn new(word: &'w [S], d: usize) -> Self {
+        Self { word, d }
+    }
+
+    /// Epsilon-closes `states` over delete-moves and returns the resulting set.
+    fn closure(&self, mut states: Vec<(usize, usize)>) -> Vec<(usize, usize)> {
+        let mut i = 0;
+        while i < states.len() {
+            let (pos, edits) = states[i];
+            if edits < self.d && pos < self.word.len() {
+                let deleted = (pos + 1, edits + 1);
+                if !states.contains(&deleted) {
+                    states.push(deleted);
+                }
+            }
+            i += 1;
+        }
+        states.sort_unstable();
+        states.dedup();
+        states
+    }
+
+    fn initial(&self) -> Vec<(usize, usize)> {
+        self.closure(vec![(0, 0)])
+    }
+
+    fn step(&self, states: &[(usize, usize)], symbol: &S) -> Vec<(usize, usize)> {
+        let mut next = Vec::new();
+        for &(pos, edits) in states {
+            if pos < self.word.len() && &self.word[pos] == symbol {
+                next.push((pos + 1, edits));
+            }
+            if edits < self.d {
+                if pos < self.word.len() {
+                    next.push((pos + 1, edits + 1)); // substitution
+                }
+                next.push((pos, edits + 1)); // insertion of `symbol`
+            }
+        }
+        self.closure(next)
+    }
+
+    fn is_accepting(&self, states: &[(usize, usize)]) -> bool {
+        states.iter().any(|&(pos, _)| pos == self.word.len())
+    }
+
+    /// Enumerates every word of length at most `self.word.len() + self.d` accepted by
+    /// this automaton, by exploring the (small, since `d` is small) subset-construction
+    /// breadth-first and bounding the search depth.
+    fn enumerate(&self, alphabet_symbols: &[S]) -> Vec<Vec<S>> {
+        let max_len = self.word.len() + self.d;
+        let mut results = Vec::new();
+        let mut frontier = vec![(Vec::new(), self.initial())];
+        if self.is_accepting(&frontier[0].1) {
+            results.push(Vec::new());
+        }
+        for _ in 0..max_len {
+            let mut next_frontier = Vec::new();
+            for (word, states) in &frontier {
+                for symbol in alphabet_symbols {
+                    let next_states = self.step(states, symbol);
+                    if next_states.is_empty() {
+                        continue;
+                    }
+                    let mut extended = word.clone();
+                    extended.push(symbol.clone());
+                    if self.is_accepting(&next_states) {
+                        results.push(extended.clone());
+                    }
+                    next_frontier.push((extended, next_states));
+                }
+            }
+            frontier = next_frontier;
+        }
+        results
+    }
+}
+
+/// Wraps an [`Oracle`] `O`, extending its `equivalence` queries with a search through the
+/// edit-distance-`d` neighborhoods of a set of `seeds`, before falling back to `O`'s own
+/// equivalence check.
+pub struct FuzzingOracle<O: Oracle> {
+    inner: O,
+    seeds: Vec<Vec<<O::Alphabet as Alphabet>::Symbol>>,
+    d: usize,
+}
+
+impl<O: Oracle> FuzzingOracle<O>
+where
+    <O::Alphabet as Alphabet>::Symbol: Clone,
+{
+    /// Wraps `inner`, searching the edit-distance-`d` neighborhood of each word in
+    /// `seeds` during equivalence queries.
+    pub fn new<W>(inner: O, seeds: impl IntoIterator<Item = W>, d: usize) -> Self
+    where
+        W: FiniteWord<Symbol = <O::Alphabet as Alphabet>::Symbol>,
+    {
+        Self {
+            inner,
+            seeds: seeds
+                .into_iter()
+                .map(|w| w.symbols().collect::<Vec<_>>())
+                .collect(),
+            d,
+        }
+    }
+}
+
+impl<O: Oracle> Oracle for FuzzingOracle<O>
+where
+    <O::Alphabet as Alphabet>::Symbol: Clone + PartialEq,
+{
+    type Alphabet = O::Alphabet;
+    type Output = O::Output;
+
+    fn alphabet(&self) -> &Self::Alphabet {
+        self.inner.alphabet()
+    }
+
+    fn output<W: FiniteWord<Symbol = <Self::Alphabet as Alphabet>::Symbol>>(
+        &self,
+        word: W,
+    ) -> Self::Output {
+        self.inner.output(word)
+    }
+
+    fn equivalence<H>(
+        &self,
+        hypothesis: &H,
+    ) -> Result<(), Counterexample<Self::Alphabet, Self::Output>>
+    where
+        H: Hypothesis<Alphabet = Self::Alphabet, Output = Self::Output>,
+    {
+        let symbols: Vec<_> = self.inner.alphabet().universe().collect();
+        for seed in &self.seeds {
+            let lev = LevenshteinAutomaton::new(seed, self.d);
+            for candidate in lev.enumerate(&symbols) {
+                let expected = self.inner.output(candidate.clone());
+                if expected != hypothesis.output(candidate.clone()) {
+                    return Err((candidate, expected));
+                }
+            }
+        }
+        self.inner.equivalence(hypothesis)
+    }
+}
@@ -0,0 +1,239 @@
+//! An [`Oracle`] driven by a linear-temporal (finite-trace) specification rather than a
+//! hand-built automaton, so the active learners in this module can be pointed directly
+//! at an LTLf property.
+//!
+//! Membership queries are answered by *progressing* the formula one letter at a time
+//! (Bacchus & Kabanza's progression function, also used by LTLf-to-automaton tools such
+//! as Lydia): `prog(f, letter)` returns the obligation that must hold on the remainder of
+//! the word such that `f` holds on `letter · remainder` iff `prog(f, letter)` holds on
+//! `remainder`. This never materializes the automaton for the formula, so membership
+//! queries are cheap even for specifications whose minimal DFA is large.
+//!
+//! Equivalence queries do build an automaton, but lazily: each distinct (simplified)
+//! residual formula reachable from the start formula becomes a state, exactly mirroring
+//! what [`super::DFAOracle`] does with an explicit automaton.
+
+use std::collections::VecDeque;
+
+use automata::automaton::DFA;
+use automata::core::alphabet::Alphabet;
+use automata::core::word::FiniteWord;
+use automata::ts::TSBuilder;
+use automata::ts::operations::Product;
+use automata::{Pointed, TransitionSystem};
+
+use super::{Counterexample, Hypothesis, Oracle};
+
+/// A finite-trace LTL property over atomic propositions of type `P`. `Next` uses *weak*
+/// semantics: `Next(f)` holds vacuously at the end of a trace, so `Always`/`Finally` are
+/// genuinely finite-trace operators (`Always f ≡ f ∧ Next(Always f)`, `Finally f ≡ f ∨
+/// Next(Finally f)`).
+///
+/// [`Property::True`] and [`Property::False`] are not part of the surface syntax a user
+/// writes, but appear as the result of progressing an [`Property::Atomic`] against a
+/// letter, and are kept around (rather than immediately collapsed) so progression stays a
+/// uniform structural rewrite.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Property<P> {
+    /// Always true, regardless of the remaining trace.
+    True,
+    /// Always false, regardless of the remaining trace.
+    False,
+    /// Holds now iff the current letter satisfies the atomic proposition.
+    Atomic(P),
+    Not(Box<Property<P>>),
+    And(Box<Property<P>>, Box<Property<P>>),
+    Or(Box<Property<P>>, Box<Property<P>>),
+    /// `G f`: `f` holds at every position of the remaining trace (including this one).
+    Always(Box<Property<P>>),
+    /// `F f`: `f` holds at some position of the remaining trace (including this one).
+    Finally(Box<Property<P>>),
+    /// `X f`: `f` holds at the next position, or vacuously if there is no next position.
+    Next(Box<Property<P>>),
+}
+
+impl<P: Clone> Property<P> {
+    fn not(self) -> Self {
+        Property::Not(Box::new(self))
+    }
+    fn and(self, other: Self) -> Self {
+        Property::And(Box::new(self), Box::new(other))
+    }
+    fn or(self, other: Self) -> Self {
+        Property::Or(Box::new(self), Box::new(other))
+    }
+
+    /// Collapses the easy absorption/identity laws around [`Property::True`]/
+    /// [`Property::False`] so that semantically trivial residuals don't blow up the
+    /// state space explored during equivalence queries. This is not a full BDD
+    /// normalization, just enough local rewriting to keep obviously-equal residuals
+    /// syntactically equal too.
+    fn simplify(self) -> Self {
+        match self {
+            Property::Not(f) => match f.simplify() {
+                Property::True => Property::False,
+                Property::False => Property::True,
+                Property::Not(g) => *g,
+                g => g.not(),
+            },
+            Property::And(f, g) => match (f.simplify(), g.simplify()) {
+                (Property::False, _) | (_, Property::False) => Property::False,
+                (Property::True, x) | (x, Property::True) => x,
+                (f, g) => f.and(g),
+            },
+            Property::Or(f, g) => match (f.simplify(), g.simplify()) {
+                (Property::True, _) | (_, Property::True) => Property::True,
+                (Property::False, x) | (x, Property::False) => x,
+                (f, g) => f.or(g),
+            },
+            Property::Always(f) => match f.simplify() {
+                Property::True => Property::True,
+                f => Property::Always(Box::new(f)),
+            },
+            Property::Finally(f) => match f.simplify() {
+                Property::False => Property::False,
+                f => Property::Finally(Box::new(f)),
+            },
+            other => other,
+        }
+    }
+
+    /// Progresses `self` across `letter`, where `valuation(letter, p)` decides whether
+    /// atomic proposition `p` holds at `letter`. The result is the residual obligation
+    /// for the remainder of the trace.
+    fn progress(&self, letter: &impl Fn(&P) -> bool) -> Self {
+        match self {
+            Property::True => Property::True,
+            Property::False => Property::False,
+            Property::Atomic(p) => {
+                if letter(p) {
+                    Property::True
+                } else {
+                    Property::False
+                }
+            }
+            Property::Not(f) => f.progress(letter).not(),
+            Property::And(f, g) => f.progress(letter).and(g.progress(letter)),
+            Property::Or(f, g) => f.progress(letter).or(g.progress(letter)),
+            // X f holds on letter·rest iff rest is empty (weak next) or f holds on rest.
+            // `¬F true` holds exactly on the empty trace.
+            Property::Next(f) => (**f)
+                .clone()
+                .or(Property::Finally(Box::new(Property::True)).not()),
+            // G f ≡ f ∧ X(G f); progressing keeps the whole `Always` obligation pending.
+            Property::Always(f) => f.progress(letter).and(Property::Always(f.clone())),
+            // F f ≡ f ∨ X(F f).
+            Property::Finally(f) => f.progress(letter).or(Property::Finally(f.clone())),
+        }
+        .simplify()
+    }
+
+    /// Whether `self` holds on the empty trace, under weak-next semantics.
+    fn accepts_epsilon(&self) -> bool {
+        match self {
+            Property::True => true,
+            Property::False => false,
+            Property::Atomic(_) => false,
+            Property::Not(f) => !f.accepts_epsilon(),
+            Property::And(f, g) => f.accepts_epsilon() && g.accepts_epsilon(),
+            Property::Or(f, g) => f.accepts_epsilon() || g.accepts_epsilon(),
+            Property::Always(_) => true,
+            Property::Finally(_) => false,
+            Property::Next(_) => true,
+        }
+    }
+}
+
+/// An [`Oracle`] answering queries against a finite-trace LTL [`Property`]. `valuation`
+/// decides, for a given letter and atomic proposition, whether that proposition holds.
+pub struct LtlfOracle<A: Alphabet, P> {
+    alphabet: A,
+    formula: Property<P>,
+    valuation: fn(&A::Symbol, &P) -> bool,
+}
+
+impl<A: Alphabet, P> LtlfOracle<A, P> {
+    /// Creates a new oracle for `formula` over `alphabet`, using `valuation` to decide
+    /// whether an atomic proposition holds at a given letter.
+    pub fn new(alphabet: A, formula: Property<P>, valuation: fn(&A::Symbol, &P) -> bool) -> Self {
+        Self {
+            alphabet,
+            formula,
+            valuation,
+        }
+    }
+
+    fn residual<W: FiniteWord<Symbol = A::Symbol>>(&self, word: W) -> Property<P> {
+        word.symbols().fold(self.formula.clone(), |obligation, sym| {
+            obligation.progress(&|p| (self.valuation)(&sym, p))
+        })
+    }
+
+    /// Lazily explores the automaton of residual formulas reachable from the start
+    /// formula and collects it into an explicit [`DFA`].
+    fn residual_automaton(&self) -> DFA<A>
+    where
+        A::Symbol: Ord,
+        P: Clone + PartialEq,
+    {
+        let symbols: Vec<A::Symbol> = self.alphabet.universe().collect();
+        let mut residuals = vec![self.formula.clone().simplify()];
+        let mut queue = VecDeque::from([0usize]);
+        let mut transitions = Vec::new();
+
+        while let Some(idx) = queue.pop_front() {
+            for sym in &symbols {
+                let next = residuals[idx].progress(&|p| (self.valuation)(sym, p));
+                let target = match residuals.iter().position(|r| r == &next) {
+                    Some(pos) => pos,
+                    None => {
+                        residuals.push(next);
+                        let pos = residuals.len() - 1;
+                        queue.push_back(pos);
+                        pos
+                    }
+                };
+                transitions.push((idx, sym.clone(), target));
+            }
+        }
+
+        let state_colors: Vec<bool> = residuals.iter().map(|r| r.accepts_epsilon()).collect();
+        TSBuilder::default()
+            .with_state_colors(state_colors)
+            .with_transitions(transitions)
+            .into_dfa(0)
+    }
+}
+
+impl<A: Alphabet, P: Clone + PartialEq> Oracle for LtlfOracle<A, P>
+where
+    A::Symbol: Ord,
+{
+    type Alphabet = A;
+    type Output = bool;
+
+    fn alphabet(&self) -> &Self::Alphabet {
+        &self.alphabet
+    }
+
+    fn output<W: FiniteWord<Symbol = A::Symbol>>(&self, word: W) -> bool {
+        self.residual(word).accepts_epsilon()
+    }
+
+    fn equivalence<H>(
+        &self,
+        hypothesis: &H,
+    ) -> Result<(), Counterexample<Self::Alphabet, Self::Output>>
+    where
+        H: Hypothesis<Alphabet = Self::Alphabet, Output = Self::Output>,
+    {
+        let automaton = self.residual_automaton();
+        for mr in (&automaton).ts_product(hypothesis).minimal_representatives_iter() {
+            let expected = automaton.accepts(&mr);
+            if expected != hypothesis.output(&mr) {
+                return Err((mr.to_vec(), expected));
+            }
+        }
+        Ok(())
+    }
+}
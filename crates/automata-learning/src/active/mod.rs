@@ -0,0 +1,34 @@
+mod levenshtein;
+mod lstar;
+mod ltlf;
+mod nlstar;
+mod oracle;
+
+pub use levenshtein::FuzzingOracle;
+pub use lstar::{LStar, lstar};
+pub use ltlf::{LtlfOracle, Property};
+pub use nlstar::{NLStar, ResidualHypothesis, nlstar};
+pub use oracle::{
+    CompletingMealyOracle, Counterexample, DFAOracle, MealyOracle, MooreOracle, Oracle,
+    SampleOracle,
+};
+
+use automata::core::Color;
+use automata::core::alphabet::Alphabet;
+use automata::core::word::FiniteWord;
+
+/// A hypothesis produced by an active learner: anything that can answer membership
+/// queries the same way an [`Oracle`] does, so the two can be compared during an
+/// equivalence query.
+pub trait Hypothesis {
+    /// The alphabet over which hypotheses are built.
+    type Alphabet: Alphabet;
+    /// The type of value produced for a given word.
+    type Output: Color;
+
+    /// Computes the output that the hypothesis assigns to `word`.
+    fn output<W: FiniteWord<Symbol = <Self::Alphabet as Alphabet>::Symbol>>(
+        &self,
+        word: W,
+    ) -> Self::Output;
+}
@@ -0,0 +1,391 @@
+//! An NL*-style learner that infers a *residual finite-state automaton* (RFSA) rather
+//! than the minimal DFA/Mealy machine that [`super::lstar`] produces.
+//!
+//! The key observation (Bollig, Habermehl, Kern & Leucker) is that the rows of an
+//! observation table form a [`Lattice`] under the ordering induced by [`Oracle::Output`]:
+//! a row `r` is *composed* if it equals the join of a set of other rows strictly below
+//! it, and *prime* (join-irreducible) otherwise. A residual automaton only needs one
+//! state per prime row rather than one per distinct row, and transitions become
+//! nondeterministic: `s · a` may be the join of several prime rows, in which case the
+//! hypothesis has an edge to every one of them. Since the oracles in this crate already
+//! require `Output: Color + Lattice` (see [`super::MealyOracle`]), this reuses that join
+//! structure directly instead of introducing a separate notion of "compatible" rows.
+//!
+//! The resulting hypotheses can be exponentially smaller than the minimal DFA for
+//! languages with a lot of componentwise structure, at the cost of having to evaluate a
+//! word by joining the outputs of every prime row reachable along *some* nondeterministic
+//! run rather than following a single deterministic path.
+
+use automata::core::Lattice;
+use automata::core::alphabet::Alphabet;
+use automata::core::word::FiniteWord;
+
+use super::{Hypothesis, Oracle};
+
+type Word<O> = Vec<<<O as Oracle>::Alphabet as Alphabet>::Symbol>;
+
+/// An observation table as used by NL*: the same `(S, E, T)` structure as plain L*, but
+/// closedness/consistency are checked against the *prime* rows only (see the module
+/// docs).
+struct ResidualTable<O: Oracle> {
+    s: Vec<Word<O>>,
+    e: Vec<Word<O>>,
+}
+
+impl<O: Oracle> ResidualTable<O>
+where
+    O::Output: Lattice,
+{
+    fn new() -> Self {
+        Self {
+            s: vec![Vec::new()],
+            e: vec![Vec::new()],
+        }
+    }
+
+    fn row(&self, oracle: &O, prefix: &[<O::Alphabet as Alphabet>::Symbol]) -> Vec<O::Output> {
+        self.e
+            .iter()
+            .map(|suffix| {
+                let mut word = prefix.to_vec();
+                word.extend(suffix.iter().cloned());
+                oracle.output(word)
+            })
+            .collect()
+    }
+
+    /// Componentwise join of two rows.
+    fn join_rows(a: &[O::Output], b: &[O::Output]) -> Vec<O::Output> {
+        a.iter().zip(b).map(|(x, y)| x.join(y)).collect()
+    }
+
+    /// Componentwise `<=` (the order induced by the lattice join: `a <= b` iff `a.join(b)
+    /// == b`).
+    fn row_leq(a: &[O::Output], b: &[O::Output]) -> bool {
+        a.iter().zip(b).all(|(x, y)| &x.join(y) == y)
+    }
+
+    /// A row is *composed* if it equals the join of some nonempty set of other rows that
+    /// are each strictly below it; otherwise it is *prime*. We only ever need to tell
+    /// composed rows apart from prime ones among the rows actually present in `rows`, so
+    /// this checks joins of subsets of `rows \ {row}` rather than every row of the table.
+    fn is_composed(row: &[O::Output], rows: &[Vec<O::Output>]) -> bool {
+        let below: Vec<&Vec<O::Output>> = rows
+            .iter()
+            .filter(|candidate| candidate.as_slice() != row && Self::row_leq(candidate, row))
+            .collect();
+        if below.is_empty() {
+            return false;
+        }
+        let joined = below
+            .iter()
+            .skip(1)
+            .fold((*below[0]).clone(), |acc, r| Self::join_rows(&acc, r));
+        joined == row
+    }
+
+    /// Returns the prime rows among `s`'s rows, each paired with one representative
+    /// access word, deduplicated by row value.
+    fn prime_rows(&self, oracle: &O) -> Vec<(Vec<O::Output>, Word<O>)> {
+        let all_rows: Vec<Vec<O::Output>> = self.s.iter().map(|p| self.row(oracle, p)).collect();
+        let mut primes: Vec<(Vec<O::Output>, Word<O>)> = Vec::new();
+        for (prefix, row) in self.s.iter().zip(&all_rows) {
+            if Self::is_composed(row, &all_rows) {
+                continue;
+            }
+            if !primes.iter().any(|(r, _)| r == row) {
+                primes.push((row.clone(), prefix.clone()));
+            }
+        }
+        primes
+    }
+
+    /// RFSA-closedness: every prime row of `S . Sigma` must already be a prime row of
+    /// `S`. Returns an extension of `S` (a one-symbol continuation of some access word)
+    /// witnessing a violation, if any.
+    fn find_closedness_defect(&self, oracle: &O) -> Option<Word<O>> {
+        let symbols: Vec<_> = oracle.alphabet().universe().collect();
+        let primes = self.prime_rows(oracle);
+        for prefix in &self.s {
+            for sym in &symbols {
+                let mut extension = prefix.clone();
+                extension.push(*sym);
+                if self.s.contains(&extension) {
+                    continue;
+                }
+                let row = self.row(oracle, &extension);
+                let all_rows: Vec<Vec<O::Output>> =
+                    self.s.iter().map(|p| self.row(oracle, p)).collect();
+                if Self::is_composed(&row, &all_rows) {
+                    continue;
+                }
+                if !primes.iter().any(|(r, _)| r == &row) {
+                    return Some(extension);
+                }
+            }
+        }
+        None
+    }
+
+    /// RFSA-consistency: appending a symbol to two rows must preserve their `<=` order
+    /// (if `row(u) <= row(v)` then `row(u.a) <= row(v.a)` for every symbol `a`). On a
+    /// violation, the distinguishing suffix `a . e` (for the column `e` where the order
+    /// breaks) is returned so it can be added to `E`.
+    fn find_consistency_defect(&self, oracle: &O) -> Option<Word<O>> {
+        let symbols: Vec<_> = oracle.alphabet().universe().collect();
+        for i in 0..self.s.len() {
+            for j in 0..self.s.len() {
+                if i == j {
+                    continue;
+                }
+                let row_i = self.row(oracle, &self.s[i]);
+                let row_j = self.row(oracle, &self.s[j]);
+                if !Self::row_leq(&row_i, &row_j) {
+                    continue;
+                }
+                for sym in &symbols {
+                    let mut ext_i = self.s[i].clone();
+                    ext_i.push(*sym);
+                    let mut ext_j = self.s[j].clone();
+                    ext_j.push(*sym);
+                    let row_ext_i = self.row(oracle, &ext_i);
+                    let row_ext_j = self.row(oracle, &ext_j);
+                    if Self::row_leq(&row_ext_i, &row_ext_j) {
+                        continue;
+                    }
+                    let idx = row_ext_i
+                        .iter()
+                        .zip(&row_ext_j)
+                        .position(|(a, b)| &a.join(b) != b)
+                        .expect("rows violate <=, so some column must witness it");
+                    let mut suffix = vec![*sym];
+                    suffix.extend(self.e[idx].iter().cloned());
+                    return Some(suffix);
+                }
+            }
+        }
+        None
+    }
+
+    fn saturate(&mut self, oracle: &O) {
+        loop {
+            if let Some(extension) = self.find_closedness_defect(oracle) {
+                self.s.push(extension);
+                continue;
+            }
+            if let Some(suffix) = self.find_consistency_defect(oracle) {
+                if !self.e.contains(&suffix) {
+                    self.e.push(suffix);
+                    continue;
+                }
+            }
+            break;
+        }
+    }
+}
+
+/// A residual hypothesis: a nondeterministic automaton whose states are prime rows.
+/// State `p` has an edge on symbol `a` to every prime row that the (possibly composed)
+/// row of `access(p) . a` is a join of, and [`Hypothesis::output`] for a word joins the
+/// outputs of every prime row reachable via some run on that word, starting from the
+/// (possibly several) initial prime rows that the row of the empty word is a join of.
+pub struct ResidualHypothesis<O: Oracle>
+where
+    O::Output: Lattice,
+{
+    /// One access word and row per prime state, in table-insertion order.
+    states: Vec<(Word<O>, Vec<O::Output>)>,
+    /// `initial[i]` holds iff prime state `i` is one of the joinands of the root row.
+    initial: Vec<bool>,
+    /// The alphabet's symbols, in the order `successors` indexes them.
+    symbols: Vec<<O::Alphabet as Alphabet>::Symbol>,
+    /// `successors[i][a]` lists the prime states whose row is one of the joinands of the
+    /// row of `access(i) . a`, where `a` indexes into `symbols`.
+    successors: Vec<Vec<Vec<usize>>>,
+}
+
+impl<O: Oracle> ResidualHypothesis<O>
+where
+    O::Output: Lattice,
+{
+    /// The prime states whose row is a joinand of `row`.
+    fn joinands_of(states: &[(Word<O>, Vec<O::Output>)], row: &[O::Output]) -> Vec<usize> {
+        let covering: Vec<usize> = states
+            .iter()
+            .enumerate()
+            .filter(|(_, (_, r))| ResidualTable::<O>::row_leq(r, row))
+            .map(|(idx, _)| idx)
+            .collect();
+        // Drop any covering state whose row equals the join of the other covering
+        // rows, since leaving it out does not change the covering set's join.
+        covering
+            .iter()
+            .copied()
+            .filter(|&idx| {
+                let others: Vec<&Vec<O::Output>> = covering
+                    .iter()
+                    .filter(|&&other| other != idx)
+                    .map(|&other| &states[other].1)
+                    .collect();
+                if others.is_empty() {
+                    return true;
+                }
+                let joined = others.iter().skip(1).fold((*others[0]).clone(), |acc, r| {
+                    ResidualTable::<O>::join_rows(&acc, r)
+                });
+                joined != states[idx].1
+            })
+            .collect()
+    }
+}
+
+impl<O: Oracle> Hypothesis for ResidualHypothesis<O>
+where
+    O::Output: Lattice,
+{
+    type Alphabet = O::Alphabet;
+    type Output = O::Output;
+
+    fn output<W: FiniteWord<Symbol = <O::Alphabet as Alphabet>::Symbol>>(
+        &self,
+        word: W,
+    ) -> O::Output {
+        let mut current: Vec<usize> = (0..self.states.len())
+            .filter(|&idx| self.initial[idx])
+            .collect();
+        for sym in word.symbols() {
+            let sym_idx = self
+                .symbols
+                .iter()
+                .position(|s| *s == sym)
+                .expect("word uses a symbol outside the learner's alphabet");
+            let mut next = Vec::new();
+            for &state in &current {
+                for &succ in &self.successors[state][sym_idx] {
+                    if !next.contains(&succ) {
+                        next.push(succ);
+                    }
+                }
+            }
+            current = next;
+        }
+        // Column 0 of every row is always the entry for the empty suffix (`E` is seeded
+        // with `epsilon` and never loses it), i.e. exactly `oracle.output(access)`.
+        current
+            .iter()
+            .map(|&idx| self.states[idx].1[0].clone())
+            .reduce(|acc, next| acc.join(&next))
+            .expect("every word is covered by at least one initial prime state")
+    }
+}
+
+/// Drives [`ResidualTable`] saturation and counterexample processing (adding every
+/// suffix of the counterexample to `E`, the classic NL* strategy) until the oracle
+/// reports no more counterexamples against the current residual hypothesis.
+pub struct NLStar<O: Oracle>
+where
+    O::Output: Lattice,
+{
+    oracle: O,
+    table: ResidualTable<O>,
+}
+
+impl<O: Oracle> NLStar<O>
+where
+    O::Output: Lattice,
+{
+    /// Creates a new residual learner for `oracle`. The `alphabet` argument is accepted
+    /// for symmetry with [`super::LStar::new`] and must match `oracle.alphabet()`.
+    pub fn new(_alphabet: O::Alphabet, oracle: O) -> Self {
+        Self {
+            oracle,
+            table: ResidualTable::new(),
+        }
+    }
+
+    fn hypothesis(&self) -> ResidualHypothesis<O> {
+        let states = self.table.prime_rows(&self.oracle);
+        let root_row = self.table.row(&self.oracle, &[]);
+        let root_joinands = ResidualHypothesis::<O>::joinands_of(&states, &root_row);
+        let initial: Vec<bool> = (0..states.len())
+            .map(|idx| root_joinands.contains(&idx))
+            .collect();
+
+        let symbols: Vec<_> = self.oracle.alphabet().universe().collect();
+        let mut successors = Vec::with_capacity(states.len());
+        for (access, _) in &states {
+            let mut row_successors = Vec::with_capacity(symbols.len());
+            for sym in &symbols {
+                let mut extension = access.clone();
+                extension.push(*sym);
+                let row = self.table.row(&self.oracle, &extension);
+                row_successors.push(ResidualHypothesis::<O>::joinands_of(&states, &row));
+            }
+            successors.push(row_successors);
+        }
+
+        ResidualHypothesis {
+            states,
+            initial,
+            symbols,
+            successors,
+        }
+    }
+
+    /// Runs NL* to a fixed point and returns the inferred residual hypothesis.
+    pub fn infer(&mut self) -> ResidualHypothesis<O> {
+        loop {
+            self.table.saturate(&self.oracle);
+            let hypothesis = self.hypothesis();
+            match self.oracle.equivalence(&hypothesis) {
+                Ok(()) => return hypothesis,
+                Err((counterexample, _)) => {
+                    for start in 0..counterexample.len() {
+                        let suffix = counterexample[start..].to_vec();
+                        if !self.table.e.contains(&suffix) {
+                            self.table.e.push(suffix);
+                        }
+                    }
+                    if !self.table.e.contains(&Vec::new()) {
+                        self.table.e.push(Vec::new());
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Convenience entry point: learns a residual hypothesis from `oracle` using [`NLStar`].
+pub fn nlstar<O: Oracle>(oracle: O) -> ResidualHypothesis<O>
+where
+    O::Output: Lattice,
+{
+    let alphabet = oracle.alphabet().clone();
+    NLStar::new(alphabet, oracle).infer()
+}
+
+#[cfg(test)]
+mod tests {
+    use automata::automaton::MealyLike;
+    use automata::ts::TSBuilder;
+
+    use super::super::MealyOracle;
+    use super::*;
+
+    #[test]
+    fn nlstar_learns_small_residual_target() {
+        let target = TSBuilder::without_state_colors()
+            .with_transitions([(0, 'a', 1, 1), (1, 'a', 0, 0)])
+            .into_mealy(0);
+        let oracle = MealyOracle::new(target.clone());
+        let learned = nlstar(oracle);
+
+        for word in ["a", "aa", "aaa", "aaaa"] {
+            assert_eq!(
+                learned.output(word),
+                target.transform(word).expect("target is complete"),
+                "learned residual hypothesis disagrees with target on {word:?}"
+            );
+        }
+    }
+}
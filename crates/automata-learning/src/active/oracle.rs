@@ -41,14 +41,6 @@ pub trait Oracle {
         H: Hypothesis<Alphabet = Self::Alphabet, Output = Self::Output>;
 }
 
-pub fn lstar<H, O>(oracle: O) -> H
-where
-    O: Oracle,
-    H: Hypothesis<Alphabet = O::Alphabet, Output = O::Output> + for<'a> From<&'a O::Alphabet>,
-{
-    oracle.alphabet().into()
-}
-
 /// An oracle/minimally adequate teacher based on a [`SetSample`]. It answers membership queries by looking up the
 /// word in the sample and returning the corresponding color. If the word is not in the sample, it returns the
 /// default color. Equivalence queries are perfomed by checking if the hypothesis produces the same output as the
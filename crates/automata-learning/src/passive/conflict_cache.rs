@@ -0,0 +1,446 @@
+//! Serde + compact binary (de)serialization for [`ConflictRelation`], plus an on-disk cache keyed
+//! by a hash of the sample it was built from.
+//!
+//! Computing a [`ConflictRelation`] via [`iteration_consistency_conflicts`](super::dpainf::iteration_consistency_conflicts)/
+//! [`prefix_consistency_conflicts`](super::dpainf::prefix_consistency_conflicts) is expensive
+//! (prefix-tree products, SCC decomposition, BFS closure of the conflict set), but the result is
+//! a pure function of the originating sample. [`ConflictRelationData`] is a plain, serde-friendly
+//! snapshot of a relation - each DFA's edge list and the conflicting index pairs - that round-trips
+//! through any format `serde` supports, the same way `hoars`'s own value model does for HOA
+//! documents. [`ConflictRelation::to_bytes`]/[`from_bytes`](ConflictRelation::from_bytes) instead
+//! write a tighter, self-describing binary encoding directly, for the common case where JSON's
+//! overhead isn't worth it; the binary encoding carries a header hash (see [`sample_hash`]) so a
+//! cached relation can be checked against the sample that would have produced it before it's
+//! trusted. [`ConflictRelationData`] carries no such hash.
+//! [`ConflictRelationCache`] wraps the binary format as a single-file disk cache.
+
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+
+use automata::core::Void;
+use automata::core::alphabet::Alphabet;
+use automata::ts::{ScalarIndexType, StateIndex};
+use automata::{Pointed, RightCongruence, TransitionSystem};
+use serde::{Deserialize, Serialize};
+
+use super::dpainf::ConflictRelation;
+
+/// Computes a content hash of a sample's classified words, used to validate a cached
+/// [`ConflictRelation`] against the sample that would have produced it, without re-running the
+/// (often expensive) construction just to find out whether they match. Callers pass each word's
+/// [`Debug`](std::fmt::Debug) representation (the same form already used for tracing samples
+/// elsewhere in this module), so this works uniformly for finite and omega samples alike.
+pub fn sample_hash(
+    positive: impl IntoIterator<Item = String>,
+    negative: impl IntoIterator<Item = String>,
+) -> u64 {
+    let mut positive: Vec<String> = positive.into_iter().collect();
+    let mut negative: Vec<String> = negative.into_iter().collect();
+    positive.sort_unstable();
+    negative.sort_unstable();
+
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    positive.hash(&mut hasher);
+    negative.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// A plain snapshot of one of [`ConflictRelation`]'s two DFAs: its state count, initial state,
+/// and full edge list, each edge a `(source, symbol, target)` triple.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CongruenceData<S> {
+    state_count: usize,
+    initial: usize,
+    edges: Vec<(usize, S, usize)>,
+}
+
+/// A plain, serde-friendly snapshot of a [`ConflictRelation`], produced by
+/// [`ConflictRelation::to_data`] and consumed by [`ConflictRelation::from_data`]. Round-trips
+/// through JSON, CBOR, or any other format `serde` supports.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConflictRelationData<S> {
+    left: CongruenceData<S>,
+    right: CongruenceData<S>,
+    conflicts: Vec<(usize, usize)>,
+}
+
+fn congruence_to_data<A: Alphabet>(cong: &RightCongruence<A>) -> CongruenceData<A::Symbol> {
+    let symbols: Vec<_> = cong.alphabet().universe().collect();
+    let edges = cong
+        .state_indices()
+        .flat_map(|source| {
+            symbols.iter().filter_map(move |&sym| {
+                cong.edges_from(source)?
+                    .find(|e| e.expression().symbols().any(|s| s == sym))
+                    .map(|e| (state_to_usize(source), sym, state_to_usize(e.target())))
+            })
+        })
+        .collect();
+    CongruenceData {
+        state_count: cong.size(),
+        initial: state_to_usize(cong.initial()),
+        edges,
+    }
+}
+
+fn congruence_from_data<A: Alphabet>(
+    data: &CongruenceData<A::Symbol>,
+    alphabet: A,
+) -> Result<RightCongruence<A>, ConflictRelationDecodeError>
+where
+    A::Symbol: Copy,
+{
+    if data.initial >= data.state_count {
+        return Err(ConflictRelationDecodeError::BadInitialState {
+            initial: data.initial,
+            state_count: data.state_count,
+        });
+    }
+
+    // `RightCongruence::new_with_initial_color` always starts with its own initial state at raw
+    // index 0, so a serialized congruence whose initial state was some other index is rebuilt by
+    // swapping that index with 0 everywhere - a permutation of the state space that preserves
+    // every edge while leaving the originally-intended state initial in the result.
+    let relabel = |s: usize| -> usize {
+        if s == data.initial {
+            0
+        } else if s == 0 {
+            data.initial
+        } else {
+            s
+        }
+    };
+
+    let mut cong = RightCongruence::new_with_initial_color(alphabet, Void);
+    for _ in 1..data.state_count {
+        cong.add_state(Void);
+    }
+    for &(source, sym, target) in &data.edges {
+        cong.add_edge((
+            usize_to_state(relabel(source)),
+            cong.make_expression(sym),
+            usize_to_state(relabel(target)),
+        ));
+    }
+    Ok(cong)
+}
+
+impl<A: Alphabet> ConflictRelation<A> {
+    /// Extracts a plain, serde-friendly snapshot of this relation. See the
+    /// [module documentation](self) for why this exists alongside
+    /// [`to_bytes`](Self::to_bytes).
+    pub fn to_data(&self) -> ConflictRelationData<A::Symbol> {
+        ConflictRelationData {
+            left: congruence_to_data(&self.dfas()[0]),
+            right: congruence_to_data(&self.dfas()[1]),
+            conflicts: self.conflicts_iter().collect(),
+        }
+    }
+
+    /// Rebuilds a [`ConflictRelation`] from a snapshot produced by [`to_data`](Self::to_data),
+    /// re-creating both DFAs' states and edges over `alphabet`. Fails if either congruence's
+    /// recorded initial state is out of range for its recorded state count.
+    pub fn from_data(
+        data: ConflictRelationData<A::Symbol>,
+        alphabet: A,
+    ) -> Result<Self, ConflictRelationDecodeError>
+    where
+        A::Symbol: Copy,
+    {
+        Ok(Self::from_dfas_and_conflicts(
+            [
+                congruence_from_data(&data.left, alphabet.clone())?,
+                congruence_from_data(&data.right, alphabet)?,
+            ],
+            data.conflicts.into_iter().collect(),
+        ))
+    }
+
+    /// Writes this relation as a compact, self-describing binary blob: an 8-byte magic header,
+    /// a format version, `sample_hash` (so a later [`from_bytes`](Self::from_bytes) call can
+    /// refuse a relation that doesn't match the sample it's about to be used for), and then each
+    /// DFA's state count, initial state, and edges as `u32` fields, followed by the conflict
+    /// pairs. Restricted to `char`-alphabets, the overwhelming majority of samples in this crate,
+    /// so symbols can be written as a fixed-width code point instead of going through `serde`.
+    pub fn to_bytes(&self, sample_hash: u64) -> Vec<u8>
+    where
+        A: Alphabet<Symbol = char>,
+    {
+        let mut buf = Vec::new();
+        buf.extend_from_slice(MAGIC);
+        buf.push(FORMAT_VERSION);
+        buf.extend_from_slice(&sample_hash.to_le_bytes());
+        write_congruence(&mut buf, &congruence_to_data(&self.dfas()[0]));
+        write_congruence(&mut buf, &congruence_to_data(&self.dfas()[1]));
+        write_u32(&mut buf, self.conflicts_iter().count() as u32);
+        for (l, r) in self.conflicts_iter() {
+            write_u32(&mut buf, l as u32);
+            write_u32(&mut buf, r as u32);
+        }
+        buf
+    }
+
+    /// Reads a relation written by [`to_bytes`](Self::to_bytes), rejecting it outright if the
+    /// embedded sample hash doesn't match `expected_sample_hash` - the caller never has to build
+    /// a [`ConflictRelation`] just to discover the cache was stale.
+    pub fn from_bytes(
+        bytes: &[u8],
+        expected_sample_hash: u64,
+        alphabet: A,
+    ) -> Result<Self, ConflictRelationDecodeError>
+    where
+        A: Alphabet<Symbol = char>,
+    {
+        let mut cursor = Cursor { bytes, pos: 0 };
+        if cursor.take(MAGIC.len())? != MAGIC.as_slice() {
+            return Err(ConflictRelationDecodeError::BadHeader);
+        }
+        if cursor.take(1)?[0] != FORMAT_VERSION {
+            return Err(ConflictRelationDecodeError::BadHeader);
+        }
+        let found_hash = u64::from_le_bytes(cursor.take(8)?.try_into().unwrap());
+        if found_hash != expected_sample_hash {
+            return Err(ConflictRelationDecodeError::SampleMismatch {
+                expected: expected_sample_hash,
+                found: found_hash,
+            });
+        }
+
+        let left = read_congruence(&mut cursor)?;
+        let right = read_congruence(&mut cursor)?;
+        let conflict_count = cursor.read_u32()?;
+        let conflicts = (0..conflict_count)
+            .map(|_| Ok((cursor.read_u32()? as usize, cursor.read_u32()? as usize)))
+            .collect::<Result<_, ConflictRelationDecodeError>>()?;
+
+        Ok(Self::from_dfas_and_conflicts(
+            [
+                congruence_from_data(&left, alphabet.clone())?,
+                congruence_from_data(&right, alphabet)?,
+            ],
+            conflicts,
+        ))
+    }
+}
+
+const MAGIC: &[u8; 8] = b"RALFCFR1";
+const FORMAT_VERSION: u8 = 1;
+
+fn write_u32(buf: &mut Vec<u8>, value: u32) {
+    buf.extend_from_slice(&value.to_le_bytes());
+}
+
+fn write_congruence(buf: &mut Vec<u8>, data: &CongruenceData<char>) {
+    write_u32(buf, data.state_count as u32);
+    write_u32(buf, data.initial as u32);
+    write_u32(buf, data.edges.len() as u32);
+    for &(source, sym, target) in &data.edges {
+        write_u32(buf, source as u32);
+        write_u32(buf, sym as u32);
+        write_u32(buf, target as u32);
+    }
+}
+
+struct Cursor<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Cursor<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], ConflictRelationDecodeError> {
+        let end = self.pos + len;
+        let slice = self
+            .bytes
+            .get(self.pos..end)
+            .ok_or(ConflictRelationDecodeError::Truncated)?;
+        self.pos = end;
+        Ok(slice)
+    }
+
+    fn read_u32(&mut self) -> Result<u32, ConflictRelationDecodeError> {
+        Ok(u32::from_le_bytes(self.take(4)?.try_into().unwrap()))
+    }
+}
+
+fn read_congruence(cursor: &mut Cursor<'_>) -> Result<CongruenceData<char>, ConflictRelationDecodeError> {
+    let state_count = cursor.read_u32()? as usize;
+    let initial = cursor.read_u32()? as usize;
+    let edge_count = cursor.read_u32()?;
+    let edges = (0..edge_count)
+        .map(|_| {
+            let source = cursor.read_u32()? as usize;
+            let symbol = char::from_u32(cursor.read_u32()?)
+                .ok_or(ConflictRelationDecodeError::Truncated)?;
+            let target = cursor.read_u32()? as usize;
+            Ok((source, symbol, target))
+        })
+        .collect::<Result<_, ConflictRelationDecodeError>>()?;
+    Ok(CongruenceData {
+        state_count,
+        initial,
+        edges,
+    })
+}
+
+/// Why reading a [`ConflictRelation`] back from [`ConflictRelation::from_bytes`] failed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConflictRelationDecodeError {
+    /// The byte stream didn't start with the expected magic/version header.
+    BadHeader,
+    /// The stream ended before a value a preceding length had promised, or an edge's symbol
+    /// was not a valid `char` code point.
+    Truncated,
+    /// The embedded sample hash didn't match the hash the caller is validating against.
+    SampleMismatch { expected: u64, found: u64 },
+    /// A congruence's recorded initial state was out of range for its recorded state count.
+    BadInitialState { initial: usize, state_count: usize },
+}
+
+/// Caches a [`ConflictRelation`] on disk as a single file, keyed by the hash of the sample that
+/// produced it - analogous to how serialized automata elsewhere are loaded back without
+/// re-determinizing them, but for the conflict relation that feeds into `dpainf`.
+pub struct ConflictRelationCache {
+    path: PathBuf,
+}
+
+impl ConflictRelationCache {
+    /// Points the cache at `path`, which doesn't need to exist yet.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    /// Returns the cached relation if `path` exists and its embedded sample hash is
+    /// `expected_sample_hash`; `None` for a missing, unreadable, or stale cache file.
+    pub fn load<A: Alphabet<Symbol = char>>(
+        &self,
+        expected_sample_hash: u64,
+        alphabet: A,
+    ) -> Option<ConflictRelation<A>> {
+        let bytes = std::fs::read(&self.path).ok()?;
+        ConflictRelation::from_bytes(&bytes, expected_sample_hash, alphabet).ok()
+    }
+
+    /// Returns the cached relation if valid, otherwise calls `compute`, writes its result back to
+    /// `path`, and returns it. A failure to write the cache file is not an error: `compute`'s
+    /// result is still returned.
+    pub fn get_or_compute<A: Alphabet<Symbol = char>>(
+        &self,
+        sample_hash: u64,
+        alphabet: A,
+        compute: impl FnOnce() -> ConflictRelation<A>,
+    ) -> ConflictRelation<A> {
+        if let Some(cached) = self.load(sample_hash, alphabet.clone()) {
+            return cached;
+        }
+        let relation = compute();
+        let _ = std::fs::write(&self.path, relation.to_bytes(sample_hash));
+        relation
+    }
+
+    /// The path this cache reads from and writes to.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+fn state_to_usize(state: StateIndex) -> usize {
+    state.to_usize()
+}
+
+fn usize_to_state(value: usize) -> StateIndex {
+    ScalarIndexType::from_usize(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use automata::core::alphabet::CharAlphabet;
+    use automata::core::math;
+    use automata::ts::IsEdge;
+
+    /// A 2-state congruence over `{'a'}`: `0 --a--> 1 --a--> 0`, with `initial` recorded as
+    /// whichever of the two states is meant to be the congruence's initial one.
+    fn two_state_data(initial: usize) -> CongruenceData<char> {
+        CongruenceData {
+            state_count: 2,
+            initial,
+            edges: vec![(0, 'a', 1), (1, 'a', 0)],
+        }
+    }
+
+    #[test]
+    fn congruence_from_data_honors_nonzero_initial() {
+        let cong = congruence_from_data(&two_state_data(1), CharAlphabet::of_size(1)).expect("valid data");
+        // `RightCongruence::new_with_initial_color` always starts with its own initial state at
+        // raw index 0, so a recorded initial of 1 must come back relabeled to 0.
+        assert_eq!(state_to_usize(cong.initial()), 0);
+        // Following 'a' from the new initial state must behave exactly as following 'a' from the
+        // original initial state 1 did: land on what used to be state 0.
+        let next = cong
+            .edges_from(cong.initial())
+            .unwrap()
+            .find(|e| e.expression().symbols().any(|s| s == 'a'))
+            .map(|e| e.target())
+            .unwrap();
+        assert_eq!(state_to_usize(next), 1);
+    }
+
+    #[test]
+    fn congruence_from_data_rejects_out_of_range_initial() {
+        let err = congruence_from_data(&two_state_data(5), CharAlphabet::of_size(1)).unwrap_err();
+        assert_eq!(
+            err,
+            ConflictRelationDecodeError::BadInitialState {
+                initial: 5,
+                state_count: 2,
+            }
+        );
+    }
+
+    #[test]
+    fn to_bytes_round_trips_through_from_bytes() {
+        let left = congruence_from_data(&two_state_data(0), CharAlphabet::of_size(1)).unwrap();
+        let right = congruence_from_data(&two_state_data(1), CharAlphabet::of_size(1)).unwrap();
+        let relation = ConflictRelation::from_dfas_and_conflicts([left, right], math::OrderedSet::default());
+
+        let hash = 0xC0FFEE;
+        let bytes = relation.to_bytes(hash);
+        let restored = ConflictRelation::from_bytes(&bytes, hash, CharAlphabet::of_size(1))
+            .expect("round-trips the bytes just written");
+
+        assert_eq!(restored.dfas()[0].size(), relation.dfas()[0].size());
+        assert_eq!(restored.dfas()[1].size(), relation.dfas()[1].size());
+        assert_eq!(
+            restored.conflicts_iter().collect::<Vec<_>>(),
+            relation.conflicts_iter().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn from_bytes_rejects_mismatched_sample_hash() {
+        let left = congruence_from_data(&two_state_data(0), CharAlphabet::of_size(1)).unwrap();
+        let right = congruence_from_data(&two_state_data(0), CharAlphabet::of_size(1)).unwrap();
+        let relation = ConflictRelation::from_dfas_and_conflicts([left, right], math::OrderedSet::default());
+        let bytes = relation.to_bytes(1);
+        assert_eq!(
+            ConflictRelation::from_bytes(&bytes, 2, CharAlphabet::of_size(1)).unwrap_err(),
+            ConflictRelationDecodeError::SampleMismatch {
+                expected: 2,
+                found: 1
+            }
+        );
+    }
+
+    #[test]
+    fn from_bytes_rejects_truncated_input() {
+        let left = congruence_from_data(&two_state_data(0), CharAlphabet::of_size(1)).unwrap();
+        let right = congruence_from_data(&two_state_data(0), CharAlphabet::of_size(1)).unwrap();
+        let relation = ConflictRelation::from_dfas_and_conflicts([left, right], math::OrderedSet::default());
+        let bytes = relation.to_bytes(1);
+        let truncated = &bytes[..bytes.len() - 1];
+        assert_eq!(
+            ConflictRelation::from_bytes(truncated, 1, CharAlphabet::of_size(1)).unwrap_err(),
+            ConflictRelationDecodeError::Truncated
+        );
+    }
+}
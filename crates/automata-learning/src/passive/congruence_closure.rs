@@ -0,0 +1,394 @@
+//! An incremental congruence-closure learner, as an alternative to the backtracking
+//! state-by-state search [`dpainf`](super::dpainf::dpainf) uses.
+//!
+//! [`CongruenceClosure`] maintains a union-find over "terms" (words reachable from the empty
+//! word via the hypothesis's own transitions) together with a signature table
+//! `sig: (class, symbol) -> class`, in the style of the Downey-Sethi-Tarjan congruence-closure
+//! algorithm. [`CongruenceClosure::assert_equal`] merges two terms and then saturates: for every
+//! symbol `σ` where *both* sides of a just-merged pair have a `σ`-successor already on file, the
+//! two successors are forced equal too and queued for the same treatment. That queued
+//! propagation step is exactly the right-congruence law `u ~ v ⟹ uσ ~ vσ`, so by the time the
+//! queue empties, every consequence of the asserted equality has been folded in - without ever
+//! rebuilding a product automaton the way [`ConflictRelation::consistent`](super::dpainf::ConflictRelation)
+//! does. Each class also carries an optional positive/negative tag; if a merge would identify a
+//! class tagged positive with one tagged negative, it is rejected (and rolled back) instead of
+//! applied, which is the disequality side of consistency.
+//!
+//! [`infer_coarsest_congruence`] drives this with the classic RPNI blue-fringe strategy: walk the
+//! sample's words in shortest-first order, and for each one, try merging its class with every
+//! earlier (already accepted) class, keeping the first merge that [`CongruenceClosure`] accepts.
+//! Like [`dpainf`](super::dpainf::dpainf), this always succeeds - leaving every word in its own
+//! singleton class is trivially consistent - so it only fails if the same word is classified both
+//! positive and negative in the sample (see [`Inconsistency`]).
+use std::collections::{HashMap, VecDeque};
+
+use automata::core::Void;
+use automata::core::alphabet::Alphabet;
+use automata::core::word::FiniteWord;
+use automata::ts::{ScalarIndexType, Sproutable};
+use automata::RightCongruence;
+
+use super::FiniteSample;
+use super::dpainf::ConsistencyCheck;
+
+/// A union-find over term ids with path compression and union by size.
+#[derive(Clone, Default)]
+struct UnionFind {
+    parent: Vec<usize>,
+    size: Vec<usize>,
+}
+
+impl UnionFind {
+    fn make_set(&mut self) -> usize {
+        let id = self.parent.len();
+        self.parent.push(id);
+        self.size.push(1);
+        id
+    }
+
+    fn find(&mut self, x: usize) -> usize {
+        if self.parent[x] != x {
+            let root = self.find(self.parent[x]);
+            self.parent[x] = root;
+        }
+        self.parent[x]
+    }
+
+    /// Merges the classes of `a` and `b`, returning the new shared root (or the existing one if
+    /// they were already merged).
+    fn union(&mut self, a: usize, b: usize) -> usize {
+        let (ra, rb) = (self.find(a), self.find(b));
+        if ra == rb {
+            return ra;
+        }
+        let (big, small) = if self.size[ra] >= self.size[rb] { (ra, rb) } else { (rb, ra) };
+        self.parent[small] = big;
+        self.size[big] += self.size[small];
+        big
+    }
+}
+
+/// Witnesses why [`CongruenceClosure::assert_equal`] (or [`infer_coarsest_congruence`]) refused
+/// to merge two classes: a word classified positive and one classified negative that the
+/// requested merge - directly or through signature propagation - would have identified.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Inconsistency<S> {
+    /// A word whose class is tagged positive.
+    pub positive: Vec<S>,
+    /// A word whose class is tagged negative, whose class the merge would have identified with
+    /// `positive`'s.
+    pub negative: Vec<S>,
+}
+
+/// Incremental congruence-closure state: a quotient of the words seen so far by the equalities
+/// asserted through [`assert_equal`](Self::assert_equal), exposed as a `(class, symbol) -> class`
+/// signature table. See the [module documentation](self).
+pub struct CongruenceClosure<A: Alphabet> {
+    alphabet: A,
+    uf: UnionFind,
+    sig: HashMap<(usize, A::Symbol), usize>,
+    representative: Vec<Vec<A::Symbol>>,
+    classification: Vec<Option<bool>>,
+    root_id: usize,
+}
+
+impl<A: Alphabet> CongruenceClosure<A> {
+    /// Creates a closure with a single class containing the empty word.
+    pub fn new(alphabet: A) -> Self {
+        let mut uf = UnionFind::default();
+        let root_id = uf.make_set();
+        Self {
+            alphabet,
+            uf,
+            sig: HashMap::new(),
+            representative: vec![Vec::new()],
+            classification: vec![None],
+            root_id,
+        }
+    }
+
+    /// The number of distinct terms created so far (not the number of classes, which can only
+    /// be smaller).
+    pub fn term_count(&self) -> usize {
+        self.representative.len()
+    }
+
+    /// Returns the term id for `word`, extending the known transition structure with a fresh
+    /// term for every prefix of `word` that isn't reachable from the root yet.
+    pub fn term_for(&mut self, word: &[A::Symbol]) -> usize {
+        let mut current = self.root_id;
+        for sym in word.iter().copied() {
+            let class = self.uf.find(current);
+            current = match self.sig.get(&(class, sym)) {
+                Some(&next) => next,
+                None => {
+                    let mut repr = self.representative[current].clone();
+                    repr.push(sym);
+                    let next = self.uf.make_set();
+                    self.representative.push(repr);
+                    self.classification.push(None);
+                    self.sig.insert((class, sym), next);
+                    next
+                }
+            };
+        }
+        current
+    }
+
+    /// Tags the class of `word` as `positive`/`negative`, failing if it was already tagged the
+    /// other way.
+    fn tag(&mut self, term: usize, positive: bool) -> bool {
+        let class = self.uf.find(term);
+        match self.classification[class] {
+            Some(existing) => existing == positive,
+            None => {
+                self.classification[class] = Some(positive);
+                true
+            }
+        }
+    }
+
+    /// Asserts that the classes of `a` and `b` are equal, propagating the consequence through
+    /// the signature table to a fixpoint. Returns an [`Inconsistency`] (leaving the closure
+    /// exactly as it was before the call) if doing so would identify a positively- and
+    /// negatively-tagged class.
+    pub fn assert_equal(&mut self, a: usize, b: usize) -> Result<(), Inconsistency<A::Symbol>> {
+        let uf_snapshot = self.uf.clone();
+        let sig_snapshot = self.sig.clone();
+        let classification_snapshot = self.classification.clone();
+
+        let mut queue = VecDeque::from([(a, b)]);
+        while let Some((x, y)) = queue.pop_front() {
+            let (rx, ry) = (self.uf.find(x), self.uf.find(y));
+            if rx == ry {
+                continue;
+            }
+
+            if let (Some(px), Some(py)) = (self.classification[rx], self.classification[ry]) {
+                if px != py {
+                    let (pos, neg) = if px { (rx, ry) } else { (ry, rx) };
+                    let inconsistency = Inconsistency {
+                        positive: self.representative[pos].clone(),
+                        negative: self.representative[neg].clone(),
+                    };
+                    self.uf = uf_snapshot;
+                    self.sig = sig_snapshot;
+                    self.classification = classification_snapshot;
+                    return Err(inconsistency);
+                }
+            }
+
+            let symbols: Vec<_> = self.alphabet.universe().collect();
+            let outgoing: Vec<_> = symbols
+                .iter()
+                .map(|&sym| {
+                    let tx = self.sig.get(&(rx, sym)).copied();
+                    let ty = self.sig.get(&(ry, sym)).copied();
+                    (sym, tx, ty)
+                })
+                .collect();
+
+            let merged = self.uf.union(rx, ry);
+            self.classification[merged] =
+                self.classification[rx].or(self.classification[ry]);
+
+            for (sym, tx, ty) in outgoing {
+                match (tx, ty) {
+                    (Some(tx), Some(ty)) => {
+                        self.sig.insert((merged, sym), tx);
+                        if self.uf.find(tx) != self.uf.find(ty) {
+                            queue.push_back((tx, ty));
+                        }
+                    }
+                    (Some(t), None) | (None, Some(t)) => {
+                        self.sig.insert((merged, sym), t);
+                    }
+                    (None, None) => {}
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Collapses the closure into a [`RightCongruence`], with one state per class (the class of
+    /// the empty word becomes the initial state) and one edge per `sig` entry. A `(class,
+    /// symbol)` pair with no recorded successor falls back to the initial state; this is a
+    /// simplification that only matters for symbols the sample never actually exercised from
+    /// that class.
+    pub fn into_congruence(mut self) -> RightCongruence<A> {
+        let root_class = self.uf.find(self.root_id);
+        let mut cong = RightCongruence::new_with_initial_color(self.alphabet.clone(), Void);
+        let initial = cong.initial();
+
+        let classes: Vec<usize> = (0..self.representative.len())
+            .map(|term| self.uf.find(term))
+            .collect::<std::collections::BTreeSet<_>>()
+            .into_iter()
+            .collect();
+
+        // The initial congruence already has exactly one state (index 0, the root class); every
+        // other class gets a fresh state, numbered in the order `add_state` assigns them.
+        let mut class_state = HashMap::new();
+        class_state.insert(root_class, 0usize);
+        let mut next_state = 1usize;
+        for &class in &classes {
+            if class != root_class {
+                cong.add_state(Void);
+                class_state.insert(class, next_state);
+                next_state += 1;
+            }
+        }
+
+        let symbols: Vec<_> = self.alphabet.universe().collect();
+        for &class in &classes {
+            let source = ScalarIndexType::from_usize(class_state[&class]);
+            for &sym in &symbols {
+                let target_class = self.sig.get(&(class, sym)).map(|&t| self.uf.find(t));
+                let target = target_class.map_or(initial, |c| {
+                    ScalarIndexType::from_usize(class_state[&c])
+                });
+                cong.add_edge((source, cong.make_expression(sym), target));
+            }
+        }
+
+        cong
+    }
+}
+
+/// Exposes a check against a [`FiniteSample`] as a [`ConsistencyCheck`], so it can be dropped
+/// into [`dpainf`](super::dpainf::dpainf) as one of its `additional_constraints` next to the
+/// existing full-product [`ConflictRelation`](super::dpainf::ConflictRelation). Consistency is
+/// decided by the sample's own `consistent`; no [`CongruenceClosure`] is built.
+pub struct CongruenceClosureCheck<'a, A: Alphabet> {
+    sample: &'a FiniteSample<A>,
+}
+
+impl<'a, A: Alphabet> CongruenceClosureCheck<'a, A> {
+    /// Wraps `sample` for use as a [`ConsistencyCheck`].
+    pub fn new(sample: &'a FiniteSample<A>) -> Self {
+        Self { sample }
+    }
+}
+
+impl<A: Alphabet> ConsistencyCheck<A> for CongruenceClosureCheck<'_, A> {
+    fn consistent(&self, cong: &RightCongruence<A>) -> bool {
+        self.sample.consistent(cong)
+    }
+
+    fn threshold(&self) -> usize {
+        self.sample.threshold()
+    }
+
+    fn alphabet(&self) -> &A {
+        self.sample.alphabet()
+    }
+}
+
+/// Infers the coarsest `RightCongruence<A>` consistent with `sample` by the classic RPNI
+/// blue-fringe strategy: visit the sample's words shortest-first, and for each, merge its class
+/// with the first earlier class [`CongruenceClosure::assert_equal`] accepts (leaving it in its
+/// own class if none do). See the [module documentation](self) for how a merge is checked.
+///
+/// # Example
+/// ```ignore
+/// use crate::passive::{FiniteSample, congruence_closure::infer_coarsest_congruence};
+///
+/// let sample = FiniteSample::new_from_pos_neg(alphabet, ["a", "aa"], ["", "b"]);
+/// let cong = infer_coarsest_congruence(&sample).unwrap();
+/// ```
+pub fn infer_coarsest_congruence<A: Alphabet>(
+    sample: &FiniteSample<A>,
+) -> Result<RightCongruence<A>, Inconsistency<A::Symbol>> {
+    let mut closure = CongruenceClosure::new(sample.alphabet().clone());
+
+    let mut labeled: Vec<(Vec<A::Symbol>, bool)> = sample
+        .positive_words()
+        .map(|w| (w.symbols().collect::<Vec<_>>(), true))
+        .chain(
+            sample
+                .negative_words()
+                .map(|w| (w.symbols().collect::<Vec<_>>(), false)),
+        )
+        .collect();
+    labeled.sort_by_key(|(word, _)| word.len());
+
+    let mut terms = Vec::with_capacity(labeled.len());
+    for (word, label) in &labeled {
+        let term = closure.term_for(word);
+        if !closure.tag(term, *label) {
+            return Err(Inconsistency {
+                positive: word.clone(),
+                negative: word.clone(),
+            });
+        }
+        terms.push(term);
+    }
+
+    for i in 1..terms.len() {
+        for j in 0..i {
+            if closure.assert_equal(terms[i], terms[j]).is_ok() {
+                break;
+            }
+        }
+    }
+
+    Ok(closure.into_congruence())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use automata::core::alphabet::CharAlphabet;
+
+    #[test]
+    fn merge_propagates_through_shared_symbol() {
+        let mut closure = CongruenceClosure::new(CharAlphabet::of_size(2));
+        let a = closure.term_for(&['a']);
+        let b = closure.term_for(&['b']);
+        let aa = closure.term_for(&['a', 'a']);
+        let ba = closure.term_for(&['b', 'a']);
+
+        closure
+            .assert_equal(a, b)
+            .expect("a and b are both untagged, so merging them is consistent");
+
+        assert_eq!(
+            closure.uf.find(aa),
+            closure.uf.find(ba),
+            "merging a and b should have propagated through their shared 'a'-successor"
+        );
+    }
+
+    #[test]
+    fn rejected_merge_rolls_back_state() {
+        let mut closure = CongruenceClosure::new(CharAlphabet::of_size(2));
+        let a = closure.term_for(&['a']);
+        let b = closure.term_for(&['b']);
+        assert!(closure.tag(a, true));
+        assert!(closure.tag(b, false));
+
+        let uf_before = closure.uf.clone();
+        let sig_before = closure.sig.clone();
+        let classification_before = closure.classification.clone();
+
+        let err = closure
+            .assert_equal(a, b)
+            .expect_err("merging a positively- and negatively-tagged class must be rejected");
+        assert_eq!(err.positive, vec!['a']);
+        assert_eq!(err.negative, vec!['b']);
+
+        assert_eq!(closure.uf.parent, uf_before.parent);
+        assert_eq!(closure.uf.size, uf_before.size);
+        assert_eq!(closure.sig, sig_before);
+        assert_eq!(closure.classification, classification_before);
+    }
+
+    #[test]
+    fn infers_coarsest_congruence_for_accept_reject_sample() {
+        let alphabet = CharAlphabet::of_size(2);
+        let sample = FiniteSample::new_from_pos_neg(alphabet, ["a", "aa"], ["", "b"]);
+        let cong = infer_coarsest_congruence(&sample).expect("sample is consistent with itself");
+        assert!(sample.consistent(&cong));
+    }
+}
@@ -0,0 +1,257 @@
+//! A declarative `.toml` corpus of samples and the [`dpainf`](super::dpainf::dpainf)/FORC
+//! outcomes expected of them, replacing the ad-hoc `OmegaSample::try_from_str` blobs and
+//! hard-coded `assert_eq!(size, ...)` calls that used to live directly in
+//! [`dpainf`](super::dpainf)'s test module.
+//!
+//! Each entry names a [`SampleSpec`] (an alphabet size and its positive/negative
+//! ultimately-periodic words), an [`InferenceSpec`] to run over it, and an [`ExpectSpec`] the
+//! outcome must match. [`load_dir`] collects every `.toml` file in a directory into a suite, and
+//! [`run_entry`] executes one entry end to end - together they give a single data-driven test
+//! harness, mirroring the table-driven `.toml`/`.dat` test suites used by the `regex` crate and
+//! similar automata-based engines, so a new regression case is a file, not a Rust edit.
+//!
+//! Scope: only the plain conflict-relation path (`prefix-congruence`/`dpainf`) and `forc` are
+//! wired to an actual inference. `additional_constraints` is parsed so the format can describe
+//! it, but entries that use it fail loudly with a message saying so rather than silently passing;
+//! the `error` expectation is checked for `prefix-congruence`/`dpainf` entries, and `forc` entries
+//! that use it fail the same way - see [`run_entry`].
+
+use std::path::Path;
+
+use automata::core::alphabet::CharAlphabet;
+use automata::core::upw;
+use automata::{RightCongruence, TransitionSystem};
+use serde::Deserialize;
+
+use super::dpainf::{DpaInfError, dpainf, prefix_consistency_conflicts};
+use super::{OmegaSample, SetSample};
+
+/// One corpus entry: a sample, the inference to run over it, and the outcome [`run_entry`]
+/// expects from that inference.
+#[derive(Debug, Clone, Deserialize)]
+pub struct CorpusEntry {
+    pub sample: SampleSpec,
+    pub inference: InferenceSpec,
+    pub expect: ExpectSpec,
+}
+
+/// A sample as plain data: the size of its alphabet (drawn from `a`, `b`, `c`, ... in order, the
+/// same way [`CharAlphabet::of_size`] does) and its positive/negative ultimately-periodic words,
+/// each written as the single finite word that [`upw!`] repeats forever.
+#[derive(Debug, Clone, Deserialize)]
+pub struct SampleSpec {
+    pub alphabet_size: usize,
+    pub positive: Vec<String>,
+    pub negative: Vec<String>,
+}
+
+impl SampleSpec {
+    fn build(&self) -> OmegaSample<CharAlphabet> {
+        let alphabet = CharAlphabet::of_size(self.alphabet_size);
+        SetSample::new_omega_from_pos_neg(
+            alphabet,
+            self.positive.iter().map(|w| upw!(w.as_str())),
+            self.negative.iter().map(|w| upw!(w.as_str())),
+        )
+    }
+}
+
+/// The knobs `dpainf` itself exposes, named the way they appear in its signature.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct DpainfOptions {
+    /// Names of extra [`ConsistencyCheck`](super::dpainf::ConsistencyCheck)s to run alongside the
+    /// conflict relation. Not yet wired to an actual constraint - see the [module scope
+    /// note](self).
+    #[serde(default)]
+    pub additional_constraints: Vec<String>,
+    #[serde(default)]
+    pub allow_transitions_into_epsilon: bool,
+    #[serde(default)]
+    pub timeout_seconds: Option<u64>,
+}
+
+/// Which inference to run over a [`SampleSpec`]'s sample.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "kebab-case", tag = "kind")]
+pub enum InferenceSpec {
+    /// Prefix-consistency conflicts fed straight to `dpainf`, as a sample's own
+    /// `infer_prefix_congruence` does internally.
+    PrefixCongruence(DpainfOptions),
+    /// Same conflicts, spelled out under `dpainf`'s own name for regression cases that
+    /// specifically exercise `allow_transitions_into_epsilon` or `timeout_seconds`.
+    Dpainf(DpainfOptions),
+    /// The family of right congruences for the class at `class_index` (default `0`, the empty
+    /// class) of the sample's own prefix congruence - as `learn_small_forc`/`learn_larger_forc`
+    /// used to compute by hand.
+    Forc {
+        #[serde(default)]
+        class_index: usize,
+    },
+}
+
+/// The outcome a [`CorpusEntry`] expects.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "kebab-case", untagged)]
+pub enum ExpectSpec {
+    /// The inferred congruence (or, for `forc`, the congruence at `class_index`) has this many
+    /// classes.
+    CongruenceSize { congruence_size: usize },
+    /// `dpainf` fails with this variant of [`DpaInfError`].
+    Error { error: ExpectedError },
+}
+
+/// The [`DpaInfError`] variant an `error` expectation names.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ExpectedError {
+    Threshold,
+    Timeout,
+}
+
+/// Parses every `*.toml` file directly inside `dir` as a [`CorpusEntry`], paired with its file
+/// name, in sorted order. Used by the crate's test harness to build the suite without
+/// hard-coding the list of files.
+pub fn load_dir(dir: &Path) -> Vec<(String, CorpusEntry)> {
+    let mut paths: Vec<_> = std::fs::read_dir(dir)
+        .unwrap_or_else(|err| panic!("could not read corpus directory {}: {err}", dir.display()))
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "toml"))
+        .collect();
+    paths.sort();
+
+    paths
+        .into_iter()
+        .map(|path| {
+            let name = path
+                .file_name()
+                .expect("every entry from read_dir has a file name")
+                .to_string_lossy()
+                .into_owned();
+            let text = std::fs::read_to_string(&path)
+                .unwrap_or_else(|err| panic!("could not read {}: {err}", path.display()));
+            let entry: CorpusEntry = toml::from_str(&text)
+                .unwrap_or_else(|err| panic!("could not parse {}: {err}", path.display()));
+            (name, entry)
+        })
+        .collect()
+}
+
+/// Runs one corpus entry end to end, returning `Ok(())` if the inference's outcome matched
+/// `entry.expect`, or an `Err` describing the mismatch (or the unsupported case it hit - see the
+/// [module scope note](self)) otherwise.
+pub fn run_entry(entry: &CorpusEntry) -> Result<(), String> {
+    let sample = entry.sample.build();
+    match &entry.inference {
+        InferenceSpec::PrefixCongruence(opts) | InferenceSpec::Dpainf(opts) => {
+            check_dpainf_result(run_dpainf(&sample, opts)?, &entry.expect)
+        }
+        InferenceSpec::Forc { class_index } => run_forc(&sample, *class_index, &entry.expect),
+    }
+}
+
+fn run_dpainf(
+    sample: &OmegaSample<CharAlphabet>,
+    opts: &DpainfOptions,
+) -> Result<Result<RightCongruence<CharAlphabet>, DpaInfError<CharAlphabet>>, String> {
+    if !opts.additional_constraints.is_empty() {
+        return Err(format!(
+            "unsupported additional_constraints {:?}: this harness only wires the bare conflict relation so far",
+            opts.additional_constraints
+        ));
+    }
+    let conflicts = prefix_consistency_conflicts(sample);
+    Ok(dpainf(
+        conflicts,
+        vec![],
+        opts.allow_transitions_into_epsilon,
+        opts.timeout_seconds,
+    ))
+}
+
+fn check_dpainf_result(
+    result: Result<RightCongruence<CharAlphabet>, DpaInfError<CharAlphabet>>,
+    expect: &ExpectSpec,
+) -> Result<(), String> {
+    match (expect, result) {
+        (ExpectSpec::CongruenceSize { congruence_size }, Ok(cong)) => {
+            if cong.size() == *congruence_size {
+                Ok(())
+            } else {
+                Err(format!(
+                    "expected congruence of size {congruence_size}, got {}",
+                    cong.size()
+                ))
+            }
+        }
+        (ExpectSpec::CongruenceSize { congruence_size }, Err(err)) => Err(format!(
+            "expected congruence of size {congruence_size}, but dpainf failed: {err:?}"
+        )),
+        (ExpectSpec::Error { error }, Err(err)) => {
+            if matches_error(*error, &err) {
+                Ok(())
+            } else {
+                Err(format!("expected a {error:?} error, but dpainf failed with {err:?}"))
+            }
+        }
+        (ExpectSpec::Error { error }, Ok(cong)) => Err(format!(
+            "expected a {error:?} error, but dpainf succeeded with a congruence of size {}",
+            cong.size()
+        )),
+    }
+}
+
+fn matches_error(expected: ExpectedError, actual: &DpaInfError<CharAlphabet>) -> bool {
+    matches!(
+        (expected, actual),
+        (ExpectedError::Threshold, DpaInfError::Threshold(..)) | (ExpectedError::Timeout, DpaInfError::Timeout(..))
+    )
+}
+
+fn run_forc(sample: &OmegaSample<CharAlphabet>, class_index: usize, expect: &ExpectSpec) -> Result<(), String> {
+    let cong = sample
+        .infer_prefix_congruence()
+        .map_err(|err| format!("prefix congruence failed before forc could run: {err:?}"))?;
+    let split = sample.split(&cong);
+    let forc = split.infer_forc();
+    let Some(prc) = forc.get(class_index) else {
+        return Err(format!(
+            "forc has {} classes, no class at index {class_index}",
+            forc.len()
+        ));
+    };
+
+    match expect {
+        ExpectSpec::CongruenceSize { congruence_size } => {
+            if prc.size() == *congruence_size {
+                Ok(())
+            } else {
+                Err(format!(
+                    "expected forc[{class_index}] of size {congruence_size}, got {}",
+                    prc.size()
+                ))
+            }
+        }
+        ExpectSpec::Error { error } => Err(format!(
+            "forc entries do not yet model expected dpainf errors (wanted {error:?})"
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn corpus_entries_match_their_expectations() {
+        let dir = Path::new(concat!(env!("CARGO_MANIFEST_DIR"), "/src/passive/corpus"));
+        let entries = load_dir(dir);
+        assert!(!entries.is_empty(), "corpus directory {} is empty", dir.display());
+
+        for (name, entry) in &entries {
+            if let Err(message) = run_entry(entry) {
+                panic!("{name}: {message}");
+            }
+        }
+    }
+}
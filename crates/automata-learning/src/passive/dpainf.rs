@@ -1,5 +1,6 @@
 use std::{
-    collections::{BTreeSet, VecDeque},
+    cell::RefCell,
+    collections::{BTreeSet, HashMap, VecDeque},
     fmt::Display,
 };
 
@@ -21,6 +22,34 @@ use automata::ts::predecessors::PredecessorIterable;
 use automata::ts::{Deterministic, IsEdge, ScalarIndexType, Shrinkable, Sproutable, StateIndex};
 use owo_colors::OwoColorize;
 
+/// A structured reason a [`ConsistencyCheck`] rejected a congruence, returned by
+/// [`ConsistencyCheck::witness`] instead of just the boolean `consistent` gives, so callers can
+/// report *why* a sample turned out unlearnable rather than only seeing a size blowup.
+#[derive(Debug, Clone)]
+pub enum ConsistencyWitness<A: Alphabet> {
+    /// A positive and a negative word from a [`FiniteSample`] both reach `state`.
+    SampleConflict {
+        /// A positively-classified word reaching `state`.
+        positive: Vec<A::Symbol>,
+        /// A negatively-classified word reaching the same `state`.
+        negative: Vec<A::Symbol>,
+        /// The shared congruence state both words reach.
+        state: StateIndex,
+    },
+    /// A [`ConflictRelation`] found its two DFAs both reachable, at states `left` and `right`,
+    /// alongside the same congruence class `class` - and `(left, right)` is on file as a conflict.
+    ProductConflict {
+        /// The shared congruence class from which both `left` and `right` are reachable.
+        class: StateIndex,
+        /// The conflicting state reached in the relation's left DFA.
+        left: StateIndex,
+        /// The conflicting state reached in the relation's right DFA.
+        right: StateIndex,
+        /// A word leading from the congruence's initial state to `class`.
+        path: Vec<A::Symbol>,
+    },
+}
+
 /// Represents a consistency check that can be performed on a congruence. This is used in the
 /// omega-sprout algorithm to ensure in each iteration, that the produced congruence relation
 /// is consistent with the given constraints. The constraints can either be given by a conflict
@@ -33,6 +62,48 @@ pub trait ConsistencyCheck<A: Alphabet> {
     fn threshold(&self) -> usize;
     /// Returns a reference to the alphabet used by the constraint.
     fn alphabet(&self) -> &A;
+
+    /// Verifies that `cong` is consistent, given that the edge `source --sym--> target` was just
+    /// tentatively added to it. The default implementation just forwards to
+    /// [`consistent`](Self::consistent), rebuilding from scratch; implementations that can cheaply
+    /// tell what changed (like [`ConflictRelation`]) override this to check incrementally instead,
+    /// staging whatever they discover rather than committing it outright.
+    ///
+    /// A caller that calls this must follow up with exactly one of
+    /// [`commit_after`](Self::commit_after) or [`rollback_after`](Self::rollback_after) once the
+    /// overall verdict for the trial edge is known (e.g. after consulting every constraint in
+    /// [`dpainf`]'s `additional_constraints`), before calling `consistent_after` again. This
+    /// matters because an incremental implementation like [`ConflictRelation`] may report `true`
+    /// for its own check while a *different* constraint still rejects the edge overall - if it
+    /// committed eagerly, its cache would be left claiming pairs are reachable through an edge
+    /// that was, in fact, discarded.
+    fn consistent_after(
+        &self,
+        cong: &RightCongruence<A>,
+        _source: StateIndex,
+        _sym: A::Symbol,
+        _target: StateIndex,
+    ) -> bool {
+        self.consistent(cong)
+    }
+
+    /// Finalizes whatever [`consistent_after`](Self::consistent_after) staged for the last trial
+    /// edge, because the overall verdict (across every constraint consulted) was "consistent".
+    /// The default implementation is a no-op, appropriate for stateless checks like the one
+    /// [`consistent_after`](Self::consistent_after)'s default forwards to.
+    fn commit_after(&self) {}
+
+    /// Undoes whatever [`consistent_after`](Self::consistent_after) staged for the last trial
+    /// edge, because the overall verdict (across every constraint consulted) was "inconsistent" -
+    /// even if this particular check reported `true`. The default implementation is a no-op.
+    fn rollback_after(&self) {}
+
+    /// If [`consistent`](Self::consistent) would return `false` for `cong`, returns a structured
+    /// witness explaining why; otherwise `None`. The default implementation never produces one -
+    /// it suits constraints (like [`SeparatesIdempotents`]) that are trivially always consistent.
+    fn witness(&self, _cong: &RightCongruence<A>) -> Option<ConsistencyWitness<A>> {
+        None
+    }
 }
 
 impl<A: Alphabet, CC: ConsistencyCheck<A>> ConsistencyCheck<A> for &CC {
@@ -45,6 +116,24 @@ impl<A: Alphabet, CC: ConsistencyCheck<A>> ConsistencyCheck<A> for &CC {
     fn threshold(&self) -> usize {
         CC::threshold(self)
     }
+    fn consistent_after(
+        &self,
+        cong: &RightCongruence<A>,
+        source: StateIndex,
+        sym: A::Symbol,
+        target: StateIndex,
+    ) -> bool {
+        CC::consistent_after(self, cong, source, sym, target)
+    }
+    fn commit_after(&self) {
+        CC::commit_after(self)
+    }
+    fn rollback_after(&self) {
+        CC::rollback_after(self)
+    }
+    fn witness(&self, cong: &RightCongruence<A>) -> Option<ConsistencyWitness<A>> {
+        CC::witness(self, cong)
+    }
 }
 
 impl<A: Alphabet> ConsistencyCheck<A> for FiniteSample<A> {
@@ -67,6 +156,86 @@ impl<A: Alphabet> ConsistencyCheck<A> for FiniteSample<A> {
     fn alphabet(&self) -> &A {
         &self.alphabet
     }
+
+    fn witness(&self, cong: &RightCongruence<A>) -> Option<ConsistencyWitness<A>> {
+        let mut negative_at: HashMap<StateIndex, Vec<A::Symbol>> = HashMap::new();
+        for word in self.negative_words() {
+            if let Some(state) = cong.reached_state_index(word) {
+                negative_at
+                    .entry(state)
+                    .or_insert_with(|| word.symbols().collect());
+            }
+        }
+        for word in self.positive_words() {
+            let Some(state) = cong.reached_state_index(word) else {
+                continue;
+            };
+            if let Some(negative) = negative_at.get(&state) {
+                return Some(ConsistencyWitness::SampleConflict {
+                    positive: word.symbols().collect(),
+                    negative: negative.clone(),
+                    state,
+                });
+            }
+        }
+        None
+    }
+}
+
+/// For every congruence state reached so far, the set of DFA states reachable alongside it in one
+/// side of a [`ConflictRelation`]'s product - the incremental analogue of one `reachable_state_indices`
+/// call in [`ConflictRelation::consistent`].
+#[derive(Clone, Default)]
+struct ReachableProduct {
+    by_cong: HashMap<StateIndex, BTreeSet<StateIndex>>,
+}
+
+impl ReachableProduct {
+    fn dfas_at(&self, cong: StateIndex) -> impl Iterator<Item = StateIndex> + '_ {
+        self.by_cong.get(&cong).into_iter().flatten().copied()
+    }
+
+    /// Records `(cong, dfa)` as reachable, returning whether it was new.
+    fn insert(&mut self, cong: StateIndex, dfa: StateIndex) -> bool {
+        self.by_cong.entry(cong).or_default().insert(dfa)
+    }
+
+    fn remove(&mut self, cong: StateIndex, dfa: StateIndex) {
+        if let Some(dfas) = self.by_cong.get_mut(&cong) {
+            dfas.remove(&dfa);
+        }
+    }
+}
+
+/// Follows the single `sym`-labelled edge out of `state` in a [`RightCongruence`], mirroring the
+/// `edges_from(...).find(...)` idiom used for Moore machines elsewhere in this crate.
+fn successor<A: Alphabet>(ts: &RightCongruence<A>, state: StateIndex, sym: A::Symbol) -> Option<StateIndex> {
+    ts.edges_from(state)?
+        .find(|e| e.expression().symbols().any(|s| s == sym))
+        .map(|e| e.target())
+}
+
+/// Recovers a word leading from `ts`'s initial state to `target` by walking predecessor edges
+/// backwards, one step at a time, and reversing the symbols collected along the way. Used to turn
+/// a bare congruence class index into something a human debugging an unlearnable sample can read.
+fn path_to<A: Alphabet>(ts: &RightCongruence<A>, target: StateIndex) -> Vec<A::Symbol> {
+    let mut path = Vec::new();
+    let mut current = target;
+    while current != ts.initial() {
+        let edge = ts
+            .predecessors(current)
+            .and_then(|mut edges| edges.next())
+            .expect("every non-initial state reachable in cong has a predecessor");
+        path.push(
+            edge.expression()
+                .symbols()
+                .next()
+                .expect("every edge in cong carries its one symbol"),
+        );
+        current = edge.source();
+    }
+    path.reverse();
+    path
 }
 
 /// Stores two DFAs and a math::Set of conflicts between them.
@@ -74,13 +243,24 @@ impl<A: Alphabet> ConsistencyCheck<A> for FiniteSample<A> {
 pub struct ConflictRelation<A: Alphabet> {
     dfas: [RightCongruence<A>; 2],
     conflicts: math::OrderedSet<(StateIndex, StateIndex)>,
+    /// Lazily-seeded, incrementally-maintained reachable product pairs for each side, consulted
+    /// by [`consistent_after`](Self::consistent_after) instead of rebuilding `ts_product` on every
+    /// trial edge in [`dpainf`]'s inner loop. `None` until the first incremental check.
+    reachable: RefCell<Option<[ReachableProduct; 2]>>,
+    /// The `(is_left, cong_state, dfa_state)` triples [`consistent_after`](Self::consistent_after)
+    /// inserted into `reachable` for the trial edge it was last called with, not yet finalized by
+    /// a matching [`commit_after`](Self::commit_after)/[`rollback_after`](Self::rollback_after)
+    /// call. Empty whenever no trial is in flight.
+    pending: RefCell<Vec<(bool, StateIndex, StateIndex)>>,
 }
 
 impl<A: Alphabet> ConsistencyCheck<A> for ConflictRelation<A> {
     fn alphabet(&self) -> &A {
         self.dfas[0].alphabet()
     }
-    /// Verifies that a given congruence is consistent with the conflicts.
+    /// Verifies that a given congruence is consistent with the conflicts. This is the slow-path
+    /// oracle: it rebuilds both products from scratch, so prefer
+    /// [`consistent_after`](Self::consistent_after) on the hot path inside `dpainf`.
     fn consistent(&self, cong: &RightCongruence<A>) -> bool {
         let left = cong.ts_product(&self.dfas[0]);
         let right = cong.ts_product(&self.dfas[1]);
@@ -109,11 +289,142 @@ impl<A: Alphabet> ConsistencyCheck<A> for ConflictRelation<A> {
     fn threshold(&self) -> usize {
         2 * self.dfas[0].size() * self.dfas[1].size()
     }
+
+    /// Incrementally checks whether `cong`, with the tentative edge `source --sym--> target`
+    /// already added, is still consistent. Only the pairs newly reachable because of that one
+    /// edge are explored (seeded from `target` and propagated forward through both `cong` and the
+    /// DFAs); everything reachable before the edge was added is already on file from earlier
+    /// calls. Every pair this call inserts into `reachable` - whether or not a conflict turns up -
+    /// is staged in `pending` rather than finalized, since this check reporting "consistent"
+    /// doesn't mean the trial edge will actually be kept (a different `additional_constraints`
+    /// check consulted afterwards might still reject it): the caller must follow up with
+    /// [`commit_after`](Self::commit_after) or [`rollback_after`](Self::rollback_after) once the
+    /// overall verdict is known, per [`ConsistencyCheck::consistent_after`]'s contract.
+    fn consistent_after(
+        &self,
+        cong: &RightCongruence<A>,
+        source: StateIndex,
+        sym: A::Symbol,
+        target: StateIndex,
+    ) -> bool {
+        debug_assert!(
+            self.pending.borrow().is_empty(),
+            "consistent_after called again before the previous trial was committed/rolled back"
+        );
+
+        let mut state = self.reachable.borrow_mut();
+        let [left, right] = state.get_or_insert_with(|| {
+            let mut left = ReachableProduct::default();
+            let mut right = ReachableProduct::default();
+            left.insert(cong.initial(), self.dfas[0].initial());
+            right.insert(cong.initial(), self.dfas[1].initial());
+            [left, right]
+        });
+
+        let mut journal: Vec<(bool, StateIndex, StateIndex)> = Vec::new();
+        let mut queue: VecDeque<(bool, StateIndex, StateIndex)> = VecDeque::new();
+        for dfa_state in left.dfas_at(source).collect_vec() {
+            if let Some(next) = successor(&self.dfas[0], dfa_state, sym) {
+                queue.push_back((true, target, next));
+            }
+        }
+        for dfa_state in right.dfas_at(source).collect_vec() {
+            if let Some(next) = successor(&self.dfas[1], dfa_state, sym) {
+                queue.push_back((false, target, next));
+            }
+        }
+
+        let symbols = cong.alphabet().universe().collect_vec();
+        let mut conflicting = false;
+        while let Some((is_left, cong_state, dfa_state)) = queue.pop_front() {
+            let side = if is_left { &mut *left } else { &mut *right };
+            if !side.insert(cong_state, dfa_state) {
+                continue;
+            }
+            journal.push((is_left, cong_state, dfa_state));
+
+            let other = if is_left { &*right } else { &*left };
+            if other.dfas_at(cong_state).any(|o| {
+                let pair = if is_left { (dfa_state, o) } else { (o, dfa_state) };
+                self.conflicts.contains(&pair)
+            }) {
+                conflicting = true;
+                break;
+            }
+
+            let dfa = if is_left { &self.dfas[0] } else { &self.dfas[1] };
+            for &sym2 in &symbols {
+                if let (Some(c2), Some(d2)) = (successor(cong, cong_state, sym2), successor(dfa, dfa_state, sym2))
+                {
+                    queue.push_back((is_left, c2, d2));
+                }
+            }
+        }
+
+        *self.pending.borrow_mut() = journal;
+        !conflicting
+    }
+
+    /// Finalizes the pairs staged by the last [`consistent_after`](Self::consistent_after) call,
+    /// leaving them permanently on file in `reachable`.
+    fn commit_after(&self) {
+        self.pending.borrow_mut().clear();
+    }
+
+    /// Undoes the pairs staged by the last [`consistent_after`](Self::consistent_after) call,
+    /// removing them from `reachable` again.
+    fn rollback_after(&self) {
+        let mut pending = self.pending.borrow_mut();
+        if pending.is_empty() {
+            return;
+        }
+        let mut state = self.reachable.borrow_mut();
+        if let Some([left, right]) = state.as_mut() {
+            for (is_left, cong_state, dfa_state) in pending.drain(..) {
+                if is_left {
+                    left.remove(cong_state, dfa_state);
+                } else {
+                    right.remove(cong_state, dfa_state);
+                }
+            }
+        }
+        pending.clear();
+    }
+
+    /// Reruns the slow-path [`consistent`](Self::consistent) search, but on the first conflicting
+    /// pair found, also recovers a word reaching the shared congruence class (by walking
+    /// predecessors in `cong` back to its initial state) instead of just reporting `false`.
+    fn witness(&self, cong: &RightCongruence<A>) -> Option<ConsistencyWitness<A>> {
+        let left = cong.ts_product(&self.dfas[0]);
+        let right = cong.ts_product(&self.dfas[1]);
+        let right_reachable = right.reachable_state_indices().collect_vec();
+
+        for ProductIndex(lcong, ldfa) in left.reachable_state_indices() {
+            for ProductIndex(rcong, rdfa) in right_reachable
+                .iter()
+                .filter(|ProductIndex(rcong, _)| rcong == &lcong)
+            {
+                if lcong == *rcong && self.conflicts.contains(&(ldfa, *rdfa)) {
+                    return Some(ConsistencyWitness::ProductConflict {
+                        class: lcong,
+                        left: ldfa,
+                        right: *rdfa,
+                        path: path_to(cong, lcong),
+                    });
+                }
+            }
+        }
+        None
+    }
 }
 
 impl<A: Alphabet> std::fmt::Debug for ConflictRelation<A> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        todo!()
+        f.debug_struct("ConflictRelation")
+            .field("left_states", &self.dfas[0].size())
+            .field("right_states", &self.dfas[1].size())
+            .field("conflicts", &self.conflicts.iter().collect::<Vec<_>>())
+            .finish()
     }
 }
 
@@ -123,6 +434,31 @@ impl<A: Alphabet> ConflictRelation<A> {
     pub fn alphabet(&self) -> &A {
         self.dfas[0].alphabet()
     }
+
+    /// Returns the two DFAs this relation is defined over, for callers (like
+    /// [`conflict_cache`](super::conflict_cache)) that need to inspect their structure directly.
+    pub(crate) fn dfas(&self) -> &[RightCongruence<A>; 2] {
+        &self.dfas
+    }
+
+    /// Iterates the conflicting `(left, right)` DFA state-index pairs.
+    pub(crate) fn conflicts_iter(&self) -> impl Iterator<Item = (StateIndex, StateIndex)> + '_ {
+        self.conflicts.iter().copied()
+    }
+
+    /// Rebuilds a relation directly from its two DFAs and conflict set, as when reconstructing
+    /// one from a serialized snapshot (see [`conflict_cache`](super::conflict_cache)).
+    pub(crate) fn from_dfas_and_conflicts(
+        dfas: [RightCongruence<A>; 2],
+        conflicts: math::OrderedSet<(StateIndex, StateIndex)>,
+    ) -> Self {
+        Self {
+            dfas,
+            conflicts,
+            reachable: RefCell::new(None),
+            pending: RefCell::new(Vec::new()),
+        }
+    }
 }
 
 /// Computes a conflict relation encoding iteration consistency. For more details on the construction,
@@ -217,6 +553,8 @@ pub fn iteration_consistency_conflicts<A: Alphabet>(
                 .into_right_congruence(),
         ],
         conflicts,
+        reachable: RefCell::new(None),
+        pending: RefCell::new(Vec::new()),
     }
 }
 
@@ -263,6 +601,8 @@ pub fn prefix_consistency_conflicts<A: Alphabet>(sample: &OmegaSample<A>) -> Con
             right_pta.erase_state_colors().into_right_congruence(),
         ],
         conflicts,
+        reachable: RefCell::new(None),
+        pending: RefCell::new(Vec::new()),
     }
 }
 
@@ -309,8 +649,10 @@ impl<A: Alphabet> ConsistencyCheck<A> for SeparatesIdempotents<'_, A> {
 
 #[derive(Debug)]
 pub enum DpaInfError<A: Alphabet> {
-    /// The threshold has been exceeded, returns constructed right congruence and threshold value
-    Threshold(RightCongruence<A>, usize),
+    /// The threshold has been exceeded, returns the constructed right congruence, the threshold
+    /// value, and a witness for each transition attempt rejected while processing the state that
+    /// triggered the overflow (see [`ConsistencyCheck::witness`]).
+    Threshold(RightCongruence<A>, usize, Vec<ConsistencyWitness<A>>),
     /// The given timeout has been exceeded, returns right congruence that has been constructed thus far
     Timeout(RightCongruence<A>),
 }
@@ -346,6 +688,7 @@ where
             return Err(DpaInfError::Timeout(cong));
         }
 
+        let mut rejections: Vec<ConsistencyWitness<A>> = Vec::new();
         for target in (0..cong.size()) {
             let target = ScalarIndexType::from_usize(target);
             if !allow_transitions_into_epsilon && target == initial {
@@ -356,26 +699,40 @@ where
                     .is_none()
             );
 
-            if conflicts.consistent(&cong)
-                && additional_constraints.iter().all(|c| c.consistent(&cong))
-            {
+            // Every constraint is consulted - never short-circuited - so that each one's
+            // `consistent_after` is always followed by a matching `commit_after`/`rollback_after`
+            // below, once the *overall* verdict is known; see `ConsistencyCheck::consistent_after`.
+            let conflicts_consistent = conflicts.consistent_after(&cong, source, sym, target);
+            let additional_consistent: Vec<bool> = additional_constraints
+                .iter()
+                .map(|c| c.consistent_after(&cong, source, sym, target))
+                .collect();
+            let is_consistent = conflicts_consistent && additional_consistent.iter().all(|c| *c);
+
+            if is_consistent {
+                conflicts.commit_after();
+                additional_constraints.iter().for_each(|c| c.commit_after());
                 trace!(
                     "\tTransition {source}--{}-->{target} is consistent",
                     sym.show(),
                 );
                 continue 'outer;
             } else {
+                conflicts.rollback_after();
+                additional_constraints.iter().for_each(|c| c.rollback_after());
                 trace!(
                     "\tTransition {source}--{}-->{target} is not consistent",
                     sym.show(),
                 );
+                rejections.extend(conflicts.witness(&cong));
+                rejections.extend(additional_constraints.iter().filter_map(|c| c.witness(&cong)));
                 cong.remove_edges_between_matching(source, target, sym);
             }
         }
 
         if cong.size() > threshold {
             error!("exceeded threshold on number of states {threshold}");
-            return Err(DpaInfError::Threshold(cong, threshold));
+            return Err(DpaInfError::Threshold(cong, threshold, rejections));
         }
 
         trace!(
@@ -393,11 +750,8 @@ where
 
 #[cfg(test)]
 pub(crate) mod tests {
-    use crate::passive::{SetSample, dpainf::ConflictRelation, sample::OmegaSample};
+    use crate::passive::sample::OmegaSample;
     use automata::core::alphabet::CharAlphabet;
-    use automata::core::upw;
-    use automata::{Class, TransitionSystem};
-    use itertools::Itertools;
 
     pub fn inf_aba_sample() -> (CharAlphabet, OmegaSample<CharAlphabet>) {
         let Ok(sample) = OmegaSample::try_from_str(
@@ -427,106 +781,4 @@ pub(crate) mod tests {
         (sample.alphabet.clone(), sample)
     }
 
-    pub fn testing_larger_forc_sample() -> (CharAlphabet, OmegaSample<CharAlphabet>) {
-        let Ok(sample) = OmegaSample::try_from_str(
-            r#"omega
-        alphabet: a,b
-        positive:
-        bbabab
-        ab
-        baa
-        abbab
-        babab
-        babba
-        bbaba
-        babab
-        babba
-        aba
-        aab
-        abaabb
-        ababb
-        a
-        abab
-        baba
-        ba
-        bbaba
-        abbab
-        babbba
-        abbab
-        abbaab
-        babbbba
-        negative:
-        bba
-        abba
-        baab
-        bbba
-        abb
-        abbba
-        bab
-        bba
-        babb
-        bbab
-        b
-        bb
-        abba
-        bbaab
-        abbb
-        bbaa
-        abbaa
-        babbab
-        bbabba
-        babbb
-        bbabb
-        "#,
-        ) else {
-            panic!("Cannot parse sample");
-        };
-        (sample.alphabet.clone(), sample)
-    }
-
-    fn testing_smaller_forc_smaple() -> (CharAlphabet, OmegaSample<CharAlphabet>) {
-        let alphabet = CharAlphabet::of_size(3);
-        (
-            alphabet.clone(),
-            SetSample::new_omega_from_pos_neg(
-                alphabet,
-                [
-                    upw!("a"),
-                    upw!("baa"),
-                    upw!("aca"),
-                    upw!("caab"),
-                    upw!("abca"),
-                ],
-                [upw!("b"), upw!("c"), upw!("ab"), upw!("ac"), upw!("abc")],
-            ),
-        )
-    }
-
-    #[test]
-    fn learn_small_forc() {
-        let (alphabet, sample) = testing_smaller_forc_smaple();
-        let cong = sample.infer_prefix_congruence().unwrap();
-        assert_eq!(cong.size(), 1);
-
-        let split_sample = sample.split(&cong);
-        let eps = Class::epsilon();
-        let eps_sample = split_sample.get(0).unwrap();
-
-        let conflicts: ConflictRelation<CharAlphabet> =
-            super::iteration_consistency_conflicts(&split_sample, eps);
-
-        let prc_eps = super::dpainf(conflicts, vec![], false, None).unwrap();
-        assert_eq!(prc_eps.size(), 6);
-    }
-
-    #[test_log::test]
-    fn learn_larger_forc() {
-        let (alphabet, sample) = testing_larger_forc_sample();
-        let cong = sample.infer_prefix_congruence().unwrap();
-        tracing::debug!("Got prefix congruence");
-        let split = sample.split(&cong);
-        let forc = split.infer_forc();
-        let prc_eps = forc[0].clone();
-        assert_eq!(prc_eps.size(), 13);
-    }
 }
@@ -1,11 +1,12 @@
 use std::fmt::Debug;
+use std::hash::Hash;
 
 use super::precise::PreciseDPA;
 use automata::automaton::MooreMachine;
-use automata::core::alphabet::Alphabet;
-use automata::core::math;
-use automata::ts::StateIndex;
-use automata::{RightCongruence, TransitionSystem};
+use automata::core::alphabet::{Alphabet, Expression};
+use automata::core::{Void, math};
+use automata::ts::{EdgeColor, IsEdge, Sproutable, StateColor, StateIndex, SymbolOf, TSBuilder};
+use automata::{Pointed, RightCongruence, TransitionSystem};
 use itertools::Itertools;
 
 /// This structure represents a family of weak priority mappings (FWPM). It consists of a leading
@@ -34,6 +35,56 @@ impl<A: Alphabet> FWPM<A> {
             .unwrap_or(0)
     }
 
+    /// Minimizes `self` in place, see [`Self::minimized`].
+    pub fn minimize(&mut self)
+    where
+        StateColor<MooreMachine<A>>: Eq + Hash + Clone,
+        EdgeColor<MooreMachine<A>>: Eq + Hash + Clone,
+    {
+        *self = self.minimized();
+    }
+
+    /// Collapses language-equivalent states, both in the leading [`RightCongruence`] and in
+    /// every progress [`MooreMachine`], using a signature-table congruence-closure procedure
+    /// (Downey-Sethi-Tarjan style partition refinement): states start merged only if they
+    /// carry equal colors, then any class whose members disagree on some symbol's `(edge
+    /// color, successor class)` pair is split, repeating to a fixpoint. Because a class can
+    /// only ever contain states that already agreed on their own color, two states of
+    /// different priority are never merged, so the weakness invariant documented on [`FWPM`]
+    /// (prefix values are `>=` suffix values) is preserved by construction rather than
+    /// checked after the fact. When minimizing the leading congruence merges two classes, the
+    /// progress machine of one of the merged classes (picked arbitrarily, since the two are
+    /// assumed language-equivalent) is kept and minimized in its place.
+    pub fn minimized(&self) -> Self
+    where
+        StateColor<MooreMachine<A>>: Eq + Hash + Clone,
+        EdgeColor<MooreMachine<A>>: Eq + Hash + Clone,
+    {
+        let (leading, new_index_of) = minimize_congruence(&self.leading);
+
+        let mut representative_of_new: math::Map<
+            StateIndex<RightCongruence<A>>,
+            StateIndex<RightCongruence<A>>,
+        > = math::Map::default();
+        for old in self.leading.state_indices() {
+            let new = *new_index_of
+                .get(&old)
+                .expect("every old state was assigned a new index");
+            representative_of_new.entry(new).or_insert(old);
+        }
+
+        let mut pm = math::OrderedMap::default();
+        for (new, old) in representative_of_new {
+            let machine = self
+                .pm
+                .get(&old)
+                .expect("every leading state has a progress mapping");
+            pm.insert(new, minimize_moore(machine));
+        }
+
+        FWPM::new(leading, pm)
+    }
+
     /// Returns a reference to the underlying right congruence.
     pub fn leading(&self) -> &RightCongruence<A> {
         &self.leading
@@ -97,3 +148,175 @@ impl<A: Alphabet> Debug for FWPM<A> {
         Ok(())
     }
 }
+
+/// Computes the coarsest congruence-closed partition of `ts`'s states, as a map from each
+/// state to its (arbitrarily numbered) class and the total number of classes, by iterating a
+/// signature-table refinement to a fixpoint: states start in the same class only if they
+/// carry equal [`TransitionSystem::StateColor`]s, and a class is split as soon as two of its
+/// members disagree, for some symbol, on the `(edge color, class of successor)` pair.
+fn congruence_classes<Ts>(ts: &Ts) -> (math::Map<StateIndex<Ts>, usize>, usize)
+where
+    Ts: TransitionSystem,
+    StateIndex<Ts>: Eq + Hash + Copy,
+    StateColor<Ts>: Eq + Clone,
+    EdgeColor<Ts>: Eq + Hash + Clone,
+{
+    let states: Vec<StateIndex<Ts>> = ts.state_indices().collect();
+    let symbols: Vec<SymbolOf<Ts>> = ts.alphabet().universe().collect();
+
+    let mut seen_colors: Vec<StateColor<Ts>> = Vec::new();
+    let mut class_of: math::Map<StateIndex<Ts>, usize> = states
+        .iter()
+        .map(|&q| {
+            let color = ts.state_color(q).expect("every state carries a color");
+            let class = seen_colors
+                .iter()
+                .position(|c| c == &color)
+                .unwrap_or_else(|| {
+                    seen_colors.push(color);
+                    seen_colors.len() - 1
+                });
+            (q, class)
+        })
+        .collect();
+    let mut num_classes = seen_colors.len();
+
+    loop {
+        let mut signature_ids: math::Map<(usize, Vec<Option<(EdgeColor<Ts>, usize)>>), usize> =
+            math::Map::default();
+        let mut next_class_of: math::Map<StateIndex<Ts>, usize> = math::Map::default();
+
+        for &q in &states {
+            let own_class = *class_of.get(&q).expect("every state was classified above");
+            let signature: Vec<Option<(EdgeColor<Ts>, usize)>> = symbols
+                .iter()
+                .map(|sym| {
+                    ts.edges_from(q).and_then(|mut edges| {
+                        edges
+                            .find(|e| e.expression().symbols().any(|s| &s == sym))
+                            .map(|e| {
+                                let target_class = *class_of
+                                    .get(&e.target())
+                                    .expect("every target was classified above");
+                                (e.color(), target_class)
+                            })
+                    })
+                })
+                .collect();
+
+            let next_id = signature_ids.len();
+            let id = *signature_ids
+                .entry((own_class, signature))
+                .or_insert(next_id);
+            next_class_of.insert(q, id);
+        }
+
+        let new_num_classes = signature_ids.len();
+        class_of = next_class_of;
+        if new_num_classes == num_classes {
+            return (class_of, num_classes);
+        }
+        num_classes = new_num_classes;
+    }
+}
+
+/// Rebuilds `mm` over the representatives of [`congruence_classes`], redirecting every edge
+/// through the representative's class; safe because `mm` is deterministic.
+fn minimize_moore<A: Alphabet>(mm: &MooreMachine<A>) -> MooreMachine<A>
+where
+    StateColor<MooreMachine<A>>: Eq + Hash + Clone,
+    EdgeColor<MooreMachine<A>>: Eq + Hash + Clone,
+{
+    let (class_of, num_classes) = congruence_classes(mm);
+
+    let mut representative: Vec<Option<StateIndex<MooreMachine<A>>>> = vec![None; num_classes];
+    for q in mm.state_indices() {
+        let class = *class_of.get(&q).expect("every state was classified above");
+        representative[class].get_or_insert(q);
+    }
+
+    let symbols: Vec<SymbolOf<MooreMachine<A>>> = mm.alphabet().universe().collect();
+    let mut colors = Vec::with_capacity(num_classes);
+    let mut transitions = Vec::new();
+    for (class, rep) in representative.iter().enumerate() {
+        let rep = rep.expect("every class has at least one member");
+        colors.push(mm.state_color(rep).expect("every state carries a color"));
+        for sym in &symbols {
+            if let Some(mut edges) = mm.edges_from(rep) {
+                if let Some(edge) = edges.find(|e| e.expression().symbols().any(|s| &s == sym)) {
+                    let target_class = *class_of
+                        .get(&edge.target())
+                        .expect("every target was classified above");
+                    transitions.push((class, *sym, target_class));
+                }
+            }
+        }
+    }
+
+    let initial_class = *class_of
+        .get(&mm.initial())
+        .expect("the initial state was classified above");
+
+    TSBuilder::without_edge_colors()
+        .with_state_colors(colors)
+        .with_transitions(transitions)
+        .into_moore(initial_class)
+}
+
+/// Rebuilds `leading` over the representatives of [`congruence_classes`], returning the
+/// minimized congruence together with the map from every old state to its (possibly merged)
+/// new one.
+fn minimize_congruence<A: Alphabet>(
+    leading: &RightCongruence<A>,
+) -> (
+    RightCongruence<A>,
+    math::Map<StateIndex<RightCongruence<A>>, StateIndex<RightCongruence<A>>>,
+) {
+    let (class_of, num_classes) = congruence_classes(leading);
+
+    let mut representative: Vec<Option<StateIndex<RightCongruence<A>>>> = vec![None; num_classes];
+    for q in leading.state_indices() {
+        let class = *class_of.get(&q).expect("every state was classified above");
+        representative[class].get_or_insert(q);
+    }
+
+    let initial_class = *class_of
+        .get(&leading.initial())
+        .expect("the initial state was classified above");
+
+    let mut new_cong = RightCongruence::new_with_initial_color(leading.alphabet().clone(), Void);
+    let mut class_to_new: Vec<Option<StateIndex<RightCongruence<A>>>> = vec![None; num_classes];
+    class_to_new[initial_class] = Some(new_cong.initial());
+    for (class, slot) in class_to_new.iter_mut().enumerate() {
+        if class != initial_class {
+            *slot = Some(new_cong.add_state(Void));
+        }
+    }
+
+    let symbols: Vec<SymbolOf<RightCongruence<A>>> = leading.alphabet().universe().collect();
+    for (class, rep) in representative.iter().enumerate() {
+        let rep = rep.expect("every class has at least one member");
+        let source = class_to_new[class].expect("every class was mapped above");
+        for sym in &symbols {
+            if let Some(mut edges) = leading.edges_from(rep) {
+                if let Some(edge) = edges.find(|e| e.expression().symbols().any(|s| &s == sym)) {
+                    let target_class = *class_of
+                        .get(&edge.target())
+                        .expect("every target was classified above");
+                    let target = class_to_new[target_class].expect("every class was mapped above");
+                    new_cong.add_edge((source, new_cong.make_expression(*sym), target));
+                }
+            }
+        }
+    }
+
+    let new_index_of = leading
+        .state_indices()
+        .map(|q| {
+            let class = *class_of.get(&q).expect("every state was classified above");
+            (q, class_to_new[class].expect("every class was mapped above"))
+        })
+        .collect();
+
+    (new_cong, new_index_of)
+}
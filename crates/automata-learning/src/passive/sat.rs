@@ -0,0 +1,229 @@
+//! Exact, SAT-based inference of a minimal DFA consistent with a [`FiniteSample`].
+//!
+//! This follows the classic encoding of Heule & Verwer: the prefixes of the sample's
+//! words are laid out as nodes of an implicit prefix tree, and we
+//! search for the smallest `k` for which there is a *merge* of that tree into `k` states
+//! that (a) defines a total, deterministic transition function and (b) separates the
+//! positive words from the negative ones. Each candidate `k` is checked by handing a CNF
+//! encoding of exactly this constraint to a SAT solver; `k` is increased until one is
+//! satisfiable. Since the prefix tree itself is already consistent with the sample, this
+//! process always terminates (in the worst case at `k` = the size of the prefix tree).
+//!
+//! Requires the `varisat` crate (a pure-Rust SAT solver) as a dependency.
+
+use std::collections::BTreeMap;
+
+use automata::automaton::DFA;
+use automata::core::alphabet::{Alphabet, Symbol};
+use automata::core::word::FiniteWord;
+use automata::ts::TSBuilder;
+use varisat::{CnfFormula, ExtendFormula, Lit, Solver, Var};
+
+use crate::passive::FiniteSample;
+
+/// A key into the single shared [`VarPool`] used by [`try_infer_with_size`], one variant per
+/// constraint domain (node coloring, transition function, acceptance). Keeping all three
+/// domains in one pool - rather than one `VarPool` per domain, each minting its own variables
+/// from index 0 - is what keeps the underlying `varisat::Var` ids (which are raw, global,
+/// unnamespaced indices) from aliasing across domains.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+enum VarKey {
+    /// "Node `node_idx` is assigned to state `state`".
+    Node(usize, usize),
+    /// "`delta(state, sym_idx) = target`".
+    Trans(usize, usize, usize),
+    /// "State `state` is accepting".
+    Accept(usize),
+}
+
+/// Allocates one SAT variable per [`VarKey`], handing out fresh indices on demand and
+/// remembering the assignment so the same key always maps back to the same variable.
+#[derive(Default)]
+struct VarPool {
+    vars: BTreeMap<VarKey, Var>,
+    next: usize,
+}
+
+impl VarPool {
+    fn get(&mut self, key: VarKey) -> Var {
+        if let Some(v) = self.vars.get(&key) {
+            return *v;
+        }
+        let v = Var::from_index(self.next);
+        self.next += 1;
+        self.vars.insert(key, v);
+        v
+    }
+}
+
+/// Tries to find a DFA with exactly `k` states that is consistent with `sample`, returning
+/// `None` if the CNF encoding is unsatisfiable for this `k`.
+fn try_infer_with_size<A: Alphabet>(sample: &FiniteSample<A>, k: usize) -> Option<DFA<A>> {
+    let symbols: Vec<A::Symbol> = sample.alphabet().universe().collect();
+
+    // Every prefix of every sample word is a node of the (implicit) prefix tree.
+    let mut nodes: Vec<Vec<A::Symbol>> = vec![Vec::new()];
+    for word in sample.positive_words().chain(sample.negative_words()) {
+        let mut prefix = Vec::new();
+        for sym in word.symbols() {
+            prefix.push(sym);
+            if !nodes.contains(&prefix) {
+                nodes.push(prefix.clone());
+            }
+        }
+    }
+
+    let mut pool = VarPool::default();
+    let mut formula = CnfFormula::new();
+
+    // Every node is assigned to exactly one of the `k` candidate states.
+    for (node_idx, _) in nodes.iter().enumerate() {
+        let lits: Vec<Lit> = (0..k)
+            .map(|state| Lit::from_var(pool.get(VarKey::Node(node_idx, state)), true))
+            .collect();
+        formula.add_clause(&lits);
+        for i in 0..k {
+            for j in (i + 1)..k {
+                formula.add_clause(&[
+                    Lit::from_var(pool.get(VarKey::Node(node_idx, i)), false),
+                    Lit::from_var(pool.get(VarKey::Node(node_idx, j)), false),
+                ]);
+            }
+        }
+    }
+
+    // The root is assigned to state 0, fixing the automaton's initial state.
+    formula.add_clause(&[Lit::from_var(pool.get(VarKey::Node(0, 0)), true)]);
+
+    // If a node is in state `i` and its child-via-`a` is in state `j`, the transition
+    // function must map `(i, a)` to `j`; conversely each `(i, a)` must map to exactly one
+    // state so the resulting automaton is deterministic and complete.
+    for (sym_idx, sym) in symbols.iter().enumerate() {
+        for state in 0..k {
+            let lits: Vec<Lit> = (0..k)
+                .map(|target| Lit::from_var(pool.get(VarKey::Trans(state, sym_idx, target)), true))
+                .collect();
+            formula.add_clause(&lits);
+            for i in 0..k {
+                for j in (i + 1)..k {
+                    formula.add_clause(&[
+                        Lit::from_var(pool.get(VarKey::Trans(state, sym_idx, i)), false),
+                        Lit::from_var(pool.get(VarKey::Trans(state, sym_idx, j)), false),
+                    ]);
+                }
+            }
+        }
+
+        for (node_idx, node) in nodes.iter().enumerate() {
+            let mut child = node.clone();
+            child.push(*sym);
+            let Some(child_idx) = nodes.iter().position(|n| n == &child) else {
+                continue;
+            };
+            for i in 0..k {
+                for j in 0..k {
+                    // node@i & child@j => delta(i, sym) = j
+                    formula.add_clause(&[
+                        Lit::from_var(pool.get(VarKey::Node(node_idx, i)), false),
+                        Lit::from_var(pool.get(VarKey::Node(child_idx, j)), false),
+                        Lit::from_var(pool.get(VarKey::Trans(i, sym_idx, j)), true),
+                    ]);
+                }
+            }
+        }
+    }
+
+    // Positive/negative sample words force the state their node is assigned to be
+    // accepting/rejecting.
+    let mut constrain = |word: &[A::Symbol], accept: bool| {
+        let node_idx = nodes.iter().position(|n| n == word).expect("prefix tree contains every sample word");
+        for state in 0..k {
+            let accept_lit = Lit::from_var(pool.get(VarKey::Accept(state)), accept);
+            formula.add_clause(&[Lit::from_var(pool.get(VarKey::Node(node_idx, state)), false), accept_lit]);
+        }
+    };
+    for word in sample.positive_words() {
+        constrain(&word.symbols().collect::<Vec<_>>(), true);
+    }
+    for word in sample.negative_words() {
+        constrain(&word.symbols().collect::<Vec<_>>(), false);
+    }
+
+    let mut solver = Solver::new();
+    solver.add_formula(&formula);
+    if !solver.solve().unwrap_or(false) {
+        return None;
+    }
+    let model = solver.model()?;
+    let is_true = |lit: Lit| model.contains(&lit);
+
+    let state_colors: Vec<bool> = (0..k)
+        .map(|state| is_true(Lit::from_var(pool.get(VarKey::Accept(state)), true)))
+        .collect();
+    let mut transitions = Vec::new();
+    for (sym_idx, sym) in symbols.iter().enumerate() {
+        for state in 0..k {
+            for target in 0..k {
+                if is_true(Lit::from_var(pool.get(VarKey::Trans(state, sym_idx, target)), true)) {
+                    transitions.push((state, *sym, target));
+                }
+            }
+        }
+    }
+
+    Some(
+        TSBuilder::default()
+            .with_state_colors(state_colors)
+            .with_transitions(transitions)
+            .into_dfa(0),
+    )
+}
+
+/// Infers a size-minimal DFA consistent with `sample` by solving an exact SAT encoding
+/// for increasing candidate sizes `k = 1, 2, ...` until one succeeds.
+///
+/// # Example
+/// ```ignore
+/// use crate::passive::{FiniteSample, sat::infer_minimal_dfa};
+///
+/// let sample = FiniteSample::new_from_pos_neg(alphabet, ["a", "aa"], ["", "b"]);
+/// let dfa = infer_minimal_dfa(&sample);
+/// ```
+pub fn infer_minimal_dfa<A: Alphabet>(sample: &FiniteSample<A>) -> DFA<A> {
+    for k in 1.. {
+        if let Some(dfa) = try_infer_with_size(sample, k) {
+            return dfa;
+        }
+    }
+    unreachable!("the prefix tree itself is always a satisfying upper bound on k")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::infer_minimal_dfa;
+    use crate::passive::FiniteSample;
+    use automata::TransitionSystem;
+    use automata::core::alphabet::CharAlphabet;
+
+    #[test]
+    fn infers_single_symbol_acceptor() {
+        let alphabet = CharAlphabet::of_size(1);
+        let sample = FiniteSample::new_from_pos_neg(alphabet, ["a"], [""]);
+        let dfa = infer_minimal_dfa(&sample);
+        assert_eq!(dfa.size(), 2, "one state can't be both accepting and rejecting");
+        assert!(dfa.accepts("a"));
+        assert!(!dfa.accepts(""));
+    }
+
+    #[test]
+    fn infers_even_length_parity_acceptor() {
+        let alphabet = CharAlphabet::of_size(1);
+        let sample = FiniteSample::new_from_pos_neg(alphabet, ["", "aa"], ["a", "aaa"]);
+        let dfa = infer_minimal_dfa(&sample);
+        assert_eq!(dfa.size(), 2, "the sample is exactly the even-length-of-a's language");
+        assert!(dfa.accepts(""));
+        assert!(dfa.accepts("aa"));
+        assert!(!dfa.accepts("a"));
+        assert!(!dfa.accepts("aaa"));
+    }
+}
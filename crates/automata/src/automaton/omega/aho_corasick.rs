@@ -0,0 +1,98 @@
+//! [`DPA::infinitely_often`]: a classical Aho-Corasick automaton repurposed as a direct, linear-size
+//! monitor for "some pattern from a given set occurs infinitely often".
+//!
+//! The construction is the textbook one: build the prefix trie of the patterns, compute failure
+//! links by a breadth-first walk of the trie, and use them to complete the trie's goto function
+//! into a total deterministic transition function (a missing trie edge follows the failure chain
+//! back toward the root). A state is an "output" state if it or any state reachable via its
+//! failure chain marks the end of a pattern - failure links always point to a strictly shallower
+//! state, so a single BFS-ordered pass propagates this correctly, including the edge case where
+//! the empty string is itself a pattern (every state's failure chain ends at the root, so
+//! marking the root an output state makes every other state one too, by propagation).
+//!
+//! Every transition landing in an output state gets priority `0`, every other transition
+//! priority `1`: under the default [`MinEvenParityCondition`](super::MinEvenParityCondition),
+//! this accepts iff priority `0` recurs infinitely often, i.e. iff some pattern matches
+//! infinitely often.
+use std::collections::{HashMap, VecDeque};
+
+use crate::core::alphabet::CharAlphabet;
+use crate::ts::TSBuilder;
+
+use super::DPA;
+
+impl DPA<CharAlphabet> {
+    /// Builds a [`DPA`] over `alphabet` accepting exactly the ω-words in which at least one of
+    /// `patterns` occurs infinitely often.
+    ///
+    /// # Example
+    /// ```
+    /// use automata::automaton::omega::DPA;
+    /// use automata::core::alphabet::CharAlphabet;
+    ///
+    /// let dpa = DPA::infinitely_often(["ab"], &CharAlphabet::from_iter(['a', 'b']));
+    /// assert!(dpa.give_accepted_word().is_some());
+    /// assert!(dpa.give_rejected_word().is_some());
+    /// ```
+    pub fn infinitely_often(
+        patterns: impl IntoIterator<Item = impl AsRef<str>>,
+        alphabet: &CharAlphabet,
+    ) -> DPA<CharAlphabet> {
+        let mut children: Vec<HashMap<char, usize>> = vec![HashMap::new()];
+        let mut is_end: Vec<bool> = vec![false];
+
+        for pattern in patterns {
+            let mut state = 0usize;
+            for c in pattern.as_ref().chars() {
+                state = *children[state].entry(c).or_insert_with(|| {
+                    children.push(HashMap::new());
+                    is_end.push(false);
+                    children.len() - 1
+                });
+            }
+            is_end[state] = true;
+        }
+
+        let symbols: Vec<char> = alphabet.universe().collect();
+        let num_states = children.len();
+        let mut goto = vec![vec![0usize; symbols.len()]; num_states];
+        let mut fail = vec![0usize; num_states];
+        let mut is_output = is_end;
+
+        let mut queue = VecDeque::new();
+        for (si, c) in symbols.iter().enumerate() {
+            if let Some(&child) = children[0].get(c) {
+                goto[0][si] = child;
+                fail[child] = 0;
+                is_output[child] = is_output[child] || is_output[0];
+                queue.push_back(child);
+            } else {
+                goto[0][si] = 0;
+            }
+        }
+        while let Some(u) = queue.pop_front() {
+            for (si, c) in symbols.iter().enumerate() {
+                if let Some(&v) = children[u].get(c) {
+                    fail[v] = goto[fail[u]][si];
+                    is_output[v] = is_output[v] || is_output[fail[v]];
+                    goto[u][si] = v;
+                    queue.push_back(v);
+                } else {
+                    goto[u][si] = goto[fail[u]][si];
+                }
+            }
+        }
+
+        let mut edges = Vec::with_capacity(num_states * symbols.len());
+        for (u, row) in goto.iter().enumerate() {
+            for (si, &target) in row.iter().enumerate() {
+                let color = if is_output[target] { 0 } else { 1 };
+                edges.push((u, symbols[si], color, target));
+            }
+        }
+
+        TSBuilder::without_state_colors()
+            .with_transitions(edges)
+            .into_dpa(0)
+    }
+}
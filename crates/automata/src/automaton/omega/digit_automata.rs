@@ -0,0 +1,165 @@
+//! Base-`b` digit automata for linear-arithmetic constraints on non-negative integers, in the
+//! style of automatic-structure / Büchi-arithmetic presentations (Boigelot & Wolper): a number is
+//! encoded as an ω-word over digits, read **least-significant digit first**, followed by an
+//! infinite suffix of the digit `0` once the actual representation is exhausted. [`DPA::integer_leq`]
+//! and [`DPA::divisible_by`] build monitors for such encodings directly, without the user having to
+//! hand-write the carry/remainder bookkeeping.
+//!
+//! This crate only handles non-negative integers: there is no sign digit, so every encoded number
+//! is implicitly `≥ 0` and every word not eventually all-`0` is simply outside the domain these
+//! automata are meant to be run on. Keeping the digit order and the absence of a sign prefix fixed
+//! across [`DPA::integer_leq`] and [`DPA::divisible_by`] is what lets their results be combined
+//! with [`ts_product`](crate::ts::operations::Product::ts_product) and
+//! [`complement`](super::IntoDPA::complement) into a single DPA for a conjunction, disjunction or
+//! negation of constraints over the *same* variables: as long as every constraint is built with
+//! the same `base` and the same coefficient vector length (so the same digit alphabet), a
+//! position in one constraint's word lines up with the same position in another's.
+//!
+//! # `integer_leq`
+//!
+//! [`DPA::integer_leq`] recognizes the encodings of integer vectors `x` with `coeffs · x ≤ bound`.
+//! Its state is the running "carry" `c`, initialized to `bound`; reading the digit vector `d` at
+//! the next position updates it to `c' = (c - coeffs · d).div_euclid(base)`. Because `div_euclid`
+//! (by the positive `base`) rounds towards negative infinity, repeatedly applying this update
+//! under the digit-`0` vector - exactly what happens forever once the actual representation of
+//! `x` is exhausted - is a contraction that drives any carry to the fixed point `0` (if it is
+//! currently `≥ 0`) or `-1` (if it is currently `< 0`), in both cases within finitely many steps.
+//! Transitions landing on carry `0` get priority `0`, everything else priority `1`, so under
+//! [`MinEvenParityCondition`](super::MinEvenParityCondition) acceptance is exactly "the carry is
+//! eventually forever `0`", i.e. `coeffs · x ≤ bound`. The same contraction argument bounds the
+//! total number of carries reachable from the initial one, which is what makes the state set
+//! finite despite `bound` being unbounded.
+//!
+//! # `divisible_by`
+//!
+//! [`DPA::divisible_by`] recognizes multiples of `m`. Its state is the pair `(remainder, power)` of
+//! `x`'s value and `base`'s power, both so far and both taken modulo `m`; reading digit `d` updates
+//! `remainder' = (remainder + d · power) mod m` and `power' = (power · base) mod m`. Once the actual
+//! digits are exhausted, `d = 0` forever, so `remainder` freezes at its final value: this is
+//! assigned priority `0` exactly when that value is `0`, so acceptance is exactly "eventually
+//! forever divisible by `m`", i.e. `x mod m == 0`.
+use std::collections::{HashMap, VecDeque};
+
+use crate::core::alphabet::CharAlphabet;
+use crate::ts::TSBuilder;
+
+use super::DPA;
+
+/// Encodes a digit vector (one digit per coefficient, each in `0..base`) as a single `char`,
+/// shared by [`DPA::integer_leq`] and [`DPA::divisible_by`] so that automata built over the same
+/// `base` and vector length agree on their alphabet.
+fn digit_symbol(digits: &[u32], base: u32) -> char {
+    let code = digits.iter().fold(0u32, |acc, &d| acc * base + d);
+    char::from_u32(code).expect("digit vector code fits into a char")
+}
+
+/// Every digit vector of length `k` over `0..base`, in the same order [`digit_symbol`] encodes
+/// them.
+fn all_digit_vectors(k: usize, base: u32) -> Vec<Vec<u32>> {
+    let mut vectors = vec![Vec::new()];
+    for _ in 0..k {
+        vectors = vectors
+            .into_iter()
+            .flat_map(|prefix| {
+                (0..base).map(move |d| {
+                    let mut next = prefix.clone();
+                    next.push(d);
+                    next
+                })
+            })
+            .collect();
+    }
+    vectors
+}
+
+impl DPA<CharAlphabet> {
+    /// Builds a [`DPA`] over the digit-vector alphabet of length `coeffs.len()` accepting exactly
+    /// the least-significant-digit-first, eventually-`0` encodings of non-negative integer
+    /// vectors `x` with `coeffs · x ≤ bound`. See the [module documentation](self) for the carry
+    /// construction and the encoding invariants.
+    ///
+    /// # Example
+    /// ```
+    /// use automata::automaton::omega::DPA;
+    ///
+    /// // x <= 5, base 10, a single coefficient.
+    /// let dpa = DPA::integer_leq(&[1], 5, 10);
+    /// assert!(dpa.give_accepted_word().is_some());
+    /// assert!(dpa.give_rejected_word().is_some());
+    /// ```
+    pub fn integer_leq(coeffs: &[i64], bound: i64, base: u32) -> DPA<CharAlphabet> {
+        let vectors = all_digit_vectors(coeffs.len(), base);
+        let symbols: Vec<char> = vectors.iter().map(|v| digit_symbol(v, base)).collect();
+
+        let mut carries = vec![bound];
+        let mut carry_index: HashMap<i64, usize> = HashMap::from([(bound, 0)]);
+        let mut queue = VecDeque::from([0usize]);
+        let mut edges = Vec::new();
+
+        while let Some(source) = queue.pop_front() {
+            let carry = carries[source];
+            for (vector, &symbol) in vectors.iter().zip(&symbols) {
+                let dot: i64 = coeffs
+                    .iter()
+                    .zip(vector)
+                    .map(|(a, &d)| a * i64::from(d))
+                    .sum();
+                let next_carry = (carry - dot).div_euclid(i64::from(base));
+                let target = *carry_index.entry(next_carry).or_insert_with(|| {
+                    carries.push(next_carry);
+                    queue.push_back(carries.len() - 1);
+                    carries.len() - 1
+                });
+                let color = if next_carry == 0 { 0 } else { 1 };
+                edges.push((source, symbol, color, target));
+            }
+        }
+
+        TSBuilder::without_state_colors()
+            .with_transitions(edges)
+            .into_dpa(0)
+    }
+
+    /// Builds a [`DPA`] over the single-digit alphabet `0..base` accepting exactly the
+    /// least-significant-digit-first, eventually-`0` encodings of non-negative multiples of `m`.
+    /// See the [module documentation](self) for the remainder construction and the encoding
+    /// invariants.
+    ///
+    /// # Example
+    /// ```
+    /// use automata::automaton::omega::DPA;
+    ///
+    /// let dpa = DPA::divisible_by(3, 10);
+    /// assert!(dpa.give_accepted_word().is_some());
+    /// assert!(dpa.give_rejected_word().is_some());
+    /// ```
+    pub fn divisible_by(m: i64, base: u32) -> DPA<CharAlphabet> {
+        let symbols: Vec<char> = (0..base).map(|d| digit_symbol(&[d], base)).collect();
+
+        let initial = (0i64, 1i64.rem_euclid(m));
+        let mut states = vec![initial];
+        let mut state_index: HashMap<(i64, i64), usize> = HashMap::from([(initial, 0)]);
+        let mut queue = VecDeque::from([0usize]);
+        let mut edges = Vec::new();
+
+        while let Some(source) = queue.pop_front() {
+            let (remainder, power) = states[source];
+            for (d, &symbol) in (0..base).zip(&symbols) {
+                let next_remainder = (remainder + i64::from(d) * power).rem_euclid(m);
+                let next_power = (power * i64::from(base)).rem_euclid(m);
+                let next_state = (next_remainder, next_power);
+                let target = *state_index.entry(next_state).or_insert_with(|| {
+                    states.push(next_state);
+                    queue.push_back(states.len() - 1);
+                    states.len() - 1
+                });
+                let color = if next_remainder == 0 { 0 } else { 1 };
+                edges.push((source, symbol, color, target));
+            }
+        }
+
+        TSBuilder::without_state_colors()
+            .with_transitions(edges)
+            .into_dpa(0)
+    }
+}
@@ -0,0 +1,424 @@
+//! A small human-authorable textual syntax for [`DPA`]s, close to the `(src, sym, color, dst)`
+//! transition tuples used throughout this module, plus a loader ([`parse_dsl`]) for it.
+//!
+//! A document is an `initial:` declaration, an optional `acceptance:` declaration (only
+//! `parity min even` is supported, since that's the only semantics a [`DPA`] carries - this
+//! matches [`DPA::from_hoa`](super::DPA::from_hoa)'s restriction for the same reason), and any
+//! number of transition tuples `(src, 'sym', color, dst)`, each terminated by a `;`. Statements
+//! may appear in any order and `#` starts a line comment. For example:
+//!
+//! ```text
+//! initial: 0;
+//! acceptance: parity min even;
+//! (0, 'a', 1, 1);
+//! (0, 'b', 1, 0);
+//! (1, 'a', 0, 0);
+//! (1, 'b', 1, 0);
+//! ```
+//!
+//! This crate doesn't vendor a parser-combinator library with built-in error recovery
+//! (`chumsky` or similar), so [`parse_dsl`] is a hand-rolled recursive-descent parser in the
+//! style of [`Regex::parse`](crate::ts::regex::Regex::parse), with the one addition that a
+//! malformed statement doesn't abort parsing: it's recorded as a [`Diagnostic`] and the parser
+//! resynchronizes just past the next `;` so that later, well-formed statements
+//! still contribute to the result. Multiple diagnostics can therefore come back from a single
+//! call, each with the byte span of the offending token and the set of tokens that would have
+//! been accepted there.
+use std::fmt;
+use std::ops::Range;
+
+use crate::core::Int;
+use crate::core::alphabet::CharAlphabet;
+use crate::ts::TSBuilder;
+
+use super::DPA;
+
+/// One malformed piece of a document passed to [`parse_dsl`]: the byte span of the offending
+/// token, a description of what was actually found there, and the set of things that would
+/// have been accepted instead.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    /// The byte range of the offending token within the source text.
+    pub span: Range<usize>,
+    /// A description of the token that was actually found at `span`.
+    pub found: String,
+    /// Descriptions of the tokens that would have been accepted at `span` instead.
+    pub expected: Vec<String>,
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "at {}..{}: found {}, expected one of: {}",
+            self.span.start,
+            self.span.end,
+            self.found,
+            self.expected.join(", ")
+        )
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum TokenKind {
+    Ident(String),
+    Number(String),
+    Char(char),
+    LParen,
+    RParen,
+    Comma,
+    Colon,
+    Semicolon,
+    Eof,
+}
+
+impl fmt::Display for TokenKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TokenKind::Ident(s) => write!(f, "identifier '{s}'"),
+            TokenKind::Number(s) => write!(f, "number '{s}'"),
+            TokenKind::Char(c) => write!(f, "character literal '{c}'"),
+            TokenKind::LParen => write!(f, "'('"),
+            TokenKind::RParen => write!(f, "')'"),
+            TokenKind::Comma => write!(f, "','"),
+            TokenKind::Colon => write!(f, "':'"),
+            TokenKind::Semicolon => write!(f, "';'"),
+            TokenKind::Eof => write!(f, "end of input"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Token {
+    kind: TokenKind,
+    span: Range<usize>,
+}
+
+fn tokenize(source: &str) -> Result<Vec<Token>, Diagnostic> {
+    let mut tokens = Vec::new();
+    let mut chars = source.char_indices().peekable();
+
+    while let Some(&(start, c)) = chars.peek() {
+        match c {
+            c if c.is_whitespace() => {
+                chars.next();
+            }
+            '#' => {
+                while chars.next_if(|&(_, c)| c != '\n').is_some() {}
+            }
+            '(' => {
+                chars.next();
+                tokens.push(Token { kind: TokenKind::LParen, span: start..start + 1 });
+            }
+            ')' => {
+                chars.next();
+                tokens.push(Token { kind: TokenKind::RParen, span: start..start + 1 });
+            }
+            ',' => {
+                chars.next();
+                tokens.push(Token { kind: TokenKind::Comma, span: start..start + 1 });
+            }
+            ':' => {
+                chars.next();
+                tokens.push(Token { kind: TokenKind::Colon, span: start..start + 1 });
+            }
+            ';' => {
+                chars.next();
+                tokens.push(Token { kind: TokenKind::Semicolon, span: start..start + 1 });
+            }
+            '\'' => {
+                chars.next();
+                let Some((_, literal)) = chars.next() else {
+                    return Err(Diagnostic {
+                        span: start..source.len(),
+                        found: "end of input".to_string(),
+                        expected: vec!["a character".to_string()],
+                    });
+                };
+                match chars.next() {
+                    Some((end, '\'')) => {
+                        tokens.push(Token { kind: TokenKind::Char(literal), span: start..end + 1 });
+                    }
+                    Some((end, other)) => {
+                        return Err(Diagnostic {
+                            span: end..end + other.len_utf8(),
+                            found: format!("'{other}'"),
+                            expected: vec!["closing \"'\"".to_string()],
+                        });
+                    }
+                    None => {
+                        return Err(Diagnostic {
+                            span: start..source.len(),
+                            found: "end of input".to_string(),
+                            expected: vec!["closing \"'\"".to_string()],
+                        });
+                    }
+                }
+            }
+            c if c.is_ascii_digit() => {
+                let mut end = start + c.len_utf8();
+                chars.next();
+                while let Some(&(pos, c)) = chars.peek() {
+                    if c.is_ascii_digit() {
+                        chars.next();
+                        end = pos + c.len_utf8();
+                    } else {
+                        break;
+                    }
+                }
+                tokens.push(Token { kind: TokenKind::Number(source[start..end].to_string()), span: start..end });
+            }
+            c if c.is_alphabetic() || c == '_' => {
+                let mut end = start + c.len_utf8();
+                chars.next();
+                while let Some(&(pos, c)) = chars.peek() {
+                    if c.is_alphanumeric() || c == '_' {
+                        chars.next();
+                        end = pos + c.len_utf8();
+                    } else {
+                        break;
+                    }
+                }
+                tokens.push(Token { kind: TokenKind::Ident(source[start..end].to_string()), span: start..end });
+            }
+            other => {
+                return Err(Diagnostic {
+                    span: start..start + other.len_utf8(),
+                    found: format!("'{other}'"),
+                    expected: vec![
+                        "'('".to_string(),
+                        "an identifier".to_string(),
+                        "a number".to_string(),
+                    ],
+                });
+            }
+        }
+    }
+
+    let eof = source.len();
+    tokens.push(Token { kind: TokenKind::Eof, span: eof..eof });
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl Parser {
+    fn peek(&self) -> &Token {
+        &self.tokens[self.pos]
+    }
+
+    fn advance(&mut self) -> Token {
+        let token = self.tokens[self.pos].clone();
+        if self.pos + 1 < self.tokens.len() {
+            self.pos += 1;
+        }
+        token
+    }
+
+    fn error(&mut self, expected: &[&str]) -> Diagnostic {
+        let found = self.peek().kind.to_string();
+        let span = self.peek().span.clone();
+        Diagnostic {
+            span,
+            found,
+            expected: expected.iter().map(|s| s.to_string()).collect(),
+        }
+    }
+
+    /// Skips tokens until just past the next `;`, or until [`TokenKind::Eof`], so that parsing
+    /// can resume at the next statement after a malformed one.
+    fn resynchronize(&mut self) {
+        loop {
+            match self.peek().kind {
+                TokenKind::Semicolon => {
+                    self.advance();
+                    return;
+                }
+                TokenKind::Eof => return,
+                _ => {
+                    self.advance();
+                }
+            }
+        }
+    }
+
+    fn expect_number(&mut self) -> Result<usize, Diagnostic> {
+        match &self.peek().kind {
+            TokenKind::Number(digits) => {
+                let digits = digits.clone();
+                self.advance();
+                Ok(digits.parse().expect("scanned only ASCII digits"))
+            }
+            _ => Err(self.error(&["a number"])),
+        }
+    }
+
+    fn expect(&mut self, kind: TokenKind, description: &str) -> Result<(), Diagnostic> {
+        if self.peek().kind == kind {
+            self.advance();
+            Ok(())
+        } else {
+            Err(self.error(&[description]))
+        }
+    }
+
+    fn expect_ident(&mut self, word: &str) -> Result<(), Diagnostic> {
+        match &self.peek().kind {
+            TokenKind::Ident(ident) if ident == word => {
+                self.advance();
+                Ok(())
+            }
+            _ => Err(self.error(&[word])),
+        }
+    }
+
+    fn parse_initial(&mut self) -> Result<usize, Diagnostic> {
+        self.expect_ident("initial")?;
+        self.expect(TokenKind::Colon, "':'")?;
+        let state = self.expect_number()?;
+        self.expect(TokenKind::Semicolon, "';'")?;
+        Ok(state)
+    }
+
+    fn parse_acceptance(&mut self) -> Result<(), Diagnostic> {
+        self.expect_ident("acceptance")?;
+        self.expect(TokenKind::Colon, "':'")?;
+        self.expect_ident("parity")?;
+        self.expect_ident("min")?;
+        self.expect_ident("even")?;
+        self.expect(TokenKind::Semicolon, "';'")?;
+        Ok(())
+    }
+
+    fn parse_transition(&mut self) -> Result<(usize, char, Int, usize), Diagnostic> {
+        self.expect(TokenKind::LParen, "'('")?;
+        let src = self.expect_number()?;
+        self.expect(TokenKind::Comma, "','")?;
+        let sym = match &self.peek().kind {
+            TokenKind::Char(c) => {
+                let c = *c;
+                self.advance();
+                c
+            }
+            _ => return Err(self.error(&["a character literal"])),
+        };
+        self.expect(TokenKind::Comma, "','")?;
+        let color = self.expect_number()? as Int;
+        self.expect(TokenKind::Comma, "','")?;
+        let dst = self.expect_number()?;
+        self.expect(TokenKind::RParen, "')'")?;
+        self.expect(TokenKind::Semicolon, "';'")?;
+        Ok((src, sym, color, dst))
+    }
+}
+
+/// Parses a [`DPA`] out of the textual syntax described in the [module documentation](self).
+///
+/// On success, returns the automaton described by the document. On failure, returns every
+/// [`Diagnostic`] collected along the way rather than just the first: a malformed statement is
+/// skipped up to its next `;` and parsing continues from there, so a single call can report
+/// several independent mistakes (a bad state id here, a missing comma there) in one pass.
+///
+/// # Example
+/// ```
+/// use automata::automaton::omega::parse_dsl;
+///
+/// let dpa = parse_dsl(
+///     "initial: 0;
+///      acceptance: parity min even;
+///      (0, 'a', 1, 1);
+///      (0, 'b', 1, 0);
+///      (1, 'a', 0, 0);
+///      (1, 'b', 1, 0);",
+/// )
+/// .unwrap();
+/// assert!(dpa.give_rejected_word().is_some());
+///
+/// let diagnostics = parse_dsl("initial: 0; (0, 'a' 1, 1);").unwrap_err();
+/// assert_eq!(diagnostics.len(), 1);
+/// ```
+pub fn parse_dsl(source: &str) -> Result<DPA<CharAlphabet>, Vec<Diagnostic>> {
+    let tokens = tokenize(source).map_err(|d| vec![d])?;
+    let mut parser = Parser { tokens, pos: 0, diagnostics: Vec::new() };
+
+    let mut initial = None;
+    let mut edges = Vec::new();
+
+    while parser.peek().kind != TokenKind::Eof {
+        let is_keyword = |word: &str| matches!(&parser.peek().kind, TokenKind::Ident(ident) if ident == word);
+        let result = if is_keyword("initial") {
+            parser.parse_initial().map(|state| initial = Some(state))
+        } else if is_keyword("acceptance") {
+            parser.parse_acceptance()
+        } else if parser.peek().kind == TokenKind::LParen {
+            parser.parse_transition().map(|edge| edges.push(edge))
+        } else {
+            Err(parser.error(&["'initial'", "'acceptance'", "'('"]))
+        };
+
+        if let Err(diagnostic) = result {
+            parser.diagnostics.push(diagnostic);
+            parser.resynchronize();
+        }
+    }
+
+    let Some(initial) = initial else {
+        parser.diagnostics.push(Diagnostic {
+            span: source.len()..source.len(),
+            found: "end of input".to_string(),
+            expected: vec!["an 'initial: <state>;' declaration".to_string()],
+        });
+        return Err(parser.diagnostics);
+    };
+
+    if !parser.diagnostics.is_empty() {
+        return Err(parser.diagnostics);
+    }
+
+    Ok(TSBuilder::without_state_colors()
+        .with_transitions(edges)
+        .into_dpa(initial))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_dsl;
+
+    #[test]
+    fn parses_well_formed_document() {
+        let dpa = parse_dsl(
+            "initial: 0;
+             acceptance: parity min even;
+             (0, 'a', 1, 1);
+             (0, 'b', 1, 0);
+             (1, 'a', 0, 0);
+             (1, 'b', 1, 0);",
+        )
+        .unwrap();
+        assert!(dpa.give_accepted_word().is_some());
+        assert!(dpa.give_rejected_word().is_some());
+    }
+
+    #[test]
+    fn collects_multiple_diagnostics() {
+        let err = parse_dsl(
+            "initial: 0;
+             (0, 'a' 1, 1);
+             (1 'b', 1, 0);",
+        )
+        .unwrap_err();
+        assert_eq!(err.len(), 2);
+        assert!(err[0].expected.iter().any(|e| e == "','"));
+    }
+
+    #[test]
+    fn reports_missing_initial_declaration() {
+        let err = parse_dsl("(0, 'a', 1, 0);").unwrap_err();
+        assert_eq!(err.len(), 1);
+        assert!(err[0].expected.iter().any(|e| e.contains("initial")));
+    }
+}
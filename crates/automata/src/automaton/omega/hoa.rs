@@ -0,0 +1,325 @@
+//! Serializes a [`DPA`] into the Hanoi Omega-Automata format, and parses a (deliberately
+//! restricted) subset of it back via [`DPA::from_hoa`].
+//!
+//! The `hoars` crate's own representation is built around its parser and is not (yet)
+//! constructible from outside that crate, so [`IntoDPA::to_hoa`] emits the textual format
+//! directly. It reuses `hoars`'s `Display` impls for the acceptance condition and its
+//! name/property vocabulary, so the synthesized `Acceptance:` and `acc-name:` lines agree
+//! with what `hoars::from_hoa` expects to read back, keeping the priority-to-acceptance-set
+//! mapping intact across a round trip.
+//!
+//! [`DPA::from_hoa`] is the other direction, implemented as a direct hand-rolled parser (in
+//! the style of [`Regex::parse`](crate::ts::regex::Regex::parse)) rather than against
+//! `hoars`'s own grammar, for the same reason `to_hoa` doesn't emit through `hoars`: there is
+//! no constructor from a `hoars` document back into this crate's transition systems. It only
+//! understands the shape [`IntoDPA::to_hoa`] actually produces - explicit `[i]`/`[i | j | ...]`
+//! disjunctions of AP indices as edge labels, a singleton acceptance-set tag `{k}` per edge
+//! taken directly as that edge's priority, and `State: n` body blocks - and rejects any
+//! `acc-name:` other than min-even parity, since that's the only semantics a [`DPA`] carries.
+//! Within that shape, `from_hoa(dpa.to_hoa())` is language-equivalent to `dpa`.
+
+use hoars::{AcceptanceCondition, AcceptanceInfo, AcceptanceName, Property};
+use itertools::Itertools;
+
+use crate::core::Int;
+use crate::core::alphabet::{Alphabet, CharAlphabet, Expression};
+use crate::ts::{Deterministic, IsEdge, TSBuilder};
+use crate::{Pointed, TransitionSystem};
+
+use super::{DPA, IntoDPA};
+
+/// Synthesizes the `Acceptance:` condition for a min-even parity acceptance over
+/// priorities `0..num_priorities`, using the standard parity encoding
+/// `Inf(0) | (Fin(1) & (Inf(2) | (Fin(3) & ...)))`.
+fn parity_acceptance_condition(num_priorities: usize) -> AcceptanceCondition {
+    fn build(priority: u32, num_priorities: u32) -> AcceptanceCondition {
+        if priority + 1 == num_priorities {
+            return if priority % 2 == 0 {
+                AcceptanceCondition::Inf(priority)
+            } else {
+                AcceptanceCondition::Fin(priority)
+            };
+        }
+        let rest = build(priority + 1, num_priorities);
+        if priority % 2 == 0 {
+            AcceptanceCondition::Inf(priority).or(rest)
+        } else {
+            AcceptanceCondition::Fin(priority).and(rest)
+        }
+    }
+    build(0, num_priorities.max(1) as u32)
+}
+
+impl<D> IntoDPA<D>
+where
+    D: Deterministic<EdgeColor = Int>,
+{
+    /// Serializes `self` into a HOA document, tagging each transition with an
+    /// acceptance set equal to its priority. The number of acceptance sets is one
+    /// plus the greatest priority occurring in `self`.
+    ///
+    /// # Example
+    /// ```
+    /// use automata::ts::TSBuilder;
+    ///
+    /// let dpa = TSBuilder::without_state_colors()
+    ///     .with_transitions([(0, 'a', 0, 1), (1, 'a', 1, 0)])
+    ///     .into_dpa(0);
+    /// let hoa = dpa.to_hoa();
+    /// assert!(hoa.starts_with("HOA: v1"));
+    /// assert!(hoa.contains("Start: 0"));
+    /// ```
+    pub fn to_hoa(&self) -> String {
+        let symbols: Vec<_> = self.alphabet().universe().collect();
+        let max_priority = self
+            .state_indices()
+            .filter_map(|q| self.edges_from(q))
+            .flatten()
+            .map(|edge| edge.color())
+            .max()
+            .unwrap_or(0);
+        let num_sets = max_priority as usize + 1;
+
+        let mut lines = vec![
+            "HOA: v1".to_string(),
+            format!("States: {}", self.state_indices().count()),
+            format!("Start: {}", self.initial()),
+            format!(
+                "AP: {} {}",
+                symbols.len(),
+                symbols.iter().map(|s| format!("\"{s}\"")).join(" ")
+            ),
+            format!(
+                "Acceptance: {} {}",
+                num_sets,
+                parity_acceptance_condition(num_sets)
+            ),
+            format!(
+                "acc-name: {} {}",
+                AcceptanceName::Parity,
+                [
+                    AcceptanceInfo::Identifier("min".into()),
+                    AcceptanceInfo::Identifier("even".into()),
+                    AcceptanceInfo::Int(num_sets as i32),
+                ]
+                .iter()
+                .join(" ")
+            ),
+            format!(
+                "properties: {} {} {}",
+                Property::Deterministic,
+                Property::TransLabels,
+                Property::ExplicitLabels
+            ),
+            "--BODY--".to_string(),
+        ];
+
+        for state in self.state_indices() {
+            lines.push(format!("State: {state}"));
+            if let Some(edges) = self.edges_from(state) {
+                for edge in edges {
+                    let label = symbols
+                        .iter()
+                        .enumerate()
+                        .filter(|(_, sym)| edge.expression().symbols().any(|s| &s == *sym))
+                        .map(|(i, _)| i.to_string())
+                        .join(" | ");
+                    lines.push(format!("[{label}] {} {{{}}}", edge.target(), edge.color()));
+                }
+            }
+        }
+        lines.push("--END--".to_string());
+        lines.join("\n")
+    }
+}
+
+impl DPA<CharAlphabet> {
+    /// Parses a HOA document produced by [`IntoDPA::to_hoa`] (or anything with the same shape)
+    /// back into a [`DPA`]. See the [module documentation](self) for exactly what shape that is.
+    ///
+    /// # Example
+    /// ```
+    /// use automata::automaton::omega::DPA;
+    /// use automata::ts::TSBuilder;
+    ///
+    /// let dpa = TSBuilder::without_state_colors()
+    ///     .with_transitions([(0, 'a', 0, 1), (1, 'a', 1, 0)])
+    ///     .into_dpa(0);
+    /// let parsed = DPA::from_hoa(&dpa.to_hoa()).unwrap();
+    /// assert!(parsed.language_equivalent(&dpa));
+    /// ```
+    pub fn from_hoa(hoa: &str) -> Result<DPA<CharAlphabet>, String> {
+        let mut lines = hoa.lines().map(str::trim);
+
+        match lines.next() {
+            Some(line) if line.starts_with("HOA:") => {}
+            _ => return Err("expected a leading 'HOA:' header line".to_string()),
+        }
+
+        let mut symbols: Option<Vec<char>> = None;
+        let mut start: Option<usize> = None;
+        for line in lines.by_ref() {
+            if line == "--BODY--" {
+                break;
+            }
+            if let Some(rest) = line.strip_prefix("Start:") {
+                start = Some(
+                    rest.trim()
+                        .parse()
+                        .map_err(|_| format!("invalid 'Start:' header: {rest}"))?,
+                );
+            } else if let Some(rest) = line.strip_prefix("AP:") {
+                symbols = Some(parse_ap_symbols(rest.trim())?);
+            } else if let Some(rest) = line.strip_prefix("acc-name:") {
+                let name = rest.trim();
+                if !name.starts_with("parity min even") {
+                    return Err(format!("unsupported acceptance condition: {name}"));
+                }
+            }
+            // `States:`, `Acceptance:` and `properties:` are informational only: the body is
+            // parsed exhaustively regardless of what they claim.
+        }
+
+        let symbols = symbols.ok_or_else(|| "missing 'AP:' header".to_string())?;
+        let initial = start.ok_or_else(|| "missing 'Start:' header".to_string())?;
+
+        let mut edges = Vec::new();
+        let mut current_state: Option<usize> = None;
+        for line in lines.by_ref() {
+            if line == "--END--" {
+                break;
+            }
+            if line.is_empty() {
+                continue;
+            }
+            if let Some(rest) = line.strip_prefix("State:") {
+                current_state = Some(
+                    rest.trim()
+                        .parse()
+                        .map_err(|_| format!("invalid 'State:' line: {rest}"))?,
+                );
+                continue;
+            }
+
+            let source = current_state
+                .ok_or_else(|| format!("edge line before any 'State:' line: {line}"))?;
+            let (label, rest) = line
+                .strip_prefix('[')
+                .and_then(|r| r.split_once(']'))
+                .ok_or_else(|| format!("expected a '[...]' label on edge line: {line}"))?;
+            let (target, acc) = rest
+                .trim()
+                .split_once('{')
+                .ok_or_else(|| format!("expected a '{{...}}' acceptance tag on edge line: {line}"))?;
+            let target: usize = target
+                .trim()
+                .parse()
+                .map_err(|_| format!("invalid edge target: {}", target.trim()))?;
+            let acc = acc
+                .strip_suffix('}')
+                .ok_or_else(|| format!("unterminated acceptance tag on edge line: {line}"))?;
+            let color: Int = acc
+                .trim()
+                .parse()
+                .map_err(|_| format!("invalid acceptance set: {}", acc.trim()))?;
+
+            for index in label.split('|') {
+                let index: usize = index
+                    .trim()
+                    .parse()
+                    .map_err(|_| format!("invalid AP index in label: {}", index.trim()))?;
+                let symbol = *symbols
+                    .get(index)
+                    .ok_or_else(|| format!("AP index out of range: {index}"))?;
+                edges.push((source, symbol, color, target));
+            }
+        }
+
+        Ok(TSBuilder::without_state_colors()
+            .with_transitions(edges)
+            .into_dpa(initial))
+    }
+}
+
+/// Parses the quoted AP name list of an `AP:` header line (after the leading count) into the
+/// single-`char` symbols [`IntoDPA::to_hoa`] writes there.
+fn parse_ap_symbols(rest: &str) -> Result<Vec<char>, String> {
+    let mut parts = rest.split_whitespace();
+    let count: usize = parts
+        .next()
+        .ok_or_else(|| "empty 'AP:' header".to_string())?
+        .parse()
+        .map_err(|_| "invalid AP count in 'AP:' header".to_string())?;
+
+    let names: Vec<char> = parts
+        .map(|quoted| {
+            let inner = quoted
+                .strip_prefix('"')
+                .and_then(|s| s.strip_suffix('"'))
+                .ok_or_else(|| format!("expected a quoted AP name: {quoted}"))?;
+            let mut chars = inner.chars();
+            match (chars.next(), chars.next()) {
+                (Some(c), None) => Ok(c),
+                _ => Err(format!("AP name is not a single character: {quoted}")),
+            }
+        })
+        .collect::<Result<_, String>>()?;
+
+    if names.len() != count {
+        return Err(format!(
+            "'AP:' header declares {count} names but lists {}",
+            names.len()
+        ));
+    }
+    Ok(names)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DPA;
+    use crate::ts::TSBuilder;
+
+    #[test]
+    fn emits_hoa_header_and_body() {
+        let dpa = TSBuilder::without_state_colors()
+            .with_transitions([(0, 'a', 0, 1), (1, 'a', 1, 0)])
+            .into_dpa(0);
+        let hoa = dpa.to_hoa();
+        assert!(hoa.starts_with("HOA: v1"));
+        assert!(hoa.contains("Start: 0"));
+        assert!(hoa.contains("--BODY--"));
+        assert!(hoa.contains("--END--"));
+        assert!(hoa.contains("Acceptance: 2"));
+    }
+
+    #[test]
+    fn round_trips_through_hoa() {
+        let dpa = TSBuilder::without_state_colors()
+            .with_transitions([
+                (0, 'a', 0, 1),
+                (0, 'b', 1, 2),
+                (1, 'a', 1, 2),
+                (1, 'b', 0, 1),
+                (2, 'a', 1, 2),
+                (2, 'b', 1, 2),
+            ])
+            .into_dpa(0);
+        let parsed = DPA::from_hoa(&dpa.to_hoa()).expect("emitted HOA should parse back");
+        assert!(parsed.language_equivalent(&dpa));
+    }
+
+    #[test]
+    fn rejects_non_parity_acceptance() {
+        let hoa = "HOA: v1\n\
+                   States: 1\n\
+                   Start: 0\n\
+                   AP: 1 \"a\"\n\
+                   Acceptance: 1 Inf(0)\n\
+                   acc-name: Buchi\n\
+                   --BODY--\n\
+                   State: 0\n\
+                   [0] 0 {0}\n\
+                   --END--";
+        assert!(DPA::from_hoa(hoa).is_err());
+    }
+}
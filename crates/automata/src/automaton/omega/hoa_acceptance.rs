@@ -0,0 +1,265 @@
+//! Classifies an arbitrary `hoars::AcceptanceCondition` -- as might come out of parsing a
+//! real HOA document rather than one of this crate's own constructors -- and lowers the
+//! recognized shape onto a [`DPA`], the read-side counterpart to
+//! [`super::hoa::IntoDPA::to_hoa`].
+//!
+//! `hoars::AcceptanceCondition::recognize` only matches the exact shapes its own
+//! `buchi`/`rabin`/`streett`/... constructors (and `parity`) produce. A condition parsed
+//! out of a real HOA file can be an arbitrary, differently-bracketed boolean combination
+//! that is *semantically* the same thing, e.g. `Inf(0) | Fin(1)` instead of the canonical
+//! `Fin(1) | Inf(0)`. [`recognize_priorities`] instead evaluates the formula against every
+//! assignment of "does set `i` occur infinitely often" for the sets it references and
+//! compares truth tables with the canonical min/max-even parity chain, which is exact as
+//! long as the condition doesn't mention more than [`MAX_BRUTE_FORCE_SETS`] sets.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use hoars::{AcceptanceAtom, AcceptanceCondition, HoaBool, Id};
+
+use crate::core::Int;
+use crate::representation::IntoTs;
+use crate::ts::Deterministic;
+use crate::Pointed;
+
+use super::DPA;
+
+/// Above this many distinct referenced acceptance sets, [`recognize_priorities`] gives up
+/// rather than walking a truth table of that size.
+const MAX_BRUTE_FORCE_SETS: usize = 20;
+
+/// The acceptance-condition families [`recognize_priorities`] can identify.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RecognizedCondition {
+    /// `Inf(c)`: accept iff set `c` occurs infinitely often.
+    Buchi(Id),
+    /// `Fin(c)`: accept iff set `c` occurs only finitely often.
+    CoBuchi(Id),
+    /// An alternating parity chain over the referenced sets, read least-first.
+    /// `colors[i]` is the set treated as HOA priority `i`; `min_even` selects between
+    /// the min-even and max-even reading of that chain.
+    Parity { colors: Vec<Id>, min_even: bool },
+}
+
+/// Collects every acceptance-set index referenced anywhere in `cond`.
+fn referenced_sets(cond: &AcceptanceCondition) -> BTreeSet<Id> {
+    match cond {
+        AcceptanceCondition::Fin(atom) | AcceptanceCondition::Inf(atom) => {
+            BTreeSet::from([atom_set(atom)])
+        }
+        AcceptanceCondition::And(left, right) | AcceptanceCondition::Or(left, right) => {
+            let mut sets = referenced_sets(left);
+            sets.extend(referenced_sets(right));
+            sets
+        }
+        AcceptanceCondition::Boolean(_) => BTreeSet::new(),
+    }
+}
+
+fn atom_set(atom: &AcceptanceAtom) -> Id {
+    match atom {
+        AcceptanceAtom::Positive(id) | AcceptanceAtom::Negative(id) => *id,
+    }
+}
+
+fn atom_holds(atom: &AcceptanceAtom, infinitely_often: &BTreeMap<Id, bool>) -> bool {
+    match atom {
+        AcceptanceAtom::Positive(id) => infinitely_often[id],
+        AcceptanceAtom::Negative(id) => !infinitely_often[id],
+    }
+}
+
+/// Evaluates `cond` against a fixed choice of which referenced sets occur infinitely
+/// often.
+fn evaluate(cond: &AcceptanceCondition, infinitely_often: &BTreeMap<Id, bool>) -> bool {
+    match cond {
+        AcceptanceCondition::Fin(atom) => !atom_holds(atom, infinitely_often),
+        AcceptanceCondition::Inf(atom) => atom_holds(atom, infinitely_often),
+        AcceptanceCondition::And(left, right) => {
+            evaluate(left, infinitely_often) && evaluate(right, infinitely_often)
+        }
+        AcceptanceCondition::Or(left, right) => {
+            evaluate(left, infinitely_often) || evaluate(right, infinitely_often)
+        }
+        AcceptanceCondition::Boolean(HoaBool(value)) => *value,
+    }
+}
+
+/// Checks whether `a` and `b` agree under every assignment of "occurs infinitely often"
+/// to the sets in `sets`.
+fn truth_table_equal(a: &AcceptanceCondition, b: &AcceptanceCondition, sets: &[Id]) -> bool {
+    (0u32..1 << sets.len()).all(|mask| {
+        let infinitely_often: BTreeMap<Id, bool> = sets
+            .iter()
+            .enumerate()
+            .map(|(bit, id)| (*id, mask & (1 << bit) != 0))
+            .collect();
+        evaluate(a, &infinitely_often) == evaluate(b, &infinitely_often)
+    })
+}
+
+/// Builds the alternating chain `Inf(colors[0]) | (Fin(colors[1]) & (...))` over
+/// `colors`, the same shape as `hoars::AcceptanceCondition::parity_rec` but over an
+/// arbitrary (possibly non-contiguous) sequence of set ids, taken in order, instead of a
+/// fixed `0..n` range.
+fn parity_chain(colors: &[Id]) -> AcceptanceCondition {
+    match colors {
+        [] => AcceptanceCondition::Boolean(HoaBool(false)),
+        [only] => AcceptanceCondition::id_inf(*only),
+        [first, rest @ ..] => AcceptanceCondition::id_inf(*first).or(parity_chain_odd(rest)),
+    }
+}
+
+fn parity_chain_odd(colors: &[Id]) -> AcceptanceCondition {
+    match colors {
+        [] => AcceptanceCondition::Boolean(HoaBool(true)),
+        [only] => AcceptanceCondition::id_fin(*only),
+        [first, rest @ ..] => AcceptanceCondition::id_fin(*first).and(parity_chain(rest)),
+    }
+}
+
+/// Classifies `cond` against the families in [`RecognizedCondition`], using exact
+/// truth-table comparison rather than shape matching so that a differently-parenthesized
+/// but equivalent formula (as can come out of parsing a real HOA file) is still
+/// recognized. Gives up (returns `None`) once `cond` references more sets than
+/// [`MAX_BRUTE_FORCE_SETS`], or doesn't match any of the known shapes.
+pub fn recognize_priorities(cond: &AcceptanceCondition) -> Option<RecognizedCondition> {
+    let sets: Vec<Id> = referenced_sets(cond).into_iter().collect();
+    if sets.is_empty() || sets.len() > MAX_BRUTE_FORCE_SETS {
+        return None;
+    }
+
+    if let [set] = sets.as_slice() {
+        if truth_table_equal(cond, &AcceptanceCondition::id_inf(*set), &sets) {
+            return Some(RecognizedCondition::Buchi(*set));
+        }
+        if truth_table_equal(cond, &AcceptanceCondition::id_fin(*set), &sets) {
+            return Some(RecognizedCondition::CoBuchi(*set));
+        }
+    }
+
+    if truth_table_equal(cond, &parity_chain(&sets), &sets) {
+        return Some(RecognizedCondition::Parity {
+            colors: sets,
+            min_even: true,
+        });
+    }
+    // Under max-even, the greatest set heads the chain and is accepting iff its priority
+    // `sets.len() - 1` is even.
+    let reversed: Vec<Id> = sets.iter().rev().copied().collect();
+    let max_even_chain = if sets.len() % 2 == 1 {
+        parity_chain(&reversed)
+    } else {
+        parity_chain_odd(&reversed)
+    };
+    if truth_table_equal(cond, &max_even_chain, &sets) {
+        return Some(RecognizedCondition::Parity {
+            colors: sets,
+            min_even: false,
+        });
+    }
+
+    None
+}
+
+/// Lowers a [`RecognizedCondition`] onto a concrete [`DPA`], given a transition system
+/// whose edge colors already carry the raw HOA acceptance-set id of each transition (the
+/// same convention [`super::hoa::IntoDPA::to_hoa`] emits under: one acceptance set per
+/// transition, equal to its edge color). `body` must only use set ids that `recognized`
+/// actually references, i.e. it should be the same automaton `recognized` was computed
+/// from via [`recognize_priorities`].
+///
+/// `Buchi` and the min-even reading of `Parity` are both already min-even parity
+/// conditions over the rank of their referenced sets read least-first, so lowering them
+/// just recolors each transition from its raw set id to that set's rank and collects the
+/// result into a [`DPA`]. `CoBuchi` and the max-even reading of `Parity` would need a
+/// `DPA` built with a non-default `Sem`, and this crate snapshot exposes no constructor
+/// for that from a transition system, so both are left unsupported and return `None`.
+pub fn lower<D>(recognized: &RecognizedCondition, body: D) -> Option<DPA<D::Alphabet>>
+where
+    D: Deterministic<EdgeColor = Int> + Pointed + IntoTs,
+{
+    let initial = body.initial();
+    match recognized {
+        RecognizedCondition::Buchi(set) => {
+            let set = *set as Int;
+            Some(
+                body.map_edge_colors(move |c| if c == set { 0 } else { 1 })
+                    .with_initial(initial)
+                    .collect_dpa(),
+            )
+        }
+        RecognizedCondition::Parity {
+            colors,
+            min_even: true,
+        } => {
+            let ranks: BTreeMap<Int, Int> = colors
+                .iter()
+                .enumerate()
+                .map(|(rank, id)| (*id as Int, rank as Int))
+                .collect();
+            Some(
+                body.map_edge_colors(move |c| ranks[&c])
+                    .with_initial(initial)
+                    .collect_dpa(),
+            )
+        }
+        RecognizedCondition::CoBuchi(_) | RecognizedCondition::Parity { .. } => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ts::TSBuilder;
+
+    #[test]
+    fn recognizes_buchi_regardless_of_wrapper() {
+        let cond = AcceptanceCondition::id_inf(3).or(AcceptanceCondition::Boolean(HoaBool(false)));
+        assert_eq!(
+            recognize_priorities(&cond),
+            Some(RecognizedCondition::Buchi(3))
+        );
+    }
+
+    #[test]
+    fn recognizes_co_buchi() {
+        let cond = AcceptanceCondition::id_fin(2);
+        assert_eq!(
+            recognize_priorities(&cond),
+            Some(RecognizedCondition::CoBuchi(2))
+        );
+    }
+
+    #[test]
+    fn recognizes_parity_built_out_of_order() {
+        // (Fin(1) & Inf(2)) | Inf(0), written with set ids out of numeric order, is the
+        // same alternating chain as parity_chain([0, 1, 2]).
+        let cond = AcceptanceCondition::id_fin(1)
+            .and(AcceptanceCondition::id_inf(2))
+            .or(AcceptanceCondition::id_inf(0));
+        assert_eq!(
+            recognize_priorities(&cond),
+            Some(RecognizedCondition::Parity {
+                colors: vec![0, 1, 2],
+                min_even: true,
+            })
+        );
+    }
+
+    #[test]
+    fn does_not_recognize_rabin_as_parity() {
+        let cond = AcceptanceCondition::id_fin(0)
+            .and(AcceptanceCondition::id_inf(1))
+            .or(AcceptanceCondition::id_fin(2).and(AcceptanceCondition::id_inf(3)));
+        assert_eq!(recognize_priorities(&cond), None);
+    }
+
+    #[test]
+    fn lowers_buchi_onto_a_dpa() {
+        let source = TSBuilder::without_state_colors()
+            .with_transitions([(0, 'a', 3, 0), (0, 'b', 5, 0)])
+            .into_dpa(0);
+        let lowered = lower(&RecognizedCondition::Buchi(3), source).unwrap();
+        assert!(lowered.give_accepted_word().is_some());
+    }
+}
@@ -0,0 +1,121 @@
+//! State-minimal [`DPA`] synthesis via counterexample-guided candidate search.
+//!
+//! Unlike [`IntoDPA::streamlined`], which only minimizes the *Mealy* structure underlying a
+//! fixed priority assignment, [`IntoDPA::minimized_states`] searches for the language-equivalent
+//! DPA with the fewest states outright - an NP-hard problem in general, since it also has to
+//! choose new transitions and priorities, not just merge existing states. The textbook approach
+//! is counterexample-guided abstraction refinement (CEGAR) over a SAT encoding of the candidate's
+//! transition function and edge priorities; this crate doesn't vendor a SAT backend, so
+//! [`candidate_for_size`] plays that role with a plain backtracking search over the same
+//! variables a SAT encoding would use. The CEGAR loop itself - grow a sample of ultimately
+//! periodic words, solve for a candidate consistent with all of them, check it against the
+//! reference with [`IntoDPA::witness_inequivalence`], and feed back any counterexample - is
+//! exactly what a SAT-backed version would do; only the "solve" step is a brute force instead of
+//! a solver call.
+use itertools::Itertools;
+
+use crate::core::{Int, alphabet::CharAlphabet, word::ReducedOmegaWord};
+use crate::ts::{Deterministic, TSBuilder, TransitionSystem};
+
+use super::{DPA, IntoDPA};
+
+impl<D> IntoDPA<D>
+where
+    D: Deterministic<EdgeColor = Int>,
+{
+    /// Synthesizes a language-equivalent [`DPA`] with the fewest possible states.
+    ///
+    /// Starts the search at the state count of [`Self::prefix_congruence`] (a lower bound, since
+    /// two states that are already prefix-congruent in `self` can certainly be merged) and bounds
+    /// candidate priorities by [`Self::low_and_high_priority`]. For each candidate size, repeatedly
+    /// finds *some* automaton of that size consistent with a growing sample of ultimately periodic
+    /// words and their reference acceptance value, checks it against `self` via
+    /// [`Self::witness_inequivalence`], and either returns it (no counterexample) or folds the
+    /// counterexample into the sample and searches again. If no candidate of a given size is
+    /// consistent with the sample at all, the size is incremented; this always terminates, since
+    /// `self`'s own size trivially admits a consistent, language-equivalent candidate.
+    ///
+    /// # Example
+    /// ```
+    /// use automata::ts::{Deterministic, TSBuilder, TransitionSystem};
+    ///
+    /// let dpa = TSBuilder::without_state_colors()
+    ///     .with_transitions([
+    ///         (0, 'a', 0, 1), (0, 'b', 1, 1),
+    ///         (1, 'a', 0, 0), (1, 'b', 1, 0),
+    ///     ])
+    ///     .into_dpa(0);
+    /// let minimal = dpa.minimized_states();
+    /// assert!(minimal.language_equivalent(&dpa));
+    /// assert_eq!(minimal.size(), 1);
+    /// ```
+    pub fn minimized_states(&self) -> DPA<CharAlphabet>
+    where
+        D: TransitionSystem<Alphabet = CharAlphabet>,
+    {
+        let symbols: Vec<char> = self.alphabet().universe().collect();
+        let (_, high) = self.low_and_high_priority();
+        let max_priority = high.max(1);
+
+        let mut samples: Vec<(ReducedOmegaWord<char>, bool)> = Vec::new();
+        if let Some(word) = self.give_accepted_word() {
+            samples.push((word, true));
+        }
+        if let Some(word) = self.give_rejected_word() {
+            samples.push((word, false));
+        }
+
+        let mut n = self.prefix_congruence().size().max(1);
+        loop {
+            let Some(candidate) = candidate_for_size(n, &symbols, max_priority, &samples) else {
+                n += 1;
+                continue;
+            };
+            match self.witness_inequivalence(&candidate) {
+                Some(cex) => {
+                    let expected = self.accepts(&cex);
+                    samples.push((cex, expected));
+                }
+                None => return candidate,
+            }
+        }
+    }
+}
+
+/// Exhaustively searches for an `n`-state [`DPA`] over `symbols`, with priorities bounded by
+/// `max_priority` and initial state fixed to index `0`, that is consistent with every
+/// `(word, accepted)` pair in `samples`. Stands in for the "solve" step of a SAT-based CEGAR
+/// loop; see the [module documentation](self).
+fn candidate_for_size(
+    n: usize,
+    symbols: &[char],
+    max_priority: Int,
+    samples: &[(ReducedOmegaWord<char>, bool)],
+) -> Option<DPA<CharAlphabet>> {
+    let num_edges = n * symbols.len();
+    if num_edges == 0 {
+        return None;
+    }
+
+    let targets = std::iter::repeat(0..n).take(num_edges).multi_cartesian_product();
+    for target in targets {
+        let priorities = std::iter::repeat(0..=max_priority)
+            .take(num_edges)
+            .multi_cartesian_product();
+        for priority in priorities {
+            let edges: Vec<(usize, char, Int, usize)> = (0..num_edges)
+                .map(|i| (i / symbols.len(), symbols[i % symbols.len()], priority[i], target[i]))
+                .collect();
+            let candidate = TSBuilder::without_state_colors()
+                .with_transitions(edges)
+                .into_dpa(0);
+            if samples
+                .iter()
+                .all(|(word, accepted)| candidate.accepts(word) == *accepted)
+            {
+                return Some(candidate);
+            }
+        }
+    }
+    None
+}
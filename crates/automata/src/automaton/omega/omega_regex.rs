@@ -0,0 +1,262 @@
+//! An ω-regular expression layer on top of [`DPA`], mirroring the finite-word
+//! [`Regex`](crate::ts::regex::Regex) module one level up: an [`OmegaRegex`] can be compiled
+//! into a [`DPA`] via [`OmegaRegex::into_dpa`], and [`IntoDPA::to_omega_regex`] extracts one
+//! back out of an automaton.
+//!
+//! Following the idea that an automaton should carry/produce the regular-language description
+//! it recognizes, an [`OmegaRegex`] is represented as a finite union of terms `U · V^ω`, where
+//! `U` and `V` are ordinary (finite-word) [`Regex`]es. Each term is compiled eagerly: as soon as
+//! the compiled automaton for `U` reaches an accepting state, control switches permanently to
+//! looping through `V`, restarting `V` every time one of its own accepting states is reached.
+//! This matches exactly the literal, unambiguous prefixes and loop words that
+//! [`IntoDPA::to_omega_regex`] derives from [`Scc::minimal_representative`] and
+//! [`Scc::maximal_loop_from`], which is what makes the round trip through [`into_dpa`]
+//! language-preserving for those terms.
+//!
+//! [`into_dpa`]: OmegaRegex::into_dpa
+//! [`Scc::minimal_representative`]: crate::ts::connected_components::Scc::minimal_representative
+//! [`Scc::maximal_loop_from`]: crate::ts::connected_components::Scc::maximal_loop_from
+
+use std::collections::{BTreeSet, HashMap};
+
+use crate::core::{Int, alphabet::CharAlphabet};
+use crate::representation::CollectTs;
+use crate::ts::operations::Product;
+use crate::ts::regex::Regex;
+use crate::ts::{Deterministic, IsEdge, StateIndex, TSBuilder, TransitionSystem};
+use crate::{DFA, Pointed};
+
+use super::{DPA, IntoDPA};
+
+/// An ω-regular expression over `char`, represented as a finite union of pairs `(U, V)`
+/// denoting the language `U · V^ω`, where `U` and `V` are finite-word [`Regex`]es.
+///
+/// See the [module documentation](self) for how terms are compiled.
+#[derive(Debug, Clone, Default)]
+pub struct OmegaRegex {
+    terms: Vec<(Regex, Regex)>,
+}
+
+impl OmegaRegex {
+    /// Builds the union of the given `(U, V)` terms, each denoting `U · V^ω`. Matches no
+    /// word at all if `terms` is empty.
+    pub fn new(terms: impl IntoIterator<Item = (Regex, Regex)>) -> Self {
+        Self {
+            terms: terms.into_iter().collect(),
+        }
+    }
+
+    /// Builds the single term `prefix · loop_body^ω`.
+    pub fn term(prefix: Regex, loop_body: Regex) -> Self {
+        Self::new([(prefix, loop_body)])
+    }
+
+    /// Builds the union of `self` and `other`, i.e. the expression matching every word matched
+    /// by either.
+    pub fn or(mut self, other: OmegaRegex) -> Self {
+        self.terms.extend(other.terms);
+        self
+    }
+
+    /// Compiles `self` into a [`DPA`] over a [`CharAlphabet`] containing (at least) every
+    /// symbol mentioned by any of its terms.
+    ///
+    /// Each term is first compiled into its own deterministic component accepting exactly
+    /// `U · V^ω` (see the [module documentation](self)), using priority `0` for transitions
+    /// that just completed a `V`-iteration and priority `1` for every other transition, which
+    /// realizes a min-even parity embedding of the usual Büchi acceptance. The components are
+    /// then combined left-to-right: each additional term is folded in via [`Product::ts_product`]
+    /// and the resulting pair colors are collapsed back down with a fresh even priority `2·i`
+    /// reserved for "term `i` just completed a loop", so that the least priority recurring
+    /// infinitely often is even iff *some* term's loop recurs infinitely often, i.e. iff the
+    /// word lies in the union of the terms' languages.
+    ///
+    /// # Example
+    /// ```
+    /// use automata::automaton::omega::OmegaRegex;
+    /// use automata::ts::regex::Regex;
+    /// use automata::ts::TSBuilder;
+    ///
+    /// let regex = OmegaRegex::term(Regex::word("a"), Regex::word("b"));
+    /// let dpa = regex.into_dpa();
+    ///
+    /// // `0` is the state before `a` was read, `1` is the `b`-loop and `2` is a sink that is
+    /// // entered on any other word, so this is exactly the language `a · b^ω`.
+    /// let expected = TSBuilder::without_state_colors()
+    ///     .with_transitions([
+    ///         (0, 'a', 1, 1), (0, 'b', 1, 2),
+    ///         (1, 'a', 1, 2), (1, 'b', 0, 1),
+    ///         (2, 'a', 1, 2), (2, 'b', 1, 2),
+    ///     ])
+    ///     .into_dpa(0);
+    /// assert!(dpa.language_equivalent(&expected));
+    /// ```
+    pub fn into_dpa(&self) -> DPA<CharAlphabet> {
+        let mut components = self.terms.iter().map(|(u, v)| compile_term(u, v));
+        let Some(first) = components.next() else {
+            return empty_dpa();
+        };
+
+        let mut num_terms = 1usize;
+        components.fold(first, |acc, next| {
+            let fallback = 2 * num_terms - 1;
+            let prod = acc.ts_product(next);
+            let initial = prod.initial();
+            let combined = prod
+                .map_edge_colors(|(ca, cb)| {
+                    if ca != fallback {
+                        ca
+                    } else if cb == 0 {
+                        2 * num_terms
+                    } else {
+                        2 * num_terms + 1
+                    }
+                })
+                .with_initial(initial)
+                .collect_dpa();
+            num_terms += 1;
+            combined
+        })
+    }
+}
+
+/// Compiles the single term `prefix · loop_body^ω` into a deterministic component of a
+/// [`DPA`], using priority `0` for transitions that just completed a `loop_body`-iteration and
+/// priority `1` for every other transition. See the [module documentation](self).
+fn compile_term(prefix: &Regex, loop_body: &Regex) -> DPA<CharAlphabet> {
+    let dfa_u = prefix.into_dfa();
+    let dfa_v = loop_body.into_dfa();
+
+    let alphabet: BTreeSet<char> = dfa_u
+        .alphabet()
+        .universe()
+        .chain(dfa_v.alphabet().universe())
+        .collect();
+
+    let u_states: Vec<_> = dfa_u.state_indices().collect();
+    let u_index: HashMap<_, usize> = u_states.iter().enumerate().map(|(i, q)| (*q, i)).collect();
+    let nu = u_states.len();
+
+    let v_states: Vec<_> = dfa_v.state_indices().collect();
+    let v_index: HashMap<_, usize> = v_states.iter().enumerate().map(|(i, q)| (*q, i)).collect();
+    let v_initial = dfa_v.initial();
+    let sink = nu + v_states.len();
+
+    let mut edges = Vec::new();
+    for &q in &u_states {
+        let source = u_index[&q];
+        if dfa_u.state_color(q).unwrap_or(false) {
+            // `prefix` has just been matched: enter the loop on `loop_body`.
+            for &a in &alphabet {
+                let target = step(&dfa_v, v_initial, a)
+                    .map(|p| nu + v_index[&p])
+                    .unwrap_or(sink);
+                edges.push((source, a, 1, target));
+            }
+        } else {
+            for &a in &alphabet {
+                let target = step(&dfa_u, q, a).map(|p| u_index[&p]).unwrap_or(sink);
+                edges.push((source, a, 1, target));
+            }
+        }
+    }
+    for &r in &v_states {
+        let source = nu + v_index[&r];
+        if dfa_v.state_color(r).unwrap_or(false) {
+            // One iteration of `loop_body` just completed: mark it with priority 0 and
+            // restart the loop.
+            for &a in &alphabet {
+                let target = step(&dfa_v, v_initial, a)
+                    .map(|p| nu + v_index[&p])
+                    .unwrap_or(sink);
+                edges.push((source, a, 0, target));
+            }
+        } else {
+            for &a in &alphabet {
+                let target = step(&dfa_v, r, a)
+                    .map(|p| nu + v_index[&p])
+                    .unwrap_or(sink);
+                edges.push((source, a, 1, target));
+            }
+        }
+    }
+    for &a in &alphabet {
+        edges.push((sink, a, 1, sink));
+    }
+
+    let initial = u_index[&dfa_u.initial()];
+    TSBuilder::without_state_colors()
+        .with_transitions(edges)
+        .into_dpa(initial)
+}
+
+/// Looks up the (unique, since `dfa` is deterministic) transition from `q` on `sym`.
+fn step(
+    dfa: &DFA<CharAlphabet>,
+    q: StateIndex<DFA<CharAlphabet>>,
+    sym: char,
+) -> Option<StateIndex<DFA<CharAlphabet>>> {
+    dfa.edges_from(q)?
+        .find(|e| *e.expression() == sym)
+        .map(|e| e.target())
+}
+
+/// The trivial single-state [`DPA`] rejecting every word, used as the compilation result of an
+/// [`OmegaRegex`] with no terms at all.
+fn empty_dpa() -> DPA<CharAlphabet> {
+    TSBuilder::without_state_colors()
+        .with_transitions([(0, '\u{0}', 1, 0)])
+        .into_dpa(0)
+}
+
+impl<D> IntoDPA<D>
+where
+    D: Deterministic<EdgeColor = Int>,
+{
+    /// Extracts an [`OmegaRegex`] describing the language accepted by `self`, by walking the
+    /// same SCC decomposition used by [`Self::witness_color`]: every non-transient SCC whose
+    /// interior contains an even (accepting) edge color contributes one `U · V^ω` term, where
+    /// `U` is the minimal word reaching the SCC (via [`Scc::minimal_representative`]) and `V`
+    /// is a maximal loop through it (via [`Scc::maximal_loop_from`]).
+    ///
+    /// [`Scc::minimal_representative`]: crate::ts::connected_components::Scc::minimal_representative
+    /// [`Scc::maximal_loop_from`]: crate::ts::connected_components::Scc::maximal_loop_from
+    ///
+    /// # Example
+    /// ```
+    /// use automata::ts::{Deterministic, TSBuilder};
+    ///
+    /// let dpa = TSBuilder::without_state_colors()
+    ///     .with_transitions([(0, 'a', 0, 0), (0, 'b', 1, 0)])
+    ///     .into_dpa(0);
+    /// let regex = dpa.to_omega_regex();
+    /// assert!(regex.into_dpa().language_equivalent(&dpa));
+    /// ```
+    pub fn to_omega_regex(&self) -> OmegaRegex
+    where
+        D: TransitionSystem<Alphabet = CharAlphabet>,
+    {
+        let sccs = self.sccs();
+        let mut terms = Vec::new();
+        for (_, scc) in sccs.iter() {
+            if scc.is_transient() {
+                continue;
+            }
+            if !scc.interior_edge_colors().iter().any(|c| c % 2 == 0) {
+                continue;
+            }
+            let rep = scc
+                .minimal_representative()
+                .as_ref()
+                .expect("accepting SCC must be reachable");
+            let cycle = scc
+                .maximal_loop_from(rep.state_index())
+                .expect("non-transient SCC must admit a loop");
+
+            let prefix: String = rep.collect_vec().into_iter().collect();
+            let loop_word: String = cycle.into_iter().collect();
+            terms.push((Regex::word(&prefix), Regex::word(&loop_word)));
+        }
+        OmegaRegex::new(terms)
+    }
+}
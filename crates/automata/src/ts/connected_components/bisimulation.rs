@@ -0,0 +1,173 @@
+//! Bisimulation-based state merging: quotients a transition system by its coarsest
+//! bisimulation, the partition in which two states are in the same block iff they have the
+//! same state color and, for every symbol, transition into the same block with the same edge
+//! color.
+
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+use std::hash::Hash;
+
+use crate::core::alphabet::{Alphabet, Expression};
+use crate::ts::{EdgeColor, IsEdge, StateColor, StateIndex, SymbolOf, TSBuilder};
+use crate::{Pointed, TransitionSystem, DTS};
+
+/// Quotients `ts` by its coarsest bisimulation and returns the resulting transition system
+/// together with a map from every original [`StateIndex`] to the index of the equivalence
+/// class (block) it was merged into.
+///
+/// Uses partition refinement with an explicit worklist of `(block, symbol)` splitters: the
+/// initial partition groups states purely by state color; for each splitter `(B, a)` popped
+/// off the worklist, every current block is split according to whether (and with what edge
+/// color) its states transition on `a` into `B` -- states that do not are grouped together
+/// regardless of where they actually go, since that disagreement is caught by some other
+/// splitter. Following Hopcroft's "process the smaller half" rule, whenever a block splits,
+/// every new sub-block except the largest is pushed back onto the worklist for every symbol,
+/// so that changes keep propagating until no splitter can refine anything further.
+pub fn bisimulation_minimize<Ts>(
+    ts: &Ts,
+) -> (
+    DTS<Ts::Alphabet, Ts::StateColor, Ts::EdgeColor>,
+    BTreeMap<StateIndex<Ts>, usize>,
+)
+where
+    Ts: TransitionSystem + Pointed,
+    StateIndex<Ts>: Ord + Hash,
+    StateColor<Ts>: Ord + Clone,
+    EdgeColor<Ts>: Ord + Clone,
+{
+    let states: Vec<StateIndex<Ts>> = ts.state_indices().collect();
+    let symbols: Vec<SymbolOf<Ts>> = ts.alphabet().universe().collect();
+
+    let mut blocks: Vec<BTreeSet<StateIndex<Ts>>> = {
+        let mut by_color: BTreeMap<StateColor<Ts>, BTreeSet<StateIndex<Ts>>> = BTreeMap::new();
+        for &q in &states {
+            by_color
+                .entry(ts.state_color(q).expect("state must exist"))
+                .or_default()
+                .insert(q);
+        }
+        by_color.into_values().collect()
+    };
+    let mut block_of: BTreeMap<StateIndex<Ts>, usize> = blocks
+        .iter()
+        .enumerate()
+        .flat_map(|(i, block)| block.iter().map(move |&q| (q, i)))
+        .collect();
+
+    let mut worklist: VecDeque<(usize, SymbolOf<Ts>)> = (0..blocks.len())
+        .flat_map(|i| symbols.iter().map(move |&a| (i, a)))
+        .collect();
+
+    while let Some((splitter, a)) = worklist.pop_front() {
+        let mut by_current_block: BTreeMap<usize, Vec<StateIndex<Ts>>> = BTreeMap::new();
+        for &q in &states {
+            by_current_block.entry(block_of[&q]).or_default().push(q);
+        }
+
+        for (block_idx, members) in by_current_block {
+            if members.len() < 2 {
+                continue;
+            }
+            let mut by_signature: BTreeMap<Option<EdgeColor<Ts>>, Vec<StateIndex<Ts>>> =
+                BTreeMap::new();
+            for q in members {
+                let signature = ts
+                    .edges_from(q)
+                    .into_iter()
+                    .flatten()
+                    .find(|e| {
+                        e.expression().symbols().any(|s| s == a)
+                            && block_of[&e.target()] == splitter
+                    })
+                    .map(|e| e.color().clone());
+                by_signature.entry(signature).or_default().push(q);
+            }
+            if by_signature.len() <= 1 {
+                continue;
+            }
+
+            let mut groups: Vec<Vec<StateIndex<Ts>>> = by_signature.into_values().collect();
+            groups.sort_by_key(|g| std::cmp::Reverse(g.len()));
+            let mut groups = groups.into_iter();
+            // The largest sub-block keeps the original index in place; only the (necessarily
+            // smaller) remainder needs to be rescheduled.
+            blocks[block_idx] = groups
+                .next()
+                .expect("at least one group")
+                .into_iter()
+                .collect();
+
+            for rest in groups {
+                let new_idx = blocks.len();
+                for &q in &rest {
+                    block_of.insert(q, new_idx);
+                }
+                blocks.push(rest.into_iter().collect());
+                worklist.extend(symbols.iter().map(|&b| (new_idx, b)));
+            }
+        }
+    }
+
+    let mut colors = Vec::with_capacity(blocks.len());
+    let mut transitions = Vec::new();
+    for (idx, block) in blocks.iter().enumerate() {
+        let representative = *block.iter().next().expect("blocks are never empty");
+        colors.push(ts.state_color(representative).expect("state must exist"));
+        if let Some(edges) = ts.edges_from(representative) {
+            for edge in edges {
+                for a in edge.expression().symbols() {
+                    transitions.push((idx, a, edge.color().clone(), block_of[&edge.target()]));
+                }
+            }
+        }
+    }
+
+    let quotient = TSBuilder::default()
+        .with_state_colors(colors)
+        .with_transitions(transitions)
+        .into_dts_with_initial(block_of[&ts.initial()]);
+
+    (quotient, block_of)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::bisimulation_minimize;
+    use crate::ts::TSBuilder;
+    use crate::Void;
+    use crate::{Pointed, TransitionSystem};
+
+    #[test]
+    fn merges_behaviorally_equivalent_states() {
+        // States 1 and 2 are bisimilar: both are non-accepting and loop back to themselves
+        // reading 'a' and 'b', so a coarsest bisimulation must merge them. State 0 is
+        // accepting, which keeps it apart from them.
+        let ts = TSBuilder::default()
+            .with_state_colors([true, false, false])
+            .with_transitions([
+                (0, 'a', Void, 1),
+                (0, 'b', Void, 2),
+                (1, 'a', Void, 1),
+                (1, 'b', Void, 1),
+                (2, 'a', Void, 2),
+                (2, 'b', Void, 2),
+            ])
+            .into_dts_with_initial(0);
+
+        let (quotient, class_of) = bisimulation_minimize(&ts);
+        assert_eq!(quotient.size(), 2);
+        assert_eq!(class_of[&1], class_of[&2]);
+        assert_ne!(class_of[&0], class_of[&1]);
+    }
+
+    #[test]
+    fn keeps_states_with_different_colors_apart() {
+        let ts = TSBuilder::default()
+            .with_state_colors([true, false])
+            .with_transitions([(0, 'a', Void, 0), (1, 'a', Void, 1)])
+            .into_dts_with_initial(0);
+
+        let (quotient, class_of) = bisimulation_minimize(&ts);
+        assert_eq!(quotient.size(), 2);
+        assert_ne!(class_of[&0], class_of[&1]);
+    }
+}
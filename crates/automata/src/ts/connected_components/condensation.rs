@@ -0,0 +1,194 @@
+//! The condensation of a transition system's SCC decomposition: an acyclic quotient graph
+//! whose nodes are the [`Scc`]s produced by [`TransitionSystem::sccs`] and whose edges mirror
+//! the border transitions between them.
+
+use std::collections::BTreeSet;
+use std::hash::Hash;
+
+use super::scc::Scc;
+use crate::TransitionSystem;
+use crate::ts::{EdgeColor, StateIndex};
+
+/// A first-class view of the condensation DAG of `ts`'s SCC decomposition. Built once from
+/// [`TransitionSystem::sccs`], it keeps a reachability bitset per SCC (computed in
+/// reverse-topological order, i.e. `reach(C) = {C} ∪ ⋃ reach(succ)` for every successor
+/// `succ` of `C`) so that [`Self::reaches`] and [`Self::transitive_reduction`] are both
+/// linear in the number of condensation edges rather than recomputing reachability on every
+/// call.
+pub struct Condensation<'a, Ts: TransitionSystem> {
+    sccs: Vec<Scc<'a, Ts>>,
+    successors: Vec<BTreeSet<usize>>,
+    reach: Vec<BTreeSet<usize>>,
+    reverse_topological: Vec<usize>,
+}
+
+impl<'a, Ts: TransitionSystem> Condensation<'a, Ts>
+where
+    EdgeColor<Ts>: Hash + Eq,
+    StateIndex<Ts>: Ord,
+{
+    /// Builds the condensation of `ts`: collapses every SCC of `ts` to one node and connects
+    /// `C` to `D` iff some border transition of `C` leads into `D`.
+    pub fn new(ts: &'a Ts) -> Self {
+        let sccs: Vec<Scc<'a, Ts>> = ts.sccs().iter().map(|(_, scc)| scc.clone()).collect();
+
+        let index_of_state: std::collections::BTreeMap<StateIndex<Ts>, usize> = sccs
+            .iter()
+            .enumerate()
+            .flat_map(|(i, scc)| scc.state_indices().map(move |q| (*q, i)))
+            .collect();
+
+        let successors: Vec<BTreeSet<usize>> = sccs
+            .iter()
+            .enumerate()
+            .map(|(i, scc)| {
+                scc.border_transitions()
+                    .iter()
+                    .map(|(_, _, _, target)| {
+                        *index_of_state
+                            .get(target)
+                            .expect("border transition must lead into some SCC")
+                    })
+                    .filter(|&j| j != i)
+                    .collect()
+            })
+            .collect();
+
+        let reverse_topological = postorder(&successors);
+
+        let mut reach: Vec<BTreeSet<usize>> = vec![BTreeSet::new(); sccs.len()];
+        for &i in &reverse_topological {
+            let mut r = BTreeSet::from([i]);
+            for &j in &successors[i] {
+                r.extend(reach[j].iter().copied());
+            }
+            reach[i] = r;
+        }
+
+        Self {
+            sccs,
+            successors,
+            reach,
+            reverse_topological,
+        }
+    }
+
+    /// Returns the number of SCCs in the condensation.
+    pub fn len(&self) -> usize {
+        self.sccs.len()
+    }
+
+    /// Returns `true` iff the condensation has no SCCs, i.e. `ts` has no states.
+    pub fn is_empty(&self) -> bool {
+        self.sccs.is_empty()
+    }
+
+    /// Returns the SCC at condensation index `i`.
+    pub fn scc(&self, i: usize) -> &Scc<'a, Ts> {
+        &self.sccs[i]
+    }
+
+    /// Iterates over condensation indices in reverse-topological order: every successor of an
+    /// SCC is yielded before the SCC itself.
+    pub fn reverse_topological(&self) -> impl Iterator<Item = usize> + '_ {
+        self.reverse_topological.iter().copied()
+    }
+
+    /// Returns `true` iff SCC `j` is reachable from SCC `i` in the condensation (an SCC always
+    /// reaches itself).
+    pub fn reaches(&self, i: usize, j: usize) -> bool {
+        self.reach[i].contains(&j)
+    }
+
+    /// Computes the transitive reduction of the condensation: the minimal edge set with the
+    /// same reachability relation. An edge `i -> j` is dropped whenever some other successor
+    /// `k` of `i` already reaches `j`, since `i -> k -> … -> j` is then a longer path that
+    /// makes `i -> j` redundant.
+    pub fn transitive_reduction(&self) -> Vec<(usize, usize)> {
+        let mut edges = Vec::new();
+        for (i, succs) in self.successors.iter().enumerate() {
+            for &j in succs {
+                let redundant = succs.iter().any(|&k| k != j && self.reach[k].contains(&j));
+                if !redundant {
+                    edges.push((i, j));
+                }
+            }
+        }
+        edges
+    }
+}
+
+/// Computes a postorder of the graph given by `successors` (a DAG: `successors[i]` are the
+/// direct successors of node `i`). In a DAG, every edge `i -> j` has `j` finish before `i`, so
+/// this list already has every node's successors appearing before the node itself -- exactly
+/// the reverse-topological order [`Condensation`] needs to fold reachability bottom-up.
+fn postorder(successors: &[BTreeSet<usize>]) -> Vec<usize> {
+    let n = successors.len();
+    let mut visited = vec![false; n];
+    let mut order = Vec::with_capacity(n);
+
+    for start in 0..n {
+        if visited[start] {
+            continue;
+        }
+        let mut stack = vec![(start, false)];
+        while let Some((node, finished)) = stack.pop() {
+            if finished {
+                order.push(node);
+                continue;
+            }
+            if visited[node] {
+                continue;
+            }
+            visited[node] = true;
+            stack.push((node, true));
+            for &succ in &successors[node] {
+                if !visited[succ] {
+                    stack.push((succ, false));
+                }
+            }
+        }
+    }
+
+    order
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ts::connected_components::Condensation;
+    use crate::{DTS, TransitionSystem};
+
+    #[test]
+    fn condensation_of_two_sccs_linked_by_a_bridge() {
+        // {0, 1} is a cycle, {2, 3} is a cycle, and a single bridge edge 1 -> 2 connects them.
+        let ts = DTS::builder()
+            .default_color(())
+            .with_transitions([
+                (0, 'a', (), 1),
+                (1, 'a', (), 0),
+                (1, 'b', (), 2),
+                (2, 'a', (), 3),
+                (3, 'a', (), 2),
+            ])
+            .into_dts_with_initial(0);
+
+        let condensation = Condensation::new(&ts);
+        assert_eq!(condensation.len(), 2);
+
+        let source_scc = condensation
+            .reverse_topological()
+            .find(|&i| condensation.scc(i).contains(&0))
+            .unwrap();
+        let target_scc = condensation
+            .reverse_topological()
+            .find(|&i| condensation.scc(i).contains(&2))
+            .unwrap();
+
+        assert!(condensation.reaches(source_scc, target_scc));
+        assert!(!condensation.reaches(target_scc, source_scc));
+        assert_eq!(
+            condensation.transitive_reduction(),
+            vec![(source_scc, target_scc)]
+        );
+    }
+}
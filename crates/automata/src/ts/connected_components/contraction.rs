@@ -0,0 +1,180 @@
+//! Transient-SCC contraction: a jump-threading-style simplification that splices out
+//! single-state, non-accepting pass-through states, analogous to collapsing a
+//! join-then-switch into a direct jump in a control-flow graph.
+
+use std::collections::BTreeMap;
+use std::hash::Hash;
+
+use super::Condensation;
+use crate::ts::{EdgeColor, EdgeExpression, IsEdge, StateColor, StateIndex, TSBuilder};
+use crate::{Pointed, TransitionSystem, DTS};
+
+/// Contracts every transient, single-state SCC of `ts` for which `is_distinguishing` returns
+/// `false` on its state color -- i.e. every state that is merely forwarded through, rather
+/// than one whose color carries meaning the caller wants to preserve. [`Pointed::initial`] is
+/// never contracted, even if it would otherwise qualify, since it must remain addressable.
+///
+/// For such a state `q`, every incoming border edge `(p, e, c, q)` is combined with every
+/// outgoing edge `(q, e', c', r)` via the caller-supplied `combine` closure into a single edge
+/// `(p, combine(e, c, e', c'), r)`, after which `q` is deleted. SCCs are visited in
+/// reverse-topological order of their [`Condensation`], so by the time a transient state is
+/// spliced out, all of its successors have already been contracted -- collapsing an entire
+/// chain of pass-through states in one sweep. The original `ts` is never mutated; a fresh
+/// transition system with identical observable behavior (modulo whatever `combine` encodes)
+/// is built instead, alongside a map from every surviving original [`StateIndex`] to its index
+/// in the result.
+pub fn contract_transient_states<Ts>(
+    ts: &Ts,
+    is_distinguishing: impl Fn(&Ts::StateColor) -> bool,
+    mut combine: impl FnMut(
+        &EdgeExpression<Ts>,
+        &Ts::EdgeColor,
+        &EdgeExpression<Ts>,
+        &Ts::EdgeColor,
+    ) -> (EdgeExpression<Ts>, Ts::EdgeColor),
+) -> (
+    DTS<Ts::Alphabet, Ts::StateColor, Ts::EdgeColor>,
+    BTreeMap<StateIndex<Ts>, usize>,
+)
+where
+    Ts: TransitionSystem + Pointed,
+    StateIndex<Ts>: Ord + Hash,
+    StateColor<Ts>: Clone,
+    EdgeColor<Ts>: Clone + Hash + Eq,
+    EdgeExpression<Ts>: Clone,
+{
+    type Edge<Ts> = (
+        EdgeExpression<Ts>,
+        <Ts as TransitionSystem>::EdgeColor,
+        StateIndex<Ts>,
+    );
+    type BackEdge<Ts> = (
+        StateIndex<Ts>,
+        EdgeExpression<Ts>,
+        <Ts as TransitionSystem>::EdgeColor,
+    );
+
+    let mut alive: std::collections::BTreeSet<StateIndex<Ts>> = ts.state_indices().collect();
+    let mut out_edges: BTreeMap<StateIndex<Ts>, Vec<Edge<Ts>>> = BTreeMap::new();
+    let mut in_edges: BTreeMap<StateIndex<Ts>, Vec<BackEdge<Ts>>> = BTreeMap::new();
+    for q in ts.state_indices() {
+        if let Some(edges) = ts.edges_from(q) {
+            for edge in edges {
+                let (e, c, r) = (
+                    edge.expression().clone(),
+                    edge.color().clone(),
+                    edge.target(),
+                );
+                out_edges
+                    .entry(q)
+                    .or_default()
+                    .push((e.clone(), c.clone(), r));
+                in_edges.entry(r).or_default().push((q, e, c));
+            }
+        }
+    }
+
+    let condensation = Condensation::new(ts);
+    for scc_idx in condensation.reverse_topological() {
+        let scc = condensation.scc(scc_idx);
+        if !scc.is_trivial() || scc.is_nontransient() {
+            continue;
+        }
+        let q = scc.first();
+        if q == ts.initial() || is_distinguishing(&ts.state_color(q).expect("state must exist")) {
+            continue;
+        }
+
+        let preds = in_edges.remove(&q).unwrap_or_default();
+        let succs = out_edges.remove(&q).unwrap_or_default();
+        for (p, _, _) in &preds {
+            if let Some(v) = out_edges.get_mut(p) {
+                v.retain(|(_, _, t)| *t != q);
+            }
+        }
+        for (_, _, r) in &succs {
+            if let Some(v) = in_edges.get_mut(r) {
+                v.retain(|(s, _, _)| *s != q);
+            }
+        }
+        for (p, e, c) in &preds {
+            for (e2, c2, r) in &succs {
+                let (expr, color) = combine(e, c, e2, c2);
+                out_edges
+                    .entry(*p)
+                    .or_default()
+                    .push((expr.clone(), color.clone(), *r));
+                in_edges.entry(*r).or_default().push((*p, expr, color));
+            }
+        }
+        alive.remove(&q);
+    }
+
+    let new_index: BTreeMap<StateIndex<Ts>, usize> =
+        alive.iter().enumerate().map(|(i, &q)| (q, i)).collect();
+
+    let mut colors = Vec::with_capacity(new_index.len());
+    let mut transitions = Vec::new();
+    for &q in &alive {
+        colors.push(ts.state_color(q).expect("state must exist"));
+        for (e, c, r) in out_edges.get(&q).into_iter().flatten() {
+            transitions.push((new_index[&q], e.clone(), c.clone(), new_index[r]));
+        }
+    }
+
+    let result = TSBuilder::default()
+        .with_state_colors(colors)
+        .with_transitions(transitions)
+        .into_dts_with_initial(new_index[&ts.initial()]);
+
+    (result, new_index)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::contract_transient_states;
+    use crate::ts::TSBuilder;
+    use crate::Void;
+    use crate::{Pointed, TransitionSystem};
+
+    #[test]
+    fn splices_out_a_chain_of_forwarding_states() {
+        // 1 and 2 are transient, single-state, non-accepting pass-throughs between 0 and the
+        // accepting sink 3; contraction should thread 0 directly to 3.
+        let ts = TSBuilder::default()
+            .with_state_colors([false, false, false, true])
+            .with_transitions([
+                (0, 'a', Void, 1),
+                (1, 'a', Void, 2),
+                (2, 'a', Void, 3),
+                (3, 'a', Void, 3),
+            ])
+            .into_dts_with_initial(0);
+
+        let (contracted, new_index) =
+            contract_transient_states(&ts, |color| *color, |_, _, e2, c2| (*e2, c2.clone()));
+
+        assert_eq!(contracted.size(), 2);
+        assert!(new_index.contains_key(&0));
+        assert!(new_index.contains_key(&3));
+        assert!(!new_index.contains_key(&1));
+        assert!(!new_index.contains_key(&2));
+        assert_eq!(
+            contracted.reached_state_index_from(new_index[&0], "a"),
+            Some(new_index[&3])
+        );
+    }
+
+    #[test]
+    fn never_contracts_the_initial_state() {
+        let ts = TSBuilder::default()
+            .with_state_colors([false, true])
+            .with_transitions([(0, 'a', Void, 1), (1, 'a', Void, 1)])
+            .into_dts_with_initial(0);
+
+        let (contracted, new_index) =
+            contract_transient_states(&ts, |_| false, |_, _, e2, c2| (*e2, c2.clone()));
+        assert_eq!(contracted.size(), 2);
+        assert!(new_index.contains_key(&0));
+    }
+}
@@ -0,0 +1,250 @@
+//! Dominator-tree analysis over the part of a [`TransitionSystem`] reachable from its initial
+//! state: state `d` dominates state `q` iff every run from the initial state to `q` passes
+//! through `d`.
+
+use std::collections::BTreeMap;
+
+use crate::ts::{IsEdge, StateIndex};
+use crate::{Pointed, TransitionSystem};
+
+/// The immediate-dominator tree of the reachable part of a [`TransitionSystem`], rooted at its
+/// initial state. Built once via [`Dominated::dominators`], it answers `idom`/`dominates`
+/// queries in constant time (or linear in the length of a dominator chain).
+pub struct DominatorTree<Idx> {
+    root: Idx,
+    idom: BTreeMap<Idx, Idx>,
+    predecessors: BTreeMap<Idx, Vec<Idx>>,
+}
+
+impl<Idx: Ord + Copy> DominatorTree<Idx> {
+    /// Returns the initial state that roots this dominator tree.
+    pub fn root(&self) -> Idx {
+        self.root
+    }
+
+    /// Returns the immediate dominator of `q`, i.e. the closest proper dominator of `q` on
+    /// every path from the root. Returns `None` if `q` is unreachable from the root. The root
+    /// is its own immediate dominator.
+    pub fn idom(&self, q: Idx) -> Option<Idx> {
+        self.idom.get(&q).copied()
+    }
+
+    /// Returns `true` iff `a` dominates `b`, i.e. every run from the root to `b` passes
+    /// through `a`. Every reachable state dominates itself; unreachable states dominate
+    /// nothing and are dominated by nothing.
+    pub fn dominates(&self, a: Idx, b: Idx) -> bool {
+        self.dominator_chain(b)
+            .is_some_and(|mut chain| chain.any(|q| q == a))
+    }
+
+    /// Returns the predecessors of `q` among the states reachable from the root, as seen by
+    /// the traversal that built this tree.
+    pub fn predecessors(&self, q: Idx) -> &[Idx] {
+        self.predecessors.get(&q).map_or(&[], Vec::as_slice)
+    }
+
+    /// Iterates over the dominator chain of `q`: `q` itself, then its immediate dominator, and
+    /// so on up to the root. Returns `None` if `q` is unreachable from the root.
+    pub fn dominator_chain(&self, q: Idx) -> Option<DominatorChain<'_, Idx>> {
+        if !self.idom.contains_key(&q) {
+            return None;
+        }
+        Some(DominatorChain {
+            tree: self,
+            next: Some(q),
+        })
+    }
+}
+
+/// Iterator over the dominator chain of a state, from itself up to the root of a
+/// [`DominatorTree`]. See [`DominatorTree::dominator_chain`].
+pub struct DominatorChain<'a, Idx> {
+    tree: &'a DominatorTree<Idx>,
+    next: Option<Idx>,
+}
+
+impl<Idx: Ord + Copy> Iterator for DominatorChain<'_, Idx> {
+    type Item = Idx;
+
+    fn next(&mut self) -> Option<Idx> {
+        let current = self.next?;
+        let parent = self.tree.idom[&current];
+        self.next = if parent == current {
+            None
+        } else {
+            Some(parent)
+        };
+        Some(current)
+    }
+}
+
+/// Adds a [`Self::dominators`] method computing the dominator tree of the part of `self`
+/// reachable from its initial state.
+pub trait Dominated: TransitionSystem + Pointed
+where
+    StateIndex<Self>: Ord,
+{
+    /// Computes the dominator tree of the states reachable from [`Pointed::initial`], using
+    /// the standard iterative data-flow algorithm: a reverse-postorder numbering is computed
+    /// from the root via DFS over [`TransitionSystem::edges_from`], the root's immediate
+    /// dominator is itself, and then every other state's immediate dominator is repeatedly
+    /// refined, in reverse-postorder, to the meet (by the "intersect" routine, which walks two
+    /// candidates up the partial idom tree by postorder number until they coincide) of its
+    /// already-processed predecessors, until nothing changes.
+    fn dominators(&self) -> DominatorTree<StateIndex<Self>> {
+        let root = self.initial();
+
+        let postorder = postorder_from(self, root);
+        let postorder_number: BTreeMap<StateIndex<Self>, usize> =
+            postorder.iter().enumerate().map(|(i, &q)| (q, i)).collect();
+
+        let mut predecessors: BTreeMap<StateIndex<Self>, Vec<StateIndex<Self>>> = BTreeMap::new();
+        for &q in &postorder {
+            if let Some(it) = self.edges_from(q) {
+                for edge in it {
+                    let target = edge.target();
+                    if postorder_number.contains_key(&target) {
+                        predecessors.entry(target).or_default().push(q);
+                    }
+                }
+            }
+        }
+
+        let reverse_postorder: Vec<StateIndex<Self>> = postorder.iter().rev().copied().collect();
+        let mut idom: BTreeMap<StateIndex<Self>, StateIndex<Self>> = BTreeMap::from([(root, root)]);
+
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for &q in reverse_postorder.iter().skip(1) {
+                let Some(preds) = predecessors.get(&q) else {
+                    continue;
+                };
+                let mut new_idom = None;
+                for &p in preds {
+                    if !idom.contains_key(&p) {
+                        continue;
+                    }
+                    new_idom = Some(match new_idom {
+                        None => p,
+                        Some(other) => intersect(&idom, &postorder_number, other, p),
+                    });
+                }
+                if let Some(new_idom) = new_idom {
+                    if idom.get(&q) != Some(&new_idom) {
+                        idom.insert(q, new_idom);
+                        changed = true;
+                    }
+                }
+            }
+        }
+
+        DominatorTree {
+            root,
+            idom,
+            predecessors,
+        }
+    }
+}
+
+impl<Ts> Dominated for Ts
+where
+    Ts: TransitionSystem + Pointed,
+    StateIndex<Ts>: Ord,
+{
+}
+
+/// Walks `a` and `b` up the partial idom tree `idom`, always advancing whichever finger sits on
+/// the state with the smaller postorder number, until both fingers agree -- their meeting point
+/// is the nearest common dominator seen so far.
+fn intersect<Idx: Ord + Copy>(
+    idom: &BTreeMap<Idx, Idx>,
+    postorder_number: &BTreeMap<Idx, usize>,
+    mut a: Idx,
+    mut b: Idx,
+) -> Idx {
+    while a != b {
+        while postorder_number[&a] < postorder_number[&b] {
+            a = idom[&a];
+        }
+        while postorder_number[&b] < postorder_number[&a] {
+            b = idom[&b];
+        }
+    }
+    a
+}
+
+/// Computes a postorder of the states reachable from `root` via [`TransitionSystem::edges_from`].
+fn postorder_from<Ts: TransitionSystem>(ts: &Ts, root: StateIndex<Ts>) -> Vec<StateIndex<Ts>>
+where
+    StateIndex<Ts>: Ord,
+{
+    let mut visited = std::collections::BTreeSet::from([root]);
+    let mut order = Vec::new();
+    let mut stack = vec![(root, false)];
+
+    while let Some((state, finished)) = stack.pop() {
+        if finished {
+            order.push(state);
+            continue;
+        }
+        stack.push((state, true));
+        if let Some(it) = ts.edges_from(state) {
+            for edge in it {
+                let target = edge.target();
+                if visited.insert(target) {
+                    stack.push((target, false));
+                }
+            }
+        }
+    }
+
+    order
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ts::connected_components::Dominated;
+    use crate::{Pointed, TransitionSystem, DTS};
+
+    #[test]
+    fn diamond_shaped_dominators() {
+        // 0 -> 1 -> 3, 0 -> 2 -> 3: both branches rejoin at 3, so 0 is the sole dominator
+        // of 1, 2 and 3 besides themselves.
+        let ts = DTS::builder()
+            .default_color(())
+            .with_transitions([
+                (0, 'a', (), 1),
+                (0, 'b', (), 2),
+                (1, 'a', (), 3),
+                (2, 'a', (), 3),
+            ])
+            .into_dts_with_initial(0);
+
+        let dom = ts.dominators();
+        assert_eq!(dom.root(), 0);
+        assert_eq!(dom.idom(0), Some(0));
+        assert_eq!(dom.idom(1), Some(0));
+        assert_eq!(dom.idom(2), Some(0));
+        assert_eq!(dom.idom(3), Some(0));
+        assert!(dom.dominates(0, 3));
+        assert!(!dom.dominates(1, 3));
+        assert!(!dom.dominates(2, 3));
+    }
+
+    #[test]
+    fn chain_of_unique_dominators() {
+        let ts = DTS::builder()
+            .default_color(())
+            .with_transitions([(0, 'a', (), 1), (1, 'a', (), 2), (2, 'a', (), 1)])
+            .into_dts_with_initial(0);
+
+        let dom = ts.dominators();
+        assert_eq!(
+            dom.dominator_chain(2).unwrap().collect::<Vec<_>>(),
+            vec![2, 1, 0]
+        );
+        assert!(dom.dominates(1, 2));
+        assert_eq!(dom.idom(ts.initial()), Some(0));
+    }
+}
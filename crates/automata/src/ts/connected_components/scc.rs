@@ -1,4 +1,9 @@
-use std::{cell::OnceCell, collections::BTreeSet, fmt::Debug, hash::Hash};
+use std::{
+    cell::OnceCell,
+    collections::{BTreeMap, BTreeSet, VecDeque},
+    fmt::Debug,
+    hash::Hash,
+};
 
 use crate::congruence::MinimalRepresentative;
 use crate::core::{
@@ -373,6 +378,151 @@ impl<'a, Ts: TransitionSystem> Scc<'a, Ts> {
         Some(word)
     }
 
+    /// Computes a minimum-length closed walk from `from` that traverses every interior
+    /// transition of the SCC at least once, via a directed Chinese-Postman construction.
+    ///
+    /// Unlike [`Self::maximal_loop_from`], which greedily consumes interior transitions and
+    /// patches any gaps with an arbitrary [`TransitionSystem::word_from_to`], this always
+    /// returns a walk of minimum possible length: every state's imbalance
+    /// `δ(v) = outdeg(v) − indeg(v)` (counted over interior transitions) is computed; a state
+    /// with `δ(v) < 0` has a deficit of outgoing edges and a state with `δ(v) > 0` has a
+    /// deficit of incoming ones, so the cheapest shortest paths from every deficit-out state to
+    /// deficit-in states are found by solving the resulting transportation problem, and copies
+    /// of those paths are duplicated onto the transition multigraph until every state is
+    /// balanced. The resulting multigraph is Eulerian (balanced, and strongly connected since
+    /// the SCC is), so its Euler circuit -- extracted from `from` via Hierholzer's algorithm --
+    /// is exactly the minimal maximal walk.
+    pub fn minimal_maximal_loop_from(&self, from: Ts::StateIndex) -> Option<Vec<SymbolOf<Ts>>>
+    where
+        EdgeColor<Ts>: Hash + Eq,
+    {
+        assert!(self.contains(&from));
+        let required: Vec<(Ts::StateIndex, SymbolOf<Ts>, Ts::StateIndex)> = self
+            .interior_transitions()
+            .iter()
+            .map(|(p, a, _, q)| (*p, *a, *q))
+            .collect();
+        if required.is_empty() {
+            return None;
+        }
+
+        let mut multigraph: BTreeMap<Ts::StateIndex, Vec<(SymbolOf<Ts>, Ts::StateIndex)>> =
+            BTreeMap::new();
+        let mut imbalance: BTreeMap<Ts::StateIndex, i64> = BTreeMap::new();
+        for (p, a, q) in &required {
+            multigraph.entry(*p).or_default().push((*a, *q));
+            *imbalance.entry(*p).or_insert(0) += 1;
+            *imbalance.entry(*q).or_insert(0) -= 1;
+        }
+
+        let mut sources = Vec::new();
+        let mut sinks = Vec::new();
+        for (&state, &delta) in &imbalance {
+            if delta < 0 {
+                sources.extend(std::iter::repeat(state).take((-delta) as usize));
+            } else if delta > 0 {
+                sinks.extend(std::iter::repeat(state).take(delta as usize));
+            }
+        }
+        assert_eq!(
+            sources.len(),
+            sinks.len(),
+            "total excess must equal total deficit across any SCC"
+        );
+
+        if !sources.is_empty() {
+            let shortest = self.interior_shortest_paths();
+            let cost: Vec<Vec<i64>> = sources
+                .iter()
+                .map(|s| {
+                    sinks
+                        .iter()
+                        .map(|t| {
+                            shortest
+                                .get(&(*s, *t))
+                                .expect("SCC is strongly connected")
+                                .0 as i64
+                        })
+                        .collect()
+                })
+                .collect();
+            for (i, j) in min_cost_assignment(&cost) {
+                for &(p, a, q) in &shortest[&(sources[i], sinks[j])].1 {
+                    multigraph.entry(p).or_default().push((a, q));
+                }
+            }
+        }
+
+        // Hierholzer's algorithm: push `from`, repeatedly follow and delete an unused
+        // outgoing edge, and on a dead end pop the vertex onto the circuit. The circuit is
+        // then built in reverse, so reversing it back yields the Euler tour.
+        let mut stack = vec![(None, from)];
+        let mut circuit: Vec<(Option<SymbolOf<Ts>>, Ts::StateIndex)> = Vec::new();
+        while let Some(&(_, v)) = stack.last() {
+            match multigraph.get_mut(&v).and_then(Vec::pop) {
+                Some((a, w)) => stack.push((Some(a), w)),
+                None => circuit.push(stack.pop().expect("stack is non-empty")),
+            }
+        }
+        circuit.reverse();
+
+        Some(circuit.into_iter().filter_map(|(a, _)| a).collect())
+    }
+
+    /// Computes the shortest (by number of edges) interior path between every ordered pair of
+    /// distinct states of the SCC, via a BFS from each state restricted to
+    /// [`Self::interior_transitions`]. Since the SCC is strongly connected, every pair is
+    /// reachable.
+    fn interior_shortest_paths(
+        &self,
+    ) -> BTreeMap<
+        (Ts::StateIndex, Ts::StateIndex),
+        (usize, Vec<(Ts::StateIndex, SymbolOf<Ts>, Ts::StateIndex)>),
+    >
+    where
+        EdgeColor<Ts>: Hash + Eq,
+    {
+        let mut adjacency: BTreeMap<Ts::StateIndex, Vec<(SymbolOf<Ts>, Ts::StateIndex)>> =
+            BTreeMap::new();
+        for (p, a, _, q) in self.interior_transitions() {
+            adjacency.entry(*p).or_default().push((*a, *q));
+        }
+
+        let mut paths = BTreeMap::new();
+        for &source in &self.states {
+            let mut predecessor: BTreeMap<Ts::StateIndex, (Ts::StateIndex, SymbolOf<Ts>)> =
+                BTreeMap::new();
+            let mut visited = BTreeSet::from([source]);
+            let mut queue = VecDeque::from([source]);
+            while let Some(v) = queue.pop_front() {
+                for &(a, w) in adjacency.get(&v).into_iter().flatten() {
+                    if visited.insert(w) {
+                        predecessor.insert(w, (v, a));
+                        queue.push_back(w);
+                    }
+                }
+            }
+
+            for &target in &self.states {
+                if target == source {
+                    continue;
+                }
+                let mut edges = Vec::new();
+                let mut current = target;
+                while current != source {
+                    let &(prev, a) = predecessor
+                        .get(&current)
+                        .expect("SCC is strongly connected, so target must be reachable");
+                    edges.push((prev, a, current));
+                    current = prev;
+                }
+                edges.reverse();
+                paths.insert((source, target), (edges.len(), edges));
+            }
+        }
+        paths
+    }
+
     /// Returns an iterator over the state indices making up the scc.
     pub fn state_indices(&self) -> std::collections::btree_set::Iter<'_, Ts::StateIndex> {
         self.states.iter()
@@ -424,9 +574,78 @@ impl<Ts: TransitionSystem> Debug for Scc<'_, Ts> {
     }
 }
 
+/// Solves the assignment problem on a square `cost` matrix: finds a bijection `i ↦ assignment[i]`
+/// minimizing `Σ cost[i][assignment[i]]`, returned as a list of `(row, column)` pairs. Implements
+/// the Kuhn-Munkres ("Hungarian") algorithm in its `O(n^3)` shortest-augmenting-path form with
+/// row/column potentials.
+fn min_cost_assignment(cost: &[Vec<i64>]) -> Vec<(usize, usize)> {
+    let n = cost.len();
+    const INF: i64 = i64::MAX / 2;
+
+    // 1-indexed, following the classical formulation: `potential_row`/`potential_col` are the
+    // dual potentials, `match_of_col[j]` is the row matched to column `j` (0 = unmatched), and
+    // `parent_of_col[j]` records the column from which `j` was reached while searching for an
+    // augmenting path, so the matching along that path can be flipped once one is found.
+    let mut potential_row = vec![0i64; n + 1];
+    let mut potential_col = vec![0i64; n + 1];
+    let mut match_of_col = vec![0usize; n + 1];
+    let mut parent_of_col = vec![0usize; n + 1];
+
+    for row in 1..=n {
+        match_of_col[0] = row;
+        let mut current_col = 0;
+        let mut min_slack = vec![INF; n + 1];
+        let mut visited_col = vec![false; n + 1];
+        loop {
+            visited_col[current_col] = true;
+            let matched_row = match_of_col[current_col];
+            let mut delta = INF;
+            let mut next_col = 0;
+            for col in 1..=n {
+                if visited_col[col] {
+                    continue;
+                }
+                let slack = cost[matched_row - 1][col - 1]
+                    - potential_row[matched_row]
+                    - potential_col[col];
+                if slack < min_slack[col] {
+                    min_slack[col] = slack;
+                    parent_of_col[col] = current_col;
+                }
+                if min_slack[col] < delta {
+                    delta = min_slack[col];
+                    next_col = col;
+                }
+            }
+            for col in 0..=n {
+                if visited_col[col] {
+                    potential_row[match_of_col[col]] += delta;
+                    potential_col[col] -= delta;
+                } else {
+                    min_slack[col] -= delta;
+                }
+            }
+            current_col = next_col;
+            if match_of_col[current_col] == 0 {
+                break;
+            }
+        }
+        // Flip the matching along the augmenting path just found.
+        while current_col != 0 {
+            let parent = parent_of_col[current_col];
+            match_of_col[current_col] = match_of_col[parent];
+            current_col = parent;
+        }
+    }
+
+    (1..=n)
+        .map(|col| (match_of_col[col] - 1, col - 1))
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::{DTS, TransitionSystem};
+    use crate::{TransitionSystem, DTS};
     use automata_core::math::Set;
 
     #[test]
@@ -457,4 +676,87 @@ mod tests {
             &Set::from_iter([(1, 'a', 2, 1)])
         );
     }
+
+    /// Replays `word` from `from` and returns the sequence of `(source, symbol, target)`
+    /// interior transitions it traverses, panicking if it ever leaves the SCC.
+    fn replay<Ts: TransitionSystem>(
+        scc: &super::Scc<'_, Ts>,
+        from: Ts::StateIndex,
+        word: &[crate::ts::SymbolOf<Ts>],
+    ) -> Vec<(Ts::StateIndex, crate::ts::SymbolOf<Ts>, Ts::StateIndex)>
+    where
+        Ts::StateIndex: Copy + PartialEq + std::fmt::Debug,
+        crate::ts::SymbolOf<Ts>: Copy + PartialEq,
+    {
+        use crate::core::alphabet::Expression;
+        use crate::ts::IsEdge;
+
+        let mut current = from;
+        let mut steps = Vec::new();
+        for &a in word {
+            let edge = scc
+                .ts()
+                .edges_from(current)
+                .expect("state must exist")
+                .find(|e| e.expression().symbols().any(|s| s == a))
+                .expect("word must stay on transitions that exist");
+            let next = edge.target();
+            assert!(scc.contains(&next), "walk must stay inside the SCC");
+            steps.push((current, a, next));
+            current = next;
+        }
+        assert_eq!(current, from, "walk must return to its start");
+        steps
+    }
+
+    #[test]
+    fn minimal_maximal_loop_already_eulerian() {
+        let ts = DTS::builder()
+            .default_color(())
+            .with_transitions([(0, 'a', (), 1), (1, 'a', (), 2), (2, 'a', (), 0)])
+            .into_dts_with_initial(0);
+        let scc = ts.sccs().first().clone();
+
+        let word = scc.minimal_maximal_loop_from(0).unwrap();
+        assert_eq!(word.len(), 3);
+        let steps = replay(&scc, 0, &word);
+        assert_eq!(
+            Set::from_iter(steps),
+            Set::from_iter([(0, 'a', 1), (1, 'a', 2), (2, 'a', 0)])
+        );
+    }
+
+    #[test]
+    fn minimal_maximal_loop_requires_duplicated_edge() {
+        // A triangle 0 -a-> 1 -a-> 2 -a-> 0 plus a shortcut 0 -b-> 2 leaves 0 with a deficit of
+        // incoming edges and 2 with a deficit of outgoing ones, so the cheapest fix duplicates
+        // the single shortest path between them: the edge 2 -a-> 0.
+        let ts = DTS::builder()
+            .default_color(())
+            .with_transitions([
+                (0, 'a', (), 1),
+                (1, 'a', (), 2),
+                (2, 'a', (), 0),
+                (0, 'b', (), 2),
+            ])
+            .into_dts_with_initial(0);
+        let scc = ts.sccs().first().clone();
+
+        let word = scc.minimal_maximal_loop_from(0).unwrap();
+        assert_eq!(word.len(), 5);
+        let steps = replay(&scc, 0, &word);
+
+        let mut required: Vec<(usize, char, usize)> =
+            vec![(0, 'a', 1), (1, 'a', 2), (2, 'a', 0), (0, 'b', 2)];
+        let mut remaining = steps.clone();
+        for edge in &required {
+            let pos = remaining.iter().position(|s| s == edge).unwrap();
+            remaining.remove(pos);
+        }
+        // The one left-over step is the duplicated edge, and it must be the shortest
+        // connection between the unbalanced states, i.e. 2 -a-> 0.
+        assert_eq!(remaining, vec![(2, 'a', 0)]);
+        required.push((2, 'a', 0));
+        assert_eq!(steps.len(), required.len());
+    }
 }
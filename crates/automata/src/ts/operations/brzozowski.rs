@@ -0,0 +1,169 @@
+//! Brzozowski's double-reversal minimization, built on top of [`Reversed`].
+//!
+//! The algorithm is folklore but pleasantly short given the pieces this crate already
+//! has: determinizing the reverse of an automaton whose initial states are its
+//! accepting states, twice, yields the minimal DFA for the original language. Each
+//! determinization is an ordinary subset construction restricted to the states
+//! reachable from the chosen start set.
+
+use std::collections::{BTreeSet, VecDeque};
+use std::hash::Hash;
+
+use crate::core::Void;
+use crate::representation::CollectTs;
+use crate::ts::operations::Reversed;
+use crate::ts::predecessors::PredecessorIterable;
+use crate::ts::{IsEdge, Sproutable, StateIndex, SymbolOf, TSBuilder};
+use crate::{DFA, Pointed, TransitionSystem};
+
+/// Performs a single subset-construction pass over `ts`, starting from the given set
+/// of `start` states, and accepting a constructed state iff its underlying set
+/// intersects `accepting`. Only the states reachable from `start` are materialized.
+///
+/// Because the input need not be complete, a symbol with no successor from any state
+/// in the current set simply produces no successor in the output (an implicit sink is
+/// never created explicitly: subset-construction already treats the empty set as a
+/// dead end by never enqueuing it).
+fn subset_construct<Ts>(
+    ts: &Ts,
+    start: BTreeSet<StateIndex<Ts>>,
+    accepting: &BTreeSet<StateIndex<Ts>>,
+) -> DFA<Ts::Alphabet>
+where
+    Ts: TransitionSystem,
+    StateIndex<Ts>: Ord + Hash,
+{
+    let alphabet = ts.alphabet().clone();
+    let symbols: Vec<SymbolOf<Ts>> = alphabet.universe().collect();
+
+    let mut builder = TSBuilder::without_edge_colors();
+    let mut seen: Vec<BTreeSet<StateIndex<Ts>>> = Vec::new();
+    let mut queue = VecDeque::new();
+
+    let initial_idx = register(&mut seen, start.clone());
+    queue.push_back((initial_idx, start));
+    let mut colors = Vec::new();
+    let mut transitions = Vec::new();
+
+    while let Some((idx, set)) = queue.pop_front() {
+        let is_accepting = set.iter().any(|q| accepting.contains(q));
+        colors.push((idx, is_accepting));
+
+        for sym in &symbols {
+            let mut moved = BTreeSet::new();
+            for q in &set {
+                if let Some(edges) = ts.edges_from(*q) {
+                    for edge in edges {
+                        if edge.expression().symbols().any(|s| &s == sym) {
+                            moved.insert(edge.target());
+                        }
+                    }
+                }
+            }
+            if moved.is_empty() {
+                continue;
+            }
+            let target_idx = match seen.iter().position(|s| s == &moved) {
+                Some(pos) => pos,
+                None => {
+                    let pos = register(&mut seen, moved.clone());
+                    queue.push_back((pos, moved));
+                    pos
+                }
+            };
+            transitions.push((idx, *sym, target_idx));
+        }
+    }
+
+    builder = builder
+        .with_state_colors(colors.into_iter().map(|(_, c)| c))
+        .with_transitions(transitions);
+    builder.into_dfa(initial_idx)
+}
+
+fn register<T: Ord>(seen: &mut Vec<BTreeSet<T>>, set: BTreeSet<T>) -> usize {
+    seen.push(set);
+    seen.len() - 1
+}
+
+/// Brzozowski minimization: determinizes the reverse of `ts` (starting from its
+/// accepting states), then reverses and determinizes the result again. The output is
+/// the canonical minimal DFA recognizing the same language as `ts`.
+///
+/// # Example
+/// ```
+/// use automata::ts::{TSBuilder, operations::brzozowski_minimize};
+///
+/// let dfa = TSBuilder::default()
+///     .with_state_colors([false, true, true, false])
+///     .with_transitions([
+///         (0, 'a', automata::Void, 1),
+///         (1, 'a', automata::Void, 2),
+///         (2, 'a', automata::Void, 2),
+///         (0, 'b', automata::Void, 3),
+///         (1, 'b', automata::Void, 3),
+///         (2, 'b', automata::Void, 3),
+///         (3, 'a', automata::Void, 3),
+///         (3, 'b', automata::Void, 3),
+///     ])
+///     .into_dfa(0);
+/// let minimized = brzozowski_minimize(&dfa);
+/// assert!(minimized.size() <= dfa.size());
+/// assert!(minimized.accepts("a"));
+/// assert!(!minimized.accepts("aaab"));
+/// ```
+pub fn brzozowski_minimize<Ts>(ts: &Ts) -> DFA<Ts::Alphabet>
+where
+    Ts: TransitionSystem<StateColor = bool> + Pointed + PredecessorIterable,
+    StateIndex<Ts>: Ord + Hash,
+{
+    let accepting: BTreeSet<_> = ts
+        .state_indices()
+        .filter(|q| ts.state_color(*q) == Some(true))
+        .collect();
+
+    let reversed = Reversed(ts);
+    let first_pass = subset_construct(&reversed, accepting, &BTreeSet::from([ts.initial()]));
+
+    let accepting_first: BTreeSet<_> = first_pass
+        .state_indices()
+        .filter(|q| first_pass.state_color(*q) == Some(true))
+        .collect();
+    let initial_first = BTreeSet::from([first_pass.initial()]);
+    let reversed_again = Reversed(&first_pass);
+    subset_construct(&reversed_again, accepting_first, &initial_first)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::brzozowski_minimize;
+    use crate::Void;
+    use crate::ts::TSBuilder;
+    use crate::{Pointed, TransitionSystem};
+
+    #[test]
+    fn minimizes_redundant_states() {
+        // Two states (1 and 2) are language-equivalent once reached; Brzozowski
+        // minimization should collapse them.
+        let dfa = TSBuilder::default()
+            .with_state_colors([false, true, true, false])
+            .with_transitions([
+                (0, 'a', Void, 1),
+                (1, 'a', Void, 2),
+                (2, 'a', Void, 2),
+                (0, 'b', Void, 3),
+                (1, 'b', Void, 3),
+                (2, 'b', Void, 3),
+                (3, 'a', Void, 3),
+                (3, 'b', Void, 3),
+            ])
+            .into_dfa(0);
+
+        let minimized = brzozowski_minimize(&dfa);
+        assert!(minimized.accepts("a"));
+        assert!(minimized.accepts("aaaaa"));
+        assert!(!minimized.accepts(""));
+        assert!(!minimized.accepts("ab"));
+        assert!(minimized.size() < dfa.size());
+    }
+}
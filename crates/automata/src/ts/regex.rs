@@ -0,0 +1,377 @@
+//! Compiles regular expressions over a [`CharAlphabet`] into deterministic finite automata.
+//!
+//! The compilation pipeline follows the textbook Thompson construction: a regular
+//! expression is first turned into an ε-NFA (represented as a
+//! [`LinkedListNondeterministic`] whose ε-edges carry the reserved symbol
+//! [`EPSILON`]), and the ε-NFA is then subset-constructed into a [`DFA`] via
+//! [`into_deterministic`](super::NTS::into_deterministic)-style ε-closure expansion.
+
+use std::collections::{BTreeSet, VecDeque};
+
+use crate::core::{Void, alphabet::CharAlphabet};
+use crate::representation::CollectTs;
+use crate::ts::{Sproutable, TransitionSystem};
+use crate::{DFA, Pointed};
+
+use super::impls::linked::LinkedListTransitionSystem;
+use super::{EdgeColor, ForAlphabet, LinkedListNondeterministic, StateIndex};
+
+/// A symbol on an ε-NFA edge: either a concrete alphabet symbol or the empty word.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum RegexSymbol {
+    /// An ordinary alphabet symbol.
+    Char(char),
+    /// The empty transition, taken without consuming input.
+    Epsilon,
+}
+
+/// A syntax tree for a (finite-word) regular expression over `char`.
+///
+/// This is intentionally minimal: it supports the operators needed to express any
+/// regular language (literal, concatenation, union, Kleene star) plus the two
+/// degenerate constants `empty`/`epsilon`.
+#[derive(Debug, Clone)]
+pub enum Regex {
+    /// Matches no word at all.
+    Empty,
+    /// Matches only the empty word.
+    Epsilon,
+    /// Matches exactly the single-symbol word `a`.
+    Literal(char),
+    /// Matches any word accepted by `left` immediately followed by one accepted by `right`.
+    Concat(Box<Regex>, Box<Regex>),
+    /// Matches any word accepted by `left` or by `right`.
+    Union(Box<Regex>, Box<Regex>),
+    /// Matches any (possibly empty) repetition of words accepted by `inner`.
+    Star(Box<Regex>),
+}
+
+impl Regex {
+    /// Builds the expression matching exactly the given `word`.
+    pub fn word(word: &str) -> Self {
+        word.chars()
+            .map(Regex::Literal)
+            .reduce(|acc, lit| acc.then(lit))
+            .unwrap_or(Regex::Epsilon)
+    }
+
+    /// Concatenates `self` with `other`.
+    pub fn then(self, other: Regex) -> Self {
+        Regex::Concat(Box::new(self), Box::new(other))
+    }
+
+    /// Builds the union of `self` and `other`.
+    pub fn or(self, other: Regex) -> Self {
+        Regex::Union(Box::new(self), Box::new(other))
+    }
+
+    /// Builds the Kleene star of `self`.
+    pub fn star(self) -> Self {
+        Regex::Star(Box::new(self))
+    }
+
+    /// Compiles `self` into a minimal-ish [`DFA`] over a [`CharAlphabet`] containing
+    /// (at least) every symbol mentioned in the expression.
+    ///
+    /// # Example
+    /// ```
+    /// use automata::ts::regex::Regex;
+    ///
+    /// let dfa = Regex::word("ab").or(Regex::word("ba")).into_dfa();
+    /// assert!(dfa.accepts("ab"));
+    /// assert!(dfa.accepts("ba"));
+    /// assert!(!dfa.accepts("aa"));
+    /// assert!(!dfa.accepts(""));
+    /// ```
+    pub fn into_dfa(&self) -> DFA<CharAlphabet> {
+        let alphabet = CharAlphabet::from_iter(self.symbols());
+        let (nfa, start, accept) = self.compile(alphabet);
+        subset_construct(nfa, start, accept)
+    }
+
+    /// Parses a regular expression from a small surface syntax supporting `.` as literal
+    /// separator is not needed here; instead this accepts a string built purely out of
+    /// `char` literals combined with `|` (union), `*` (postfix star) and parentheses, with
+    /// concatenation being implicit (adjacency). This is a convenience entry point for
+    /// [`DFA::from_regex`].
+    pub fn parse(src: &str) -> Result<Regex, String> {
+        let tokens: Vec<char> = src.chars().collect();
+        let mut pos = 0;
+        let expr = parse_union(&tokens, &mut pos)?;
+        if pos != tokens.len() {
+            return Err(format!("unexpected trailing input at position {pos}"));
+        }
+        Ok(expr)
+    }
+
+    fn symbols(&self) -> BTreeSet<char> {
+        match self {
+            Regex::Empty | Regex::Epsilon => BTreeSet::new(),
+            Regex::Literal(c) => BTreeSet::from([*c]),
+            Regex::Concat(l, r) | Regex::Union(l, r) => {
+                l.symbols().into_iter().chain(r.symbols()).collect()
+            }
+            Regex::Star(inner) => inner.symbols(),
+        }
+    }
+
+    /// Thompson construction: compiles `self` into a fragment of an ε-NFA sharing
+    /// the given `alphabet`, returning the transition system together with its
+    /// designated start and accepting states.
+    fn compile(
+        &self,
+        alphabet: CharAlphabet,
+    ) -> (
+        LinkedListNondeterministic<CharAlphabet, Void, Void>,
+        StateIndex,
+        StateIndex,
+    ) {
+        let mut nfa = LinkedListNondeterministic::for_alphabet(alphabet);
+        let (start, accept) = self.thompson(&mut nfa);
+        (nfa, start, accept)
+    }
+
+    fn thompson(
+        &self,
+        nfa: &mut LinkedListNondeterministic<CharAlphabet, Void, Void>,
+    ) -> (StateIndex, StateIndex) {
+        match self {
+            Regex::Empty => {
+                // A fragment with no path from start to accept at all.
+                let start = nfa.add_state(Void);
+                let accept = nfa.add_state(Void);
+                (start, accept)
+            }
+            Regex::Epsilon => {
+                let start = nfa.add_state(Void);
+                let accept = nfa.add_state(Void);
+                add_epsilon(nfa, start, accept);
+                (start, accept)
+            }
+            Regex::Literal(c) => {
+                let start = nfa.add_state(Void);
+                let accept = nfa.add_state(Void);
+                nfa.add_edge((start, *c, Void, accept));
+                (start, accept)
+            }
+            Regex::Concat(left, right) => {
+                let (lstart, laccept) = left.thompson(nfa);
+                let (rstart, raccept) = right.thompson(nfa);
+                add_epsilon(nfa, laccept, rstart);
+                (lstart, raccept)
+            }
+            Regex::Union(left, right) => {
+                let (lstart, laccept) = left.thompson(nfa);
+                let (rstart, raccept) = right.thompson(nfa);
+                let start = nfa.add_state(Void);
+                let accept = nfa.add_state(Void);
+                add_epsilon(nfa, start, lstart);
+                add_epsilon(nfa, start, rstart);
+                add_epsilon(nfa, laccept, accept);
+                add_epsilon(nfa, raccept, accept);
+                (start, accept)
+            }
+            Regex::Star(inner) => {
+                let (istart, iaccept) = inner.thompson(nfa);
+                let start = nfa.add_state(Void);
+                let accept = nfa.add_state(Void);
+                add_epsilon(nfa, start, istart);
+                add_epsilon(nfa, start, accept);
+                add_epsilon(nfa, iaccept, istart);
+                add_epsilon(nfa, iaccept, accept);
+                (start, accept)
+            }
+        }
+    }
+}
+
+/// The byte used to mark an ε-edge, as [`LinkedListNondeterministic`] edges are indexed
+/// by `char`. We reserve `'\0'` since it cannot appear as a genuine alphabet symbol coming
+/// from [`Regex::parse`] or [`Regex::word`].
+const EPSILON: char = '\0';
+
+fn add_epsilon(
+    nfa: &mut LinkedListNondeterministic<CharAlphabet, Void, Void>,
+    from: StateIndex,
+    to: StateIndex,
+) {
+    nfa.add_edge((from, EPSILON, Void, to));
+}
+
+/// Computes the ε-closure of a set of NFA states.
+fn epsilon_closure(
+    nfa: &LinkedListNondeterministic<CharAlphabet, Void, Void>,
+    states: impl IntoIterator<Item = StateIndex>,
+) -> BTreeSet<StateIndex> {
+    let mut closure: BTreeSet<StateIndex> = states.into_iter().collect();
+    let mut queue: VecDeque<StateIndex> = closure.iter().copied().collect();
+    while let Some(q) = queue.pop_front() {
+        if let Some(edges) = nfa.edges_from(q) {
+            for edge in edges {
+                if *edge.expression() == EPSILON && closure.insert(edge.target()) {
+                    queue.push_back(edge.target());
+                }
+            }
+        }
+    }
+    closure
+}
+
+/// Performs subset construction over the ε-NFA fragment, producing a [`DFA`] whose
+/// states are ε-closed sets of NFA states, accepting iff the set contains `accept`.
+fn subset_construct(
+    nfa: LinkedListNondeterministic<CharAlphabet, Void, Void>,
+    start: StateIndex,
+    accept: StateIndex,
+) -> DFA<CharAlphabet> {
+    let alphabet = nfa.alphabet().clone();
+    let symbols: Vec<char> = alphabet
+        .universe()
+        .filter(|c| *c != EPSILON)
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect();
+
+    let mut dts = LinkedListTransitionSystem::for_alphabet(alphabet);
+
+    let initial_set = epsilon_closure(&nfa, [start]);
+    let mut seen: Vec<BTreeSet<StateIndex>> = vec![initial_set.clone()];
+    let initial_index = dts.add_state(initial_set.contains(&accept));
+    let mut queue = VecDeque::from([(initial_index, initial_set)]);
+
+    while let Some((dstate, set)) = queue.pop_front() {
+        for sym in &symbols {
+            let mut moved = BTreeSet::new();
+            for q in &set {
+                if let Some(edges) = nfa.edges_from(*q) {
+                    for edge in edges {
+                        if edge.expression() == sym {
+                            moved.insert(edge.target());
+                        }
+                    }
+                }
+            }
+            if moved.is_empty() {
+                continue;
+            }
+            let closed = epsilon_closure(&nfa, moved);
+            let target_index = match seen.iter().position(|s| s == &closed) {
+                Some(idx) => EdgeColor::from(idx),
+                None => {
+                    let color = closed.contains(&accept);
+                    let idx = dts.add_state(color);
+                    seen.push(closed.clone());
+                    queue.push_back((idx, closed));
+                    idx
+                }
+            };
+            dts.add_edge((dstate, *sym, Void, target_index));
+        }
+    }
+
+    dts.with_initial(initial_index).collect_dfa()
+}
+
+fn parse_union(tokens: &[char], pos: &mut usize) -> Result<Regex, String> {
+    let mut expr = parse_concat(tokens, pos)?;
+    while *pos < tokens.len() && tokens[*pos] == '|' {
+        *pos += 1;
+        let rhs = parse_concat(tokens, pos)?;
+        expr = expr.or(rhs);
+    }
+    Ok(expr)
+}
+
+fn parse_concat(tokens: &[char], pos: &mut usize) -> Result<Regex, String> {
+    let mut expr: Option<Regex> = None;
+    while *pos < tokens.len() && tokens[*pos] != '|' && tokens[*pos] != ')' {
+        let atom = parse_star(tokens, pos)?;
+        expr = Some(match expr {
+            Some(e) => e.then(atom),
+            None => atom,
+        });
+    }
+    expr.ok_or_else(|| "expected an expression".to_string())
+}
+
+fn parse_star(tokens: &[char], pos: &mut usize) -> Result<Regex, String> {
+    let mut atom = parse_atom(tokens, pos)?;
+    while *pos < tokens.len() && tokens[*pos] == '*' {
+        *pos += 1;
+        atom = atom.star();
+    }
+    Ok(atom)
+}
+
+fn parse_atom(tokens: &[char], pos: &mut usize) -> Result<Regex, String> {
+    match tokens.get(*pos) {
+        Some('(') => {
+            *pos += 1;
+            let inner = parse_union(tokens, pos)?;
+            if tokens.get(*pos) != Some(&')') {
+                return Err(format!("expected closing ')' at position {pos}"));
+            }
+            *pos += 1;
+            Ok(inner)
+        }
+        Some(&c) => {
+            *pos += 1;
+            Ok(Regex::Literal(c))
+        }
+        None => Err("unexpected end of input".to_string()),
+    }
+}
+
+impl DFA<CharAlphabet> {
+    /// Parses `expr` as a regular expression (see [`Regex::parse`]) and compiles it
+    /// directly into a [`DFA`].
+    ///
+    /// # Example
+    /// ```
+    /// use automata::DFA;
+    /// use automata::core::alphabet::CharAlphabet;
+    ///
+    /// let dfa = DFA::<CharAlphabet>::from_regex("a(b|c)*").unwrap();
+    /// assert!(dfa.accepts("abcbc"));
+    /// assert!(!dfa.accepts("b"));
+    /// ```
+    pub fn from_regex(expr: &str) -> Result<DFA<CharAlphabet>, String> {
+        Ok(Regex::parse(expr)?.into_dfa())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Regex;
+
+    #[test]
+    fn literal_and_concat() {
+        let dfa = Regex::word("ab").into_dfa();
+        assert!(dfa.accepts("ab"));
+        assert!(!dfa.accepts("a"));
+        assert!(!dfa.accepts("abc"));
+    }
+
+    #[test]
+    fn union_and_star() {
+        let dfa = Regex::parse("a(b|c)*").unwrap().into_dfa();
+        assert!(dfa.accepts("a"));
+        assert!(dfa.accepts("abcbc"));
+        assert!(!dfa.accepts(""));
+        assert!(!dfa.accepts("b"));
+    }
+
+    #[test]
+    fn empty_language() {
+        let dfa = Regex::Empty.into_dfa();
+        assert!(!dfa.accepts(""));
+        assert!(!dfa.accepts("a"));
+    }
+
+    #[test]
+    fn epsilon_language() {
+        let dfa = Regex::Epsilon.into_dfa();
+        assert!(dfa.accepts(""));
+        assert!(!dfa.accepts("a"));
+    }
+}
@@ -1,7 +1,10 @@
+use std::collections::BTreeSet;
+use std::hash::Hash;
+
 use crate::{Pointed, TransitionSystem};
 use automata_core::alphabet::Matcher;
 
-use super::{EdgeExpression, EdgeTuple, StateColor, StateIndex};
+use super::{EdgeColor, EdgeExpression, EdgeTuple, StateColor, StateIndex};
 
 /// Encapsulates the ability to remove states, edges, and transitions from a transition system.
 pub trait Shrinkable: TransitionSystem {
@@ -215,6 +218,111 @@ pub trait Shrinkable: TransitionSystem {
     {
         self.trim_from(self.initial())
     }
+
+    /// Removes every state that does not lie in a nontrivial strongly connected component,
+    /// i.e. one with at least one interior transition (computed via Tarjan's algorithm, see
+    /// [`TransitionSystem::sccs`]). A state that is merely reachable but lies on no cycle can
+    /// never be part of an infinite run, so for omega-automata it is as useless as an
+    /// unreachable one. Returns the set of all removed state indices with their associated
+    /// color.
+    ///
+    /// # Example
+    /// ```
+    /// use automata::{
+    ///     core::alphabet::CharAlphabet,
+    ///     ts::{ForAlphabet, Shrinkable, Sproutable},
+    ///     DTS,
+    /// };
+    ///
+    /// let mut ts = DTS::for_alphabet(CharAlphabet::of_size(1));
+    /// let q0 = ts.add_state(true);
+    /// let q1 = ts.add_state(false);
+    /// let q2 = ts.add_state(false);
+    ///
+    /// ts.add_edge((q0, 'a', q1));
+    /// ts.add_edge((q1, 'a', q0));
+    /// ts.add_edge((q0, 'a', q2));
+    ///
+    /// assert_eq!(ts.trim_to_scc(), vec![(q2, false)]);
+    /// ```
+    fn trim_to_scc(&mut self) -> Vec<(StateIndex<Self>, StateColor<Self>)>
+    where
+        EdgeColor<Self>: Hash + Eq,
+    {
+        let cyclic: BTreeSet<_> = self
+            .sccs()
+            .iter()
+            .filter(|(_, scc)| scc.is_nontransient())
+            .flat_map(|(_, scc)| scc.state_indices().copied())
+            .collect();
+
+        let mut out = Vec::new();
+        for q in self.state_indices_vec() {
+            if !cyclic.contains(&q) {
+                let c = self.remove_state(q).expect("We know this exists");
+                out.push((q, c));
+            }
+        }
+        out
+    }
+
+    /// First removes every state unreachable from the initial state ([`Self::trim`]), then
+    /// removes every remaining state from which no nontrivial strongly connected component is
+    /// reachable, i.e. that is not co-reachable to any cycle. Combined with [`Self::trim`],
+    /// this keeps only the states that can both be reached from the initial state and can
+    /// still reach some cycle, the cheap structural precondition for an omega-automaton to
+    /// accept anything. Returns the set of all removed state indices with their associated
+    /// color, from both passes.
+    ///
+    /// # Example
+    /// ```
+    /// use automata::{
+    ///     core::alphabet::CharAlphabet,
+    ///     ts::{ForAlphabet, Shrinkable, Sproutable},
+    ///     DTS,
+    /// };
+    ///
+    /// let mut ts = DTS::for_alphabet(CharAlphabet::of_size(1));
+    /// let q0 = ts.add_state(true);
+    /// let q1 = ts.add_state(false);
+    /// let q2 = ts.add_state(false);
+    /// let q3 = ts.add_state(false);
+    ///
+    /// ts.add_edge((q0, 'a', q1));
+    /// ts.add_edge((q1, 'a', q0));
+    /// ts.add_edge((q0, 'a', q2));
+    /// ts.add_edge((q2, 'a', q3));
+    ///
+    /// let mut removed = ts.double_trim();
+    /// removed.sort();
+    /// assert_eq!(removed, vec![(q2, false), (q3, false)]);
+    /// ```
+    fn double_trim(&mut self) -> Vec<(StateIndex<Self>, StateColor<Self>)>
+    where
+        Self: Pointed,
+        EdgeColor<Self>: Hash + Eq,
+    {
+        let mut removed = self.trim();
+
+        let cyclic: BTreeSet<_> = self
+            .sccs()
+            .iter()
+            .filter(|(_, scc)| scc.is_nontransient())
+            .flat_map(|(_, scc)| scc.state_indices().copied())
+            .collect();
+
+        for q in self.state_indices_vec() {
+            if !self
+                .reachable_state_indices_from(q)
+                .any(|p| cyclic.contains(&p))
+            {
+                let c = self.remove_state(q).expect("We know this exists");
+                removed.push((q, c));
+            }
+        }
+
+        removed
+    }
 }
 
 #[cfg(test)]
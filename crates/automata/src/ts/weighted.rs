@@ -0,0 +1,261 @@
+//! Treats edge colors as weights drawn from a [`Semiring`] and evaluates weighted runs.
+//!
+//! This complements the plain `EdgeColor`/`StateColor` machinery: instead of merely
+//! tagging transitions, a [`Semiring`]-valued `EdgeColor` lets us aggregate path weights,
+//! either over a single finite word ([`WeightedRun::weight_of`]) or over *all* paths from
+//! the initial state via [`WeightedRun::shortest_distance`].
+
+use std::collections::VecDeque;
+use std::hash::Hash;
+
+use crate::core::alphabet::Expression;
+use crate::core::{math::Map, word::FiniteWord};
+use crate::ts::{EdgeColor, IsEdge, StateIndex, SymbolOf};
+use crate::{Pointed, TransitionSystem};
+
+/// A semiring `(S, ⊕, ⊗, 0, 1)`: an algebraic structure with an additive identity
+/// `zero`, a multiplicative identity `one`, and operations `add`/`mul` that need not
+/// be invertible. Weighted automata use semirings to aggregate path weights: `mul`
+/// combines weights along a path, `add` combines weights of alternative paths.
+pub trait Semiring: Clone {
+    /// The additive identity; annihilating for `mul`.
+    fn zero() -> Self;
+    /// The multiplicative identity.
+    fn one() -> Self;
+    /// Combines two weights that label alternative (parallel) paths.
+    fn add(&self, other: &Self) -> Self;
+    /// Combines two weights that label a single (sequential) path.
+    fn mul(&self, other: &Self) -> Self;
+}
+
+/// The Boolean semiring `({false, true}, ||, &&, false, true)`. Used for plain
+/// reachability: a word is "accepted" iff some path's weight is `true`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Boolean(pub bool);
+
+impl Semiring for Boolean {
+    fn zero() -> Self {
+        Boolean(false)
+    }
+    fn one() -> Self {
+        Boolean(true)
+    }
+    fn add(&self, other: &Self) -> Self {
+        Boolean(self.0 || other.0)
+    }
+    fn mul(&self, other: &Self) -> Self {
+        Boolean(self.0 && other.0)
+    }
+}
+
+/// The tropical (min-plus) semiring over `f64`, with `+∞` as the additive identity.
+/// Used to compute shortest-path weights: `add` takes the minimum, `mul` adds.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Tropical(pub f64);
+
+impl Semiring for Tropical {
+    fn zero() -> Self {
+        Tropical(f64::INFINITY)
+    }
+    fn one() -> Self {
+        Tropical(0.0)
+    }
+    fn add(&self, other: &Self) -> Self {
+        Tropical(self.0.min(other.0))
+    }
+    fn mul(&self, other: &Self) -> Self {
+        Tropical(self.0 + other.0)
+    }
+}
+
+/// The Viterbi semiring over `[0, 1]`, used for maximum-probability paths:
+/// `add` takes the maximum, `mul` multiplies probabilities.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Viterbi(pub f64);
+
+impl Semiring for Viterbi {
+    fn zero() -> Self {
+        Viterbi(0.0)
+    }
+    fn one() -> Self {
+        Viterbi(1.0)
+    }
+    fn add(&self, other: &Self) -> Self {
+        Viterbi(self.0.max(other.0))
+    }
+    fn mul(&self, other: &Self) -> Self {
+        Viterbi(self.0 * other.0)
+    }
+}
+
+/// The probability semiring over `f64` (ordinary `+`/`*`), used to compute the total
+/// acceptance mass over all accepting paths.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Probability(pub f64);
+
+impl Semiring for Probability {
+    fn zero() -> Self {
+        Probability(0.0)
+    }
+    fn one() -> Self {
+        Probability(1.0)
+    }
+    fn add(&self, other: &Self) -> Self {
+        Probability(self.0 + other.0)
+    }
+    fn mul(&self, other: &Self) -> Self {
+        Probability(self.0 * other.0)
+    }
+}
+
+/// Provides weighted-run evaluation for any [`TransitionSystem`] whose [`EdgeColor`]
+/// is a [`Semiring`].
+pub trait WeightedRun: TransitionSystem
+where
+    EdgeColor<Self>: Semiring,
+{
+    /// Computes the semiring sum, over all paths from `from` that spell out `word`,
+    /// of the product of the edge weights along the path. Returns [`Semiring::zero`]
+    /// if no such path exists.
+    fn weight_of<W: FiniteWord<Symbol = SymbolOf<Self>>>(
+        &self,
+        from: StateIndex<Self>,
+        word: W,
+    ) -> EdgeColor<Self> {
+        let mut frontier: Map<StateIndex<Self>, EdgeColor<Self>> = Map::default();
+        frontier.insert(from, EdgeColor::<Self>::one());
+
+        for sym in word.symbols() {
+            let mut next: Map<StateIndex<Self>, EdgeColor<Self>> = Map::default();
+            for (state, weight) in &frontier {
+                let Some(edges) = self.edges_from(*state) else {
+                    continue;
+                };
+                for edge in edges {
+                    if edge.expression().symbols().any(|s| s == sym) {
+                        let contributed = weight.mul(edge.color());
+                        next.entry(edge.target())
+                            .and_modify(|w| *w = w.add(&contributed))
+                            .or_insert(contributed);
+                    }
+                }
+            }
+            frontier = next;
+            if frontier.is_empty() {
+                return EdgeColor::<Self>::zero();
+            }
+        }
+
+        frontier
+            .values()
+            .fold(EdgeColor::<Self>::zero(), |acc, w| acc.add(w))
+    }
+
+    /// Computes, for every state reachable from `from`, the semiring sum of the
+    /// weights of all paths from `from` to that state. This is a generalized
+    /// Bellman-Ford fixpoint: `d[target] = add(d[target], mul(r[source], w))`,
+    /// relaxed until no distance changes, where `r[source]` is the weight added to
+    /// `d[source]` since `source` was last relaxed, so no path is counted twice. Terminates for `k`-closed semirings
+    /// (e.g. [`Boolean`], or [`Tropical`]/[`Viterbi`] on acyclic/DAG-reducible
+    /// fragments); callers working with unbounded semirings should bound the
+    /// number of relaxation rounds themselves.
+    fn shortest_distance(&self, from: StateIndex<Self>) -> Map<StateIndex<Self>, EdgeColor<Self>>
+    where
+        StateIndex<Self>: Hash + Eq + Ord,
+        EdgeColor<Self>: PartialEq,
+    {
+        let mut dist: Map<StateIndex<Self>, EdgeColor<Self>> = Map::default();
+        dist.insert(from, EdgeColor::<Self>::one());
+        let mut residual: Map<StateIndex<Self>, EdgeColor<Self>> = Map::default();
+        residual.insert(from, EdgeColor::<Self>::one());
+
+        let mut queue: VecDeque<StateIndex<Self>> = VecDeque::from([from]);
+        let mut in_queue = std::collections::BTreeSet::from([from]);
+
+        while let Some(source) = queue.pop_front() {
+            in_queue.remove(&source);
+            let Some(r_source) = residual.remove(&source) else {
+                continue;
+            };
+            let Some(edges) = self.edges_from(source) else {
+                continue;
+            };
+            for edge in edges {
+                let target = edge.target();
+                let candidate = r_source.mul(edge.color());
+                let updated = match dist.get(&target) {
+                    Some(existing) => {
+                        let merged = existing.add(&candidate);
+                        if &merged == existing { None } else { Some(merged) }
+                    }
+                    None => Some(candidate.clone()),
+                };
+                if let Some(new_dist) = updated {
+                    dist.insert(target, new_dist);
+                    residual
+                        .entry(target)
+                        .and_modify(|r| *r = r.add(&candidate))
+                        .or_insert(candidate);
+                    if in_queue.insert(target) {
+                        queue.push_back(target);
+                    }
+                }
+            }
+        }
+
+        dist
+    }
+}
+
+impl<T: TransitionSystem> WeightedRun for T where EdgeColor<T>: Semiring {}
+
+/// Extension point so `self.initial()` can be used directly with [`WeightedRun`].
+pub trait WeightedRunFromInitial: WeightedRun + Pointed
+where
+    EdgeColor<Self>: Semiring,
+{
+    /// Like [`WeightedRun::shortest_distance`] but starting from [`Pointed::initial`].
+    fn shortest_distance_from_initial(&self) -> Map<StateIndex<Self>, EdgeColor<Self>>
+    where
+        StateIndex<Self>: Hash + Eq + Ord,
+        EdgeColor<Self>: PartialEq,
+    {
+        self.shortest_distance(self.initial())
+    }
+}
+
+impl<T: WeightedRun + Pointed> WeightedRunFromInitial for T where EdgeColor<T>: Semiring {}
+
+#[cfg(test)]
+mod tests {
+    use super::{Boolean, Semiring, Tropical, WeightedRun};
+    use crate::ts::{Sproutable, TSBuilder};
+
+    #[test]
+    fn boolean_weight_is_reachability() {
+        let ts = TSBuilder::default()
+            .with_state_colors([(), (), ()])
+            .with_transitions([
+                (0, 'a', Boolean(true), 1),
+                (1, 'a', Boolean(true), 2),
+                (0, 'b', Boolean(false), 2),
+            ])
+            .into_linked_list_deterministic();
+        assert_eq!(ts.weight_of(0, "aa"), Boolean(true));
+        assert_eq!(ts.weight_of(0, "b"), Boolean(false));
+    }
+
+    #[test]
+    fn tropical_shortest_path() {
+        let ts = TSBuilder::default()
+            .with_state_colors([(), (), ()])
+            .with_transitions([
+                (0, 'a', Tropical(1.0), 1),
+                (1, 'a', Tropical(1.0), 2),
+                (0, 'a', Tropical(5.0), 2),
+            ])
+            .into_linked_list_nondeterministic();
+        let distances = ts.shortest_distance(0);
+        assert_eq!(distances.get(&2), Some(&Tropical(2.0)));
+    }
+}
@@ -0,0 +1,309 @@
+//! A small, structured builder for the DOT graph description language.
+//!
+//! Rendering used to be a matter of concatenating strings by hand. This module gives
+//! callers a typed [`DotGraph`] they can populate with nodes, edges and clusters,
+//! which is then serialized with [`DotGraph::to_dot_string`]. [`GraphvizSource`] is
+//! produced from this builder rather than ad-hoc formatting.
+
+use std::collections::BTreeMap;
+
+/// Whether a [`DotGraph`] is rendered as a directed (`digraph`, edges using `->`) or
+/// undirected (`graph`, edges using `--`) graph.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GraphKind {
+    /// A directed graph, serialized with the `digraph` keyword and `->` edges.
+    Digraph,
+    /// An undirected graph, serialized with the `graph` keyword and `--` edges.
+    Graph,
+}
+
+impl GraphKind {
+    fn keyword(self) -> &'static str {
+        match self {
+            GraphKind::Digraph => "digraph",
+            GraphKind::Graph => "graph",
+        }
+    }
+
+    fn edge_operator(self) -> &'static str {
+        match self {
+            GraphKind::Digraph => "->",
+            GraphKind::Graph => "--",
+        }
+    }
+}
+
+/// A map of DOT attributes (e.g. `label`, `shape`, `color`, `style`), rendered sorted by
+/// key as `[key=value, ...]`.
+#[derive(Debug, Clone, Default)]
+pub struct Attributes(BTreeMap<String, String>);
+
+impl Attributes {
+    /// Creates an empty attribute map.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the `label` attribute.
+    pub fn label(mut self, label: impl Into<String>) -> Self {
+        self.0.insert("label".to_string(), quote(&label.into()));
+        self
+    }
+
+    /// Sets the `shape` attribute.
+    pub fn shape(mut self, shape: impl Into<String>) -> Self {
+        self.0.insert("shape".to_string(), shape.into());
+        self
+    }
+
+    /// Sets the `color` attribute.
+    pub fn color(mut self, color: impl Into<String>) -> Self {
+        self.0.insert("color".to_string(), color.into());
+        self
+    }
+
+    /// Sets the `style` attribute.
+    pub fn style(mut self, style: impl Into<String>) -> Self {
+        self.0.insert("style".to_string(), style.into());
+        self
+    }
+
+    /// Sets an arbitrary attribute by name.
+    pub fn set(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.0.insert(key.into(), value.into());
+        self
+    }
+
+    fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    fn to_dot_string(&self) -> String {
+        if self.0.is_empty() {
+            return String::new();
+        }
+        let body = self
+            .0
+            .iter()
+            .map(|(k, v)| format!("{k}={v}"))
+            .collect::<Vec<_>>()
+            .join(", ");
+        format!(" [{body}]")
+    }
+}
+
+fn quote(s: &str) -> String {
+    format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// A single node, identified by its `id`, with an optional attribute set.
+#[derive(Debug, Clone)]
+pub struct DotNode {
+    id: String,
+    attributes: Attributes,
+}
+
+/// A single edge between two node ids, with an optional attribute set.
+#[derive(Debug, Clone)]
+pub struct DotEdge {
+    source: String,
+    target: String,
+    attributes: Attributes,
+}
+
+/// A named subgraph that is rendered as a `cluster_<name>` block, used by graphviz to
+/// draw a bounding box around a group of nodes.
+#[derive(Debug, Clone)]
+pub struct DotCluster {
+    name: String,
+    label: Option<String>,
+    nodes: Vec<DotNode>,
+}
+
+impl DotCluster {
+    /// Creates a new, empty cluster with the given `name`.
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            label: None,
+            nodes: Vec::new(),
+        }
+    }
+
+    /// Sets the human-readable label shown on the cluster's bounding box.
+    pub fn with_label(mut self, label: impl Into<String>) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+
+    /// Adds a node to the cluster.
+    pub fn with_node(mut self, node: DotNode) -> Self {
+        self.nodes.push(node);
+        self
+    }
+
+    fn to_dot_string(&self, indent: &str) -> String {
+        let mut out = format!("{indent}subgraph cluster_{} {{\n", self.name);
+        if let Some(label) = &self.label {
+            out.push_str(&format!("{indent}  label={};\n", quote(label)));
+        }
+        for node in &self.nodes {
+            out.push_str(&format!(
+                "{indent}  {}{};\n",
+                quote(&node.id),
+                node.attributes.to_dot_string()
+            ));
+        }
+        out.push_str(&format!("{indent}}}\n"));
+        out
+    }
+}
+
+/// A structured, backend-independent DOT graph builder.
+///
+/// # Example
+/// ```
+/// use graphviz_render::dot::{DotGraph, DotNode, DotEdge, Attributes, GraphKind};
+///
+/// let graph = DotGraph::new(GraphKind::Digraph, "example")
+///     .with_node(DotNode::new("0", Attributes::new().label("q0").shape("circle")))
+///     .with_node(DotNode::new("1", Attributes::new().label("q1").shape("doublecircle")))
+///     .with_edge(DotEdge::new("0", "1", Attributes::new().label("a")));
+///
+/// let dot = graph.to_dot_string();
+/// assert!(dot.starts_with("digraph example {"));
+/// assert!(dot.contains("\"0\" -> \"1\""));
+/// ```
+#[derive(Debug, Clone)]
+pub struct DotGraph {
+    kind: GraphKind,
+    name: String,
+    nodes: Vec<DotNode>,
+    edges: Vec<DotEdge>,
+    clusters: Vec<DotCluster>,
+}
+
+impl DotNode {
+    /// Creates a new node with the given `id` and attribute set.
+    pub fn new(id: impl Into<String>, attributes: Attributes) -> Self {
+        Self {
+            id: id.into(),
+            attributes,
+        }
+    }
+}
+
+impl DotEdge {
+    /// Creates a new edge between `source` and `target` with the given attribute set.
+    pub fn new(source: impl Into<String>, target: impl Into<String>, attributes: Attributes) -> Self {
+        Self {
+            source: source.into(),
+            target: target.into(),
+            attributes,
+        }
+    }
+}
+
+impl DotGraph {
+    /// Creates an empty graph of the given `kind` and `name`.
+    pub fn new(kind: GraphKind, name: impl Into<String>) -> Self {
+        Self {
+            kind,
+            name: name.into(),
+            nodes: Vec::new(),
+            edges: Vec::new(),
+            clusters: Vec::new(),
+        }
+    }
+
+    /// Adds a node.
+    pub fn with_node(mut self, node: DotNode) -> Self {
+        self.nodes.push(node);
+        self
+    }
+
+    /// Adds an edge. If `self` is a [`GraphKind::Graph`], the edge is rendered
+    /// without direction (`--`) regardless of `source`/`target` order.
+    pub fn with_edge(mut self, edge: DotEdge) -> Self {
+        self.edges.push(edge);
+        self
+    }
+
+    /// Adds a cluster subgraph.
+    pub fn with_cluster(mut self, cluster: DotCluster) -> Self {
+        self.clusters.push(cluster);
+        self
+    }
+
+    /// Serializes `self` into a DOT source string.
+    pub fn to_dot_string(&self) -> String {
+        let mut out = format!("{} {} {{\n", self.kind.keyword(), self.name);
+        for node in &self.nodes {
+            out.push_str(&format!(
+                "  {}{};\n",
+                quote(&node.id),
+                node.attributes.to_dot_string()
+            ));
+        }
+        for cluster in &self.clusters {
+            out.push_str(&cluster.to_dot_string("  "));
+        }
+        for edge in &self.edges {
+            let attrs = edge.attributes.to_dot_string();
+            out.push_str(&format!(
+                "  {} {} {}{};\n",
+                quote(&edge.source),
+                self.kind.edge_operator(),
+                quote(&edge.target),
+                attrs
+            ));
+        }
+        out.push_str("}\n");
+        out
+    }
+}
+
+impl crate::GraphvizSource {
+    /// Builds a [`GraphvizSource`] from a structured [`DotGraph`].
+    pub fn from_dot_graph(graph: &DotGraph) -> Self {
+        crate::GraphvizSource(graph.to_dot_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn directed_vs_undirected_operator() {
+        let digraph = DotGraph::new(GraphKind::Digraph, "g")
+            .with_node(DotNode::new("a", Attributes::new()))
+            .with_node(DotNode::new("b", Attributes::new()))
+            .with_edge(DotEdge::new("a", "b", Attributes::new()));
+        assert!(digraph.to_dot_string().contains("\"a\" -> \"b\""));
+
+        let graph = DotGraph::new(GraphKind::Graph, "g")
+            .with_node(DotNode::new("a", Attributes::new()))
+            .with_node(DotNode::new("b", Attributes::new()))
+            .with_edge(DotEdge::new("a", "b", Attributes::new()));
+        assert!(graph.to_dot_string().contains("\"a\" -- \"b\""));
+    }
+
+    #[test]
+    fn attributes_rendered_sorted() {
+        let attrs = Attributes::new().color("red").label("x");
+        assert_eq!(attrs.to_dot_string(), " [color=red, label=\"x\"]");
+    }
+
+    #[test]
+    fn cluster_renders_nested_block() {
+        let graph = DotGraph::new(GraphKind::Digraph, "g").with_cluster(
+            DotCluster::new("0")
+                .with_label("group")
+                .with_node(DotNode::new("a", Attributes::new())),
+        );
+        let dot = graph.to_dot_string();
+        assert!(dot.contains("subgraph cluster_0"));
+        assert!(dot.contains("label=\"group\""));
+    }
+}
@@ -1,7 +1,18 @@
 use thiserror::Error;
 
+pub mod dot;
+
 pub struct GraphvizSource(String);
 
+impl GraphvizSource {
+    /// Wraps a raw, already-formatted DOT source string. Prefer
+    /// [`GraphvizSource::from_dot_graph`] when the graph is being constructed
+    /// programmatically, since it quotes and escapes node ids and labels.
+    pub fn new(dot: impl Into<String>) -> Self {
+        Self(dot.into())
+    }
+}
+
 pub struct Svg(String);
 
 pub struct PngImage(Vec<u8>);
@@ -0,0 +1,173 @@
+//! A chumsky parser stage on top of [`lexer::tokenizer`](crate::lexer::tokenizer) that
+//! folds an HOA acceptance formula (`Inf(0) & (Fin(1) | Inf(2))` and friends) into an
+//! [`AcceptanceCondition`].
+//!
+//! [`AcceptanceCondition`] is, by design, a *positive* boolean expression over
+//! [`AcceptanceAtom`]s: it has no `Not` node, since negation of a whole subformula can
+//! always be pushed down to the leaves instead (`Fin`/`Inf` swap, `And`/`Or` swap,
+//! constants flip). This parser does exactly that while folding `!`, rather than adding a
+//! node the rest of the crate (`simplify`, `recognize`, the `Display` impl) would have to
+//! special-case. The `!` *inside* an atom, as in `Fin(!0)`, is different: it negates which
+//! runs of set `0` are being counted, not the formula, so it is kept as
+//! [`AcceptanceAtom::Negative`].
+
+use chumsky::prelude::*;
+
+use crate::lexer::Token;
+use crate::{build_error_report, AcceptanceAtom, AcceptanceCondition, FromHoaError, HoaBool, Id};
+
+/// Pushes a top-level negation down to the leaves of `cond`, staying inside the
+/// positive-boolean fragment. `Fin`/`Inf` are already mutual negations of the same atom,
+/// so negating one just swaps the wrapper; `And`/`Or` swap by De Morgan.
+fn negate(cond: AcceptanceCondition) -> AcceptanceCondition {
+    match cond {
+        AcceptanceCondition::Fin(atom) => AcceptanceCondition::Inf(atom),
+        AcceptanceCondition::Inf(atom) => AcceptanceCondition::Fin(atom),
+        AcceptanceCondition::And(left, right) => {
+            AcceptanceCondition::Or(Box::new(negate(*left)), Box::new(negate(*right)))
+        }
+        AcceptanceCondition::Or(left, right) => {
+            AcceptanceCondition::And(Box::new(negate(*left)), Box::new(negate(*right)))
+        }
+        AcceptanceCondition::Boolean(HoaBool(value)) => {
+            AcceptanceCondition::Boolean(HoaBool(!value))
+        }
+    }
+}
+
+/// Parses the tokenized form of an HOA acceptance formula into an [`AcceptanceCondition`],
+/// handling `Fin`/`Inf` leaves (with an optional negated atom, e.g. `Fin(!0)`), `t`/`f`
+/// constants, parenthesization, and `!`/`&`/`|` with the usual precedence
+/// (`!` > `&` > `|`).
+pub fn acceptance_condition_parser(
+) -> impl Parser<Token, AcceptanceCondition, Error = Simple<Token>> + Clone {
+    recursive(|expr| {
+        let id = select! { Token::Int(value) => value }
+            .map(|value: String| value.parse::<Id>().unwrap_or_default());
+
+        let atom = id
+            .clone()
+            .map(AcceptanceAtom::Positive)
+            .or(just(Token::Op('!'))
+                .ignore_then(id)
+                .map(AcceptanceAtom::Negative));
+
+        let fin = just(Token::Fin)
+            .ignore_then(
+                atom.clone()
+                    .delimited_by(just(Token::Paren('(')), just(Token::Paren(')'))),
+            )
+            .map(AcceptanceCondition::Fin);
+        let inf = just(Token::Inf)
+            .ignore_then(atom.delimited_by(just(Token::Paren('(')), just(Token::Paren(')'))))
+            .map(AcceptanceCondition::Inf);
+
+        let constant = select! {
+            Token::Identifier(name) if name == "t" => AcceptanceCondition::Boolean(HoaBool(true)),
+            Token::Identifier(name) if name == "f" => AcceptanceCondition::Boolean(HoaBool(false)),
+        };
+
+        let parenthesized = expr.delimited_by(just(Token::Paren('(')), just(Token::Paren(')')));
+
+        let primary = fin.or(inf).or(constant).or(parenthesized);
+
+        let unary = recursive(|unary| {
+            just(Token::Op('!'))
+                .ignore_then(unary)
+                .map(negate)
+                .or(primary)
+        });
+
+        let conjunction = unary
+            .clone()
+            .then(just(Token::Op('&')).ignore_then(unary).repeated())
+            .foldl(|left, right| left.and(right));
+
+        conjunction
+            .clone()
+            .then(just(Token::Op('|')).ignore_then(conjunction).repeated())
+            .foldl(|left, right| left.or(right))
+    })
+}
+
+/// Convenience entry point: tokenizes and parses `src` as a standalone HOA acceptance
+/// formula (i.e. the right-hand side of an `Acceptance:` header line, without the leading
+/// set count), using the same error reporting as [`crate::from_hoa`].
+pub fn parse_acceptance_condition(src: &str) -> Result<AcceptanceCondition, FromHoaError> {
+    let tokens = crate::lexer::tokenizer()
+        .parse(src)
+        .map_err(|error_list| {
+            build_error_report(
+                src,
+                error_list.into_iter().map(|err| err.map(|c| c.to_string())),
+            )
+        })
+        .map_err(FromHoaError::LexerError)?;
+
+    let length = src.chars().count();
+    acceptance_condition_parser()
+        .parse(chumsky::Stream::from_iter(
+            length..length + 1,
+            tokens.into_iter(),
+        ))
+        .map_err(|error_list| {
+            build_error_report(
+                src,
+                error_list.into_iter().map(|err| err.map(|t| t.to_string())),
+            )
+        })
+        .map_err(FromHoaError::ParserError)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::AcceptanceCondition;
+
+    #[test]
+    fn parses_leaf_and_conjunction() {
+        assert_eq!(
+            parse_acceptance_condition("Inf(0)").unwrap(),
+            AcceptanceCondition::id_inf(0)
+        );
+        assert_eq!(
+            parse_acceptance_condition("Fin(1) & Inf(2)").unwrap(),
+            AcceptanceCondition::id_fin(1).and(AcceptanceCondition::id_inf(2))
+        );
+    }
+
+    #[test]
+    fn respects_precedence_and_parens() {
+        assert_eq!(
+            parse_acceptance_condition("Inf(0) & Inf(1) | Inf(2)").unwrap(),
+            AcceptanceCondition::id_inf(0)
+                .and(AcceptanceCondition::id_inf(1))
+                .or(AcceptanceCondition::id_inf(2))
+        );
+        assert_eq!(
+            parse_acceptance_condition("Inf(0) & (Inf(1) | Inf(2))").unwrap(),
+            AcceptanceCondition::id_inf(0)
+                .and(AcceptanceCondition::id_inf(1).or(AcceptanceCondition::id_inf(2)))
+        );
+    }
+
+    #[test]
+    fn negation_is_pushed_to_the_leaves() {
+        assert_eq!(
+            parse_acceptance_condition("!Inf(0)").unwrap(),
+            AcceptanceCondition::id_fin(0)
+        );
+        assert_eq!(
+            parse_acceptance_condition("!(Fin(0) & Inf(1))").unwrap(),
+            AcceptanceCondition::id_inf(0).or(AcceptanceCondition::id_fin(1))
+        );
+    }
+
+    #[test]
+    fn negated_atom_stays_inside_the_leaf() {
+        assert_eq!(
+            parse_acceptance_condition("Fin(!0)").unwrap(),
+            AcceptanceCondition::Fin(AcceptanceAtom::Negative(0))
+        );
+    }
+}
@@ -0,0 +1,408 @@
+//! A structured reader for a complete HOA document, built on top of [`crate::lexer`],
+//! [`crate::label`] and [`crate::acceptance_parser`].
+//!
+//! This sits alongside [`crate::from_hoa`] rather than replacing it: `from_hoa` builds the
+//! crate's own [`crate::HoaRepresentation`], while [`parse_document`] produces a
+//! self-contained [`ParsedDocument`] whose edge and state labels have already had every
+//! `@alias` substituted away. It also tells `--BODY--`/`--END--`/`--ABORT--` framing apart
+//! properly (using the dedicated [`Token::Abort`] the lexer now emits, instead of
+//! `from_hoa`'s `str::contains("--ABORT--")` check) and recovers from a malformed `State:`
+//! line instead of giving up on the rest of the document: [`parse_document`] reports every
+//! error it found, not just the first.
+//!
+//! Implicit/"stored" labels -- a `State:` line carrying its own `[...]` label that then
+//! applies to any of its edges that omit one -- are parsed (into [`ParsedState::label`])
+//! but not propagated onto such edges; every HOA document this crate itself writes
+//! ([`crate::output::to_hoa`]) labels every edge explicitly, so this is the form that
+//! matters in practice.
+
+use std::collections::HashMap;
+
+use chumsky::prelude::*;
+
+use crate::acceptance_parser::acceptance_condition_parser;
+use crate::label::{label_expr_parser, resolve_aliases, LabelExpr};
+use crate::lexer::Token;
+use crate::{
+    build_error_report, AcceptanceCondition, AcceptanceName, AcceptanceSignature, AliasName,
+    AtomicProposition, FromHoaError, Id, Property, StateConjunction,
+};
+
+/// A single header line, e.g. `States: 3` or `Start: 0`. Headers this reader doesn't
+/// specifically know about are kept as [`ParsedHeader::Other`] rather than dropped.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParsedHeader {
+    /// `HOA: v1`.
+    Hoa(String),
+    /// `States: n`.
+    States(Id),
+    /// `Start: 0 & 2` (a conjunction, for alternating automata; usually a singleton).
+    Start(StateConjunction),
+    /// `AP: 2 "a" "b"`.
+    AtomicPropositions(Vec<AtomicProposition>),
+    /// `Alias: @a 0 & !1`, with the right-hand side not yet alias-resolved.
+    Alias(AliasName, LabelExpr),
+    /// `Acceptance: 2 Inf(0) | Fin(1)`.
+    Acceptance(u32, AcceptanceCondition),
+    /// `acc-name: parity min even 2`; only the family name itself is kept.
+    AcceptanceName(AcceptanceName),
+    /// `properties: trans-labels explicit-labels ...`; entries this crate doesn't
+    /// recognize are silently dropped rather than failing the whole header.
+    Properties(Vec<Property>),
+    /// `name: "a description"`.
+    Name(String),
+    /// `tool: "name" ["version"]`.
+    Tool(String, Option<String>),
+    /// Any other header, keyed by its name with the raw, unparsed tokens that followed it.
+    Other(String, Vec<Token>),
+}
+
+/// One outgoing transition: `[label] state-conj {acc-sig}`, with `label` and the
+/// acceptance signature optional since both can be inherited/omitted per the HOA grammar.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParsedEdge {
+    pub label: Option<LabelExpr>,
+    pub targets: StateConjunction,
+    pub acceptance: Option<AcceptanceSignature>,
+}
+
+/// One `State:` line together with the edges that follow it, up to the next `State:` or
+/// `--END--`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParsedState {
+    /// The state's own implicit label, if it carries one; see the module docs.
+    pub label: Option<LabelExpr>,
+    pub index: Id,
+    pub name: Option<String>,
+    pub acceptance: Option<AcceptanceSignature>,
+    pub edges: Vec<ParsedEdge>,
+}
+
+/// The `--BODY--` ... `--END--` section.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct ParsedBody {
+    pub states: Vec<ParsedState>,
+}
+
+/// A fully parsed HOA document: its headers, its `Alias:` definitions (resolved, so each
+/// maps straight to an alias-free [`LabelExpr`]), and its body, with every edge and state
+/// label already passed through [`resolve_aliases`].
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct ParsedDocument {
+    pub headers: Vec<ParsedHeader>,
+    pub aliases: HashMap<AliasName, LabelExpr>,
+    pub body: ParsedBody,
+}
+
+fn int_token() -> impl Parser<Token, String, Error = Simple<Token>> + Clone {
+    select! { Token::Int(value) => value }
+}
+
+fn state_num() -> impl Parser<Token, Id, Error = Simple<Token>> + Clone {
+    int_token().map(|value| value.parse().unwrap_or_default())
+}
+
+fn state_conjunction() -> impl Parser<Token, StateConjunction, Error = Simple<Token>> + Clone {
+    state_num()
+        .then(just(Token::Op('&')).ignore_then(state_num()).repeated())
+        .map(|(first, rest)| {
+            let mut ids = vec![first];
+            ids.extend(rest);
+            StateConjunction(ids)
+        })
+}
+
+fn bracketed_label() -> impl Parser<Token, LabelExpr, Error = Simple<Token>> + Clone {
+    label_expr_parser().delimited_by(just(Token::Paren('[')), just(Token::Paren(']')))
+}
+
+fn acceptance_signature() -> impl Parser<Token, AcceptanceSignature, Error = Simple<Token>> + Clone
+{
+    state_num()
+        .repeated()
+        .delimited_by(just(Token::Paren('{')), just(Token::Paren('}')))
+        .map(AcceptanceSignature)
+}
+
+/// Any run of tokens that doesn't start a new header or body/end marker, consumed and
+/// discarded; mops up header parameters this reader doesn't specifically model (e.g. the
+/// `min even 2` tail of an `acc-name:` line).
+fn rest_of_line_tokens() -> impl Parser<Token, Vec<Token>, Error = Simple<Token>> + Clone {
+    filter(|tok: &Token| {
+        !matches!(
+            tok,
+            Token::Header(_) | Token::BodyStart | Token::BodyEnd | Token::Abort
+        )
+    })
+    .repeated()
+}
+
+fn rest_of_line() -> impl Parser<Token, (), Error = Simple<Token>> + Clone {
+    rest_of_line_tokens().ignored()
+}
+
+fn header_line() -> impl Parser<Token, ParsedHeader, Error = Simple<Token>> + Clone {
+    let ident = select! { Token::Identifier(value) => value };
+    let text = select! { Token::Text(value) => value };
+
+    let hoa = just(Token::Header("HOA".into()))
+        .ignore_then(ident)
+        .map(ParsedHeader::Hoa);
+
+    let states = just(Token::Header("States".into()))
+        .ignore_then(state_num())
+        .map(ParsedHeader::States);
+
+    let start = just(Token::Header("Start".into()))
+        .ignore_then(state_conjunction())
+        .map(ParsedHeader::Start);
+
+    let ap = just(Token::Header("AP".into()))
+        .ignore_then(int_token())
+        .ignore_then(text.repeated())
+        .map(ParsedHeader::AtomicPropositions);
+
+    let alias = just(Token::Header("Alias".into()))
+        .ignore_then(select! { Token::Alias(name) => name })
+        .then(label_expr_parser())
+        .map(|(name, expr)| ParsedHeader::Alias(AliasName(name), expr));
+
+    let acceptance = just(Token::Header("Acceptance".into()))
+        .ignore_then(int_token())
+        .then(acceptance_condition_parser())
+        .map(|(count, cond)| ParsedHeader::Acceptance(count.parse().unwrap_or_default(), cond));
+
+    let acc_name = just(Token::Header("acc-name".into()))
+        .ignore_then(ident)
+        .try_map(|name, span| {
+            AcceptanceName::try_from(name)
+                .map(ParsedHeader::AcceptanceName)
+                .map_err(|_| Simple::custom(span, "unknown acceptance name"))
+        });
+
+    let properties = just(Token::Header("properties".into()))
+        .ignore_then(ident.repeated())
+        .map(|names| {
+            ParsedHeader::Properties(
+                names
+                    .into_iter()
+                    .filter_map(|name| Property::try_from(name).ok())
+                    .collect(),
+            )
+        });
+
+    let name = just(Token::Header("name".into()))
+        .ignore_then(text)
+        .map(ParsedHeader::Name);
+
+    let tool = just(Token::Header("tool".into()))
+        .ignore_then(text)
+        .then(text.or_not())
+        .map(|(name, version)| ParsedHeader::Tool(name, version));
+
+    let known = hoa
+        .or(states)
+        .or(start)
+        .or(ap)
+        .or(alias)
+        .or(acceptance)
+        .or(acc_name)
+        .or(properties)
+        .or(name)
+        .or(tool)
+        .then_ignore(rest_of_line());
+
+    let other = select! { Token::Header(name) => name }
+        .then(rest_of_line_tokens())
+        .map(|(name, tokens)| ParsedHeader::Other(name, tokens));
+
+    known.or(other)
+}
+
+fn edge() -> impl Parser<Token, ParsedEdge, Error = Simple<Token>> + Clone {
+    bracketed_label()
+        .or_not()
+        .then(state_conjunction())
+        .then(acceptance_signature().or_not())
+        .map(|((label, targets), acceptance)| ParsedEdge {
+            label,
+            targets,
+            acceptance,
+        })
+}
+
+fn state() -> impl Parser<Token, ParsedState, Error = Simple<Token>> + Clone {
+    just(Token::Header("State".into()))
+        .ignore_then(bracketed_label().or_not())
+        .then(state_num())
+        .then(select! { Token::Text(value) => value }.or_not())
+        .then(acceptance_signature().or_not())
+        .then(edge().repeated())
+        .map(
+            |((((label, index), name), acceptance), edges)| ParsedState {
+                label,
+                index,
+                name,
+                acceptance,
+                edges,
+            },
+        )
+}
+
+fn headers_parser() -> impl Parser<Token, Vec<ParsedHeader>, Error = Simple<Token>> + Clone {
+    header_line()
+        .recover_with(skip_then_retry_until([Token::BodyStart]))
+        .repeated()
+}
+
+fn body_parser() -> impl Parser<Token, ParsedBody, Error = Simple<Token>> + Clone {
+    just(Token::BodyStart)
+        .ignore_then(
+            state()
+                .recover_with(skip_then_retry_until([
+                    Token::Header("State".into()),
+                    Token::BodyEnd,
+                ]))
+                .repeated(),
+        )
+        .then_ignore(just(Token::BodyEnd))
+        .map(|states| ParsedBody { states })
+}
+
+fn document_parser() -> impl Parser<Token, ParsedDocument, Error = Simple<Token>> {
+    headers_parser().then(body_parser()).map(|(headers, body)| {
+        let raw_aliases: HashMap<AliasName, LabelExpr> = headers
+            .iter()
+            .filter_map(|header| match header {
+                ParsedHeader::Alias(name, expr) => Some((name.clone(), expr.clone())),
+                _ => None,
+            })
+            .collect();
+        let aliases: HashMap<AliasName, LabelExpr> = raw_aliases
+            .iter()
+            .map(|(name, expr)| (name.clone(), resolve_aliases(expr, &raw_aliases)))
+            .collect();
+
+        let body = ParsedBody {
+            states: body
+                .states
+                .into_iter()
+                .map(|state| ParsedState {
+                    label: state.label.map(|label| resolve_aliases(&label, &aliases)),
+                    edges: state
+                        .edges
+                        .into_iter()
+                        .map(|edge| ParsedEdge {
+                            label: edge.label.map(|label| resolve_aliases(&label, &aliases)),
+                            ..edge
+                        })
+                        .collect(),
+                    ..state
+                })
+                .collect(),
+        };
+
+        ParsedDocument {
+            headers,
+            aliases,
+            body,
+        }
+    })
+}
+
+/// Tokenizes and parses `src` as a full HOA document, resolving every `@alias` reference
+/// in the body and collecting as many errors as possible instead of stopping at the
+/// first one. Returns [`FromHoaError::Abort`] if the lexer finds a `--ABORT--` marker
+/// anywhere in `src`, per the HOA spec (the tool emitting the document gave up, so
+/// whatever was written before it is not a complete document).
+pub fn parse_document(src: &str) -> Result<ParsedDocument, FromHoaError> {
+    let tokens = crate::lexer::tokenizer()
+        .parse(src)
+        .map_err(|error_list| {
+            build_error_report(
+                src,
+                error_list.into_iter().map(|err| err.map(|c| c.to_string())),
+            )
+        })
+        .map_err(FromHoaError::LexerError)?;
+
+    if tokens.iter().any(|(tok, _)| matches!(tok, Token::Abort)) {
+        return Err(FromHoaError::Abort);
+    }
+
+    let length = src.chars().count();
+    let (document, errors) = document_parser().parse_recovery(chumsky::Stream::from_iter(
+        length..length + 1,
+        tokens.into_iter(),
+    ));
+
+    if !errors.is_empty() {
+        return Err(FromHoaError::ParserError(build_error_report(
+            src,
+            errors.into_iter().map(|err| err.map(|t| t.to_string())),
+        )));
+    }
+
+    Ok(document.expect("parser reported no errors but produced no document"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_headers_and_resolves_aliases_in_the_body() {
+        let src = r#"HOA: v1
+States: 2
+Start: 0
+AP: 1 "a"
+Alias: @good 0
+Acceptance: 1 Inf(0)
+--BODY--
+State: 0
+[@good] 0 {0}
+[!@good] 1
+State: 1
+[t] 1
+--END--
+"#;
+        let document = parse_document(src).unwrap();
+        assert_eq!(document.headers.len(), 6);
+        assert_eq!(document.aliases.len(), 1);
+        assert_eq!(document.body.states.len(), 2);
+        assert_eq!(
+            document.body.states[0].edges[0].label,
+            Some(LabelExpr::Ap(0))
+        );
+        assert_eq!(
+            document.body.states[0].edges[1].label,
+            Some(LabelExpr::Not(Box::new(LabelExpr::Ap(0))))
+        );
+    }
+
+    #[test]
+    fn abort_marker_short_circuits() {
+        assert!(matches!(
+            parse_document("HOA: v1\n--ABORT--"),
+            Err(FromHoaError::Abort)
+        ));
+    }
+
+    #[test]
+    fn recovers_from_a_malformed_state_line_and_keeps_parsing() {
+        let src = r#"HOA: v1
+States: 2
+Start: 0
+AP: 0
+Acceptance: 1 Inf(0)
+--BODY--
+State: 0
+[t] not-a-number
+State: 1
+[t] 1
+--END--
+"#;
+        let err = parse_document(src).unwrap_err();
+        assert!(matches!(err, FromHoaError::ParserError(_)));
+    }
+}
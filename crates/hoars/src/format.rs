@@ -158,6 +158,244 @@ impl AcceptanceCondition {
     pub fn id_inf(id: Id) -> Self {
         Self::Inf(AcceptanceAtom::Positive(id))
     }
+
+    /// Creates a generalized Büchi acceptance condition `⋀ Inf(i)` over the given sets.
+    /// Returns `true` for an empty iterator, matching the empty conjunction.
+    pub fn generalized_buchi(sets: impl IntoIterator<Item = Id>) -> Self {
+        sets.into_iter()
+            .map(Self::id_inf)
+            .reduce(|acc, next| acc.and(next))
+            .unwrap_or(Self::Boolean(HoaBool(true)))
+    }
+
+    /// Creates a generalized co-Büchi acceptance condition `⋁ Fin(i)` over the given
+    /// sets. Returns `false` for an empty iterator, matching the empty disjunction.
+    pub fn generalized_co_buchi(sets: impl IntoIterator<Item = Id>) -> Self {
+        sets.into_iter()
+            .map(Self::id_fin)
+            .reduce(|acc, next| acc.or(next))
+            .unwrap_or(Self::Boolean(HoaBool(false)))
+    }
+
+    /// Creates a Rabin acceptance condition `⋁ᵢ (Fin(Eᵢ) ∧ Inf(Fᵢ))` from a set of
+    /// `(Eᵢ, Fᵢ)` pairs.
+    pub fn rabin(pairs: impl IntoIterator<Item = (Id, Id)>) -> Self {
+        pairs
+            .into_iter()
+            .map(|(fin, inf)| Self::id_fin(fin).and(Self::id_inf(inf)))
+            .reduce(|acc, next| acc.or(next))
+            .unwrap_or(Self::Boolean(HoaBool(false)))
+    }
+
+    /// Creates a Streett acceptance condition `⋀ᵢ (Fin(Eᵢ) ∨ Inf(Fᵢ))` from a set of
+    /// `(Eᵢ, Fᵢ)` pairs, dual to [`Self::rabin`].
+    pub fn streett(pairs: impl IntoIterator<Item = (Id, Id)>) -> Self {
+        pairs
+            .into_iter()
+            .map(|(fin, inf)| Self::id_fin(fin).or(Self::id_inf(inf)))
+            .reduce(|acc, next| acc.and(next))
+            .unwrap_or(Self::Boolean(HoaBool(true)))
+    }
+
+    /// Creates a generalized Rabin acceptance condition `⋁ᵢ (Fin(Eᵢ) ∧ ⋀ⱼ Inf(Fᵢⱼ))`
+    /// from a set of pairs, each an `Eᵢ` together with its (possibly empty) collection of
+    /// `Fᵢⱼ`.
+    pub fn generalized_rabin(pairs: impl IntoIterator<Item = (Id, Vec<Id>)>) -> Self {
+        pairs
+            .into_iter()
+            .map(|(fin, infs)| {
+                let infs = infs
+                    .into_iter()
+                    .map(Self::id_inf)
+                    .reduce(|acc, next| acc.and(next))
+                    .unwrap_or(Self::Boolean(HoaBool(true)));
+                Self::id_fin(fin).and(infs)
+            })
+            .reduce(|acc, next| acc.or(next))
+            .unwrap_or(Self::Boolean(HoaBool(false)))
+    }
+
+    /// Collects the conjuncts of a (possibly deeply nested) conjunction, treating any
+    /// node that is not itself an `And` as a single conjunct.
+    fn flatten_and(&self) -> Vec<Self> {
+        match self {
+            Self::And(left, right) => {
+                let mut conjuncts = left.flatten_and();
+                conjuncts.extend(right.flatten_and());
+                conjuncts
+            }
+            other => vec![other.clone()],
+        }
+    }
+
+    /// Collects the disjuncts of a (possibly deeply nested) disjunction, treating any
+    /// node that is not itself an `Or` as a single disjunct.
+    fn flatten_or(&self) -> Vec<Self> {
+        match self {
+            Self::Or(left, right) => {
+                let mut disjuncts = left.flatten_or();
+                disjuncts.extend(right.flatten_or());
+                disjuncts
+            }
+            other => vec![other.clone()],
+        }
+    }
+
+    /// Applies boolean identities (`Boolean(true)`/`Boolean(false)` annihilate or vanish
+    /// under `And`/`Or`), flattens nested conjunctions/disjunctions of the same kind, and
+    /// removes duplicate conjuncts/disjuncts, recursively from the leaves up.
+    pub fn simplify(&self) -> Self {
+        match self {
+            Self::And(..) => {
+                let mut parts: Vec<Self> = self.flatten_and().iter().map(Self::simplify).collect();
+                if parts
+                    .iter()
+                    .any(|part| matches!(part, Self::Boolean(HoaBool(false))))
+                {
+                    return Self::Boolean(HoaBool(false));
+                }
+                parts.retain(|part| !matches!(part, Self::Boolean(HoaBool(true))));
+                dedup(&mut parts);
+                parts
+                    .into_iter()
+                    .reduce(|acc, next| acc.and(next))
+                    .unwrap_or(Self::Boolean(HoaBool(true)))
+            }
+            Self::Or(..) => {
+                let mut parts: Vec<Self> = self.flatten_or().iter().map(Self::simplify).collect();
+                if parts
+                    .iter()
+                    .any(|part| matches!(part, Self::Boolean(HoaBool(true))))
+                {
+                    return Self::Boolean(HoaBool(true));
+                }
+                parts.retain(|part| !matches!(part, Self::Boolean(HoaBool(false))));
+                dedup(&mut parts);
+                parts
+                    .into_iter()
+                    .reduce(|acc, next| acc.or(next))
+                    .unwrap_or(Self::Boolean(HoaBool(false)))
+            }
+            other => other.clone(),
+        }
+    }
+
+    /// Structurally matches the shape produced by [`Self::parity_rec`] starting at
+    /// priority `current`: an alternating chain `Inf(current) ∨ (Fin(current+1) ∧ (...))`
+    /// that bottoms out in a bare `Inf`/`Fin` leaf on the correct parity.
+    fn is_parity_shape(&self, current: Id) -> bool {
+        match self {
+            Self::Inf(AcceptanceAtom::Positive(id)) => *id == current && current.rem(2) == 0,
+            Self::Fin(AcceptanceAtom::Positive(id)) => *id == current && current.rem(2) == 1,
+            Self::Or(left, right) if current.rem(2) == 0 => {
+                matches!(left.as_ref(), Self::Inf(AcceptanceAtom::Positive(id)) if *id == current)
+                    && right.is_parity_shape(current + 1)
+            }
+            Self::And(left, right) if current.rem(2) == 1 => {
+                matches!(left.as_ref(), Self::Fin(AcceptanceAtom::Positive(id)) if *id == current)
+                    && right.is_parity_shape(current + 1)
+            }
+            _ => false,
+        }
+    }
+
+    /// `Fin(Eᵢ) ∧ Inf(Fᵢ)` for a single pair, the shape of one Rabin disjunct.
+    fn is_rabin_pair(&self) -> bool {
+        matches!(
+            self,
+            Self::And(left, right)
+                if matches!(left.as_ref(), Self::Fin(AcceptanceAtom::Positive(_)))
+                    && matches!(right.as_ref(), Self::Inf(AcceptanceAtom::Positive(_)))
+        )
+    }
+
+    /// `Fin(Eᵢ) ∧ ⋀ⱼ Inf(Fᵢⱼ)` for `j >= 1`, the shape of one generalized-Rabin disjunct.
+    fn is_generalized_rabin_pair(&self) -> bool {
+        let parts = self.flatten_and();
+        parts.len() >= 2
+            && matches!(parts[0], Self::Fin(AcceptanceAtom::Positive(_)))
+            && parts[1..]
+                .iter()
+                .all(|part| matches!(part, Self::Inf(AcceptanceAtom::Positive(_))))
+    }
+
+    /// `Fin(Eᵢ) ∨ Inf(Fᵢ)` for a single pair, the shape of one Streett conjunct.
+    fn is_streett_pair(&self) -> bool {
+        matches!(
+            self,
+            Self::Or(left, right)
+                if matches!(left.as_ref(), Self::Fin(AcceptanceAtom::Positive(_)))
+                    && matches!(right.as_ref(), Self::Inf(AcceptanceAtom::Positive(_)))
+        )
+    }
+
+    /// Structurally classifies `self` back into the [`AcceptanceName`] family it was
+    /// built from (after [`Self::simplify`]ing away any boolean noise), if it matches one
+    /// of the named shapes exactly. This is the converse of `buchi`/`rabin`/`streett`/...:
+    /// it recognizes the shapes those constructors (and [`Self::parity`]) produce, not
+    /// arbitrary semantically-equivalent conditions.
+    pub fn recognize(&self) -> Option<AcceptanceName> {
+        let cond = self.simplify();
+        match &cond {
+            Self::Boolean(HoaBool(true)) => return Some(AcceptanceName::All),
+            Self::Boolean(HoaBool(false)) => return Some(AcceptanceName::None),
+            _ => {}
+        }
+        if cond == Self::id_inf(0) {
+            return Some(AcceptanceName::Buchi);
+        }
+        if cond == Self::id_fin(0) {
+            return Some(AcceptanceName::CoBuchi);
+        }
+        if cond.is_parity_shape(0) {
+            return Some(AcceptanceName::Parity);
+        }
+
+        let conjuncts = cond.flatten_and();
+        if conjuncts.len() > 1
+            && conjuncts
+                .iter()
+                .all(|part| matches!(part, Self::Inf(AcceptanceAtom::Positive(_))))
+        {
+            return Some(AcceptanceName::GeneralizedBuchi);
+        }
+        if conjuncts.len() > 1 && conjuncts.iter().all(Self::is_streett_pair) {
+            return Some(AcceptanceName::Streett);
+        }
+
+        let disjuncts = cond.flatten_or();
+        if disjuncts.len() > 1
+            && disjuncts
+                .iter()
+                .all(|part| matches!(part, Self::Fin(AcceptanceAtom::Positive(_))))
+        {
+            return Some(AcceptanceName::GeneralizedCoBuchi);
+        }
+        if !disjuncts.is_empty() && disjuncts.iter().all(Self::is_rabin_pair) {
+            return Some(AcceptanceName::Rabin);
+        }
+        if !disjuncts.is_empty()
+            && disjuncts
+                .iter()
+                .all(|part| Self::is_rabin_pair(part) || Self::is_generalized_rabin_pair(part))
+        {
+            return Some(AcceptanceName::GeneralizedRabin);
+        }
+
+        None
+    }
+}
+
+/// A simple quadratic dedup that works for any `Eq` type, used by [`AcceptanceCondition::simplify`]
+/// since acceptance conditions don't implement `Hash`.
+fn dedup<T: Eq>(items: &mut Vec<T>) {
+    let mut deduped: Vec<T> = Vec::with_capacity(items.len());
+    for item in items.drain(..) {
+        if !deduped.contains(&item) {
+            deduped.push(item);
+        }
+    }
+    *items = deduped;
 }
 
 /// Represents the name of a type of acceptance condition.
@@ -311,7 +549,7 @@ impl AcceptanceInfo {
 
 #[cfg(test)]
 mod tests {
-    use crate::AcceptanceCondition;
+    use crate::{AcceptanceCondition, AcceptanceName, HoaBool};
 
     #[test]
     fn parity_acceptance_creator() {
@@ -322,4 +560,66 @@ mod tests {
                 .or(AcceptanceCondition::id_fin(1).and(AcceptanceCondition::id_inf(2)))
         );
     }
+
+    #[test]
+    fn rabin_and_streett_are_dual_shapes() {
+        let rabin = AcceptanceCondition::rabin([(0, 1), (2, 3)]);
+        assert_eq!(
+            rabin,
+            AcceptanceCondition::id_fin(0)
+                .and(AcceptanceCondition::id_inf(1))
+                .or(AcceptanceCondition::id_fin(2).and(AcceptanceCondition::id_inf(3)))
+        );
+
+        let streett = AcceptanceCondition::streett([(0, 1), (2, 3)]);
+        assert_eq!(
+            streett,
+            AcceptanceCondition::id_fin(0)
+                .or(AcceptanceCondition::id_inf(1))
+                .and(AcceptanceCondition::id_fin(2).or(AcceptanceCondition::id_inf(3)))
+        );
+    }
+
+    #[test]
+    fn simplify_absorbs_boolean_constants_and_dedups() {
+        let cond = AcceptanceCondition::id_inf(0)
+            .and(AcceptanceCondition::Boolean(HoaBool(true)))
+            .and(AcceptanceCondition::id_inf(0));
+        assert_eq!(cond.simplify(), AcceptanceCondition::id_inf(0));
+
+        let cond = AcceptanceCondition::id_fin(0).or(AcceptanceCondition::Boolean(HoaBool(true)));
+        assert_eq!(cond.simplify(), AcceptanceCondition::Boolean(HoaBool(true)));
+    }
+
+    #[test]
+    fn recognize_named_families() {
+        assert_eq!(
+            AcceptanceCondition::buchi().recognize(),
+            Some(AcceptanceName::Buchi)
+        );
+        assert_eq!(
+            AcceptanceCondition::generalized_buchi([0, 1, 2]).recognize(),
+            Some(AcceptanceName::GeneralizedBuchi)
+        );
+        assert_eq!(
+            AcceptanceCondition::generalized_co_buchi([0, 1]).recognize(),
+            Some(AcceptanceName::GeneralizedCoBuchi)
+        );
+        assert_eq!(
+            AcceptanceCondition::rabin([(0, 1), (2, 3)]).recognize(),
+            Some(AcceptanceName::Rabin)
+        );
+        assert_eq!(
+            AcceptanceCondition::streett([(0, 1), (2, 3)]).recognize(),
+            Some(AcceptanceName::Streett)
+        );
+        assert_eq!(
+            AcceptanceCondition::generalized_rabin([(0, vec![1, 2]), (3, vec![4])]).recognize(),
+            Some(AcceptanceName::GeneralizedRabin)
+        );
+        assert_eq!(
+            AcceptanceCondition::parity(4).recognize(),
+            Some(AcceptanceName::Parity)
+        );
+    }
 }
@@ -0,0 +1,232 @@
+//! An idempotent canonical reformatter for HOA source text, built on top of
+//! [`crate::document::parse_document`] rather than [`crate::output::to_hoa`]: the latter
+//! serializes the crate's own [`crate::HoaRepresentation`], but canonicalizing aliases and
+//! dropping tool headers both require restructuring the parsed document itself, which
+//! [`ParsedDocument`] exposes and `HoaRepresentation` does not.
+//!
+//! Beyond whitespace, [`format_hoa`] canonicalizes: `properties:` token order (alphabetical),
+//! boolean constants (`t`/`f`, via [`LabelExpr`]'s own `Display`), parenthesization (the
+//! minimal-parens printer already used by [`crate::output`]), and -- per [`FormatOptions`] --
+//! whether `@alias` references are inlined away or kept/re-extracted, and whether `tool:`
+//! headers are dropped. Running it twice over its own output is a no-op.
+
+use itertools::Itertools;
+
+use crate::document::{ParsedDocument, ParsedEdge, ParsedHeader, ParsedState};
+use crate::label::LabelExpr;
+use crate::{AliasName, FromHoaError, Property};
+
+/// How [`format_hoa`] should treat `@alias` references.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AliasMode {
+    /// Drop every `Alias:` header and leave every body label as the already-alias-resolved
+    /// expression [`crate::document::parse_document`] produced.
+    Inline,
+    /// Keep the `Alias:` headers (sorted by name) and, wherever a state or edge label is
+    /// structurally identical to one of their definitions, print it as the `@name` reference
+    /// instead of spelling the definition back out.
+    Extract,
+}
+
+/// Settings for [`format_hoa`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FormatOptions {
+    pub alias_mode: AliasMode,
+    /// If `true`, `tool:` headers are dropped from the output.
+    pub drop_tool_headers: bool,
+}
+
+impl Default for FormatOptions {
+    fn default() -> Self {
+        Self {
+            alias_mode: AliasMode::Inline,
+            drop_tool_headers: false,
+        }
+    }
+}
+
+/// Parses `input` as a HOA document and re-serializes it in a single canonical form, such
+/// that formatting the result again yields byte-identical output.
+pub fn format_hoa(input: &str, options: &FormatOptions) -> Result<String, FromHoaError> {
+    let document = crate::document::parse_document(input)?;
+    Ok(render_document(&document, options))
+}
+
+pub(crate) fn render_document(document: &ParsedDocument, options: &FormatOptions) -> String {
+    let mut aliases: Vec<(&AliasName, &LabelExpr)> = document.aliases.iter().collect();
+    aliases.sort_by(|a, b| a.0.0.cmp(&b.0.0));
+
+    let mut lines: Vec<String> = document
+        .headers
+        .iter()
+        .filter(|header| !matches!(header, ParsedHeader::Alias(..)))
+        .filter(|header| !(options.drop_tool_headers && matches!(header, ParsedHeader::Tool(..))))
+        .map(render_header)
+        .collect();
+
+    if matches!(options.alias_mode, AliasMode::Extract) {
+        lines.extend(
+            aliases
+                .iter()
+                .map(|(name, expr)| format!("Alias: {name} {expr}")),
+        );
+    }
+
+    lines.push("--BODY--".to_string());
+    lines.extend(document.body.states.iter().map(|state| match options.alias_mode {
+        AliasMode::Inline => render_state(state),
+        AliasMode::Extract => render_state(&extract_aliases_in_state(state, &aliases)),
+    }));
+    lines.push("--END--".to_string());
+
+    lines.join("\n")
+}
+
+fn render_header(header: &ParsedHeader) -> String {
+    match header {
+        ParsedHeader::Hoa(version) => format!("HOA: {version}"),
+        ParsedHeader::States(count) => format!("States: {count}"),
+        ParsedHeader::Start(conjunction) => format!("Start: {conjunction}"),
+        ParsedHeader::AtomicPropositions(aps) => format!(
+            "AP: {} {}",
+            aps.len(),
+            aps.iter().map(|ap| format!("\"{ap}\"")).join(" ")
+        ),
+        ParsedHeader::Alias(name, expr) => format!("Alias: {name} {expr}"),
+        ParsedHeader::Acceptance(count, condition) => format!("Acceptance: {count} {condition}"),
+        ParsedHeader::AcceptanceName(name) => format!("acc-name: {name}"),
+        ParsedHeader::Properties(properties) => {
+            let mut sorted: Vec<String> = properties.iter().map(Property::to_string).collect();
+            sorted.sort();
+            format!("properties: {}", sorted.join(" "))
+        }
+        ParsedHeader::Name(name) => format!("name: \"{name}\""),
+        ParsedHeader::Tool(name, version) => match version {
+            Some(version) => format!("tool: \"{name}\" \"{version}\""),
+            None => format!("tool: \"{name}\""),
+        },
+        ParsedHeader::Other(name, tokens) => {
+            if tokens.is_empty() {
+                format!("{name}:")
+            } else {
+                format!("{name}: {}", tokens.iter().join(" "))
+            }
+        }
+    }
+}
+
+fn render_state(state: &ParsedState) -> String {
+    let mut line = String::from("State:");
+    if let Some(label) = &state.label {
+        line.push_str(&format!(" [{label}]"));
+    }
+    line.push_str(&format!(" {}", state.index));
+    if let Some(name) = &state.name {
+        line.push_str(&format!(" \"{name}\""));
+    }
+    if let Some(acceptance) = &state.acceptance {
+        if !acceptance.is_empty() {
+            line.push_str(&format!(" {acceptance}"));
+        }
+    }
+    std::iter::once(line)
+        .chain(state.edges.iter().map(render_edge))
+        .join("\n")
+}
+
+fn render_edge(edge: &ParsedEdge) -> String {
+    let mut line = String::new();
+    if let Some(label) = &edge.label {
+        line.push_str(&format!("[{label}] "));
+    }
+    line.push_str(&edge.targets.to_string());
+    if let Some(acceptance) = &edge.acceptance {
+        if !acceptance.is_empty() {
+            line.push_str(&format!(" {acceptance}"));
+        }
+    }
+    line
+}
+
+fn extract_aliases_in_state(
+    state: &ParsedState,
+    aliases: &[(&AliasName, &LabelExpr)],
+) -> ParsedState {
+    ParsedState {
+        label: state.label.as_ref().map(|label| extract_alias(label, aliases)),
+        edges: state
+            .edges
+            .iter()
+            .map(|edge| ParsedEdge {
+                label: edge.label.as_ref().map(|label| extract_alias(label, aliases)),
+                ..edge.clone()
+            })
+            .collect(),
+        ..state.clone()
+    }
+}
+
+fn extract_alias(expr: &LabelExpr, aliases: &[(&AliasName, &LabelExpr)]) -> LabelExpr {
+    match aliases.iter().find(|(_, definition)| *definition == expr) {
+        Some((name, _)) => LabelExpr::Alias((*name).clone()),
+        None => expr.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> &'static str {
+        "HOA: v1\nStates: 2\nStart: 0\nAP: 1 \"a\"\ntool: \"ralf\" \"1.0\"\nAlias: @good 0\nAcceptance: 1 Inf(0)\nproperties: trans-labels deterministic\n--BODY--\nState: 0\n[@good] 0 {0}\n[!@good] 1\nState: 1\n[t] 1\n--END--\n"
+    }
+
+    #[test]
+    fn inlining_drops_alias_headers_and_spells_out_definitions() {
+        let options = FormatOptions {
+            alias_mode: AliasMode::Inline,
+            drop_tool_headers: false,
+        };
+        let formatted = format_hoa(sample(), &options).unwrap();
+        assert!(!formatted.contains("Alias:"));
+        assert!(formatted.contains("[0] 0 {0}"));
+        assert!(formatted.contains("[!0] 1"));
+    }
+
+    #[test]
+    fn extracting_keeps_alias_headers_and_re_references_them() {
+        let options = FormatOptions {
+            alias_mode: AliasMode::Extract,
+            drop_tool_headers: false,
+        };
+        let formatted = format_hoa(sample(), &options).unwrap();
+        assert!(formatted.contains("Alias: @good 0"));
+        assert!(formatted.contains("[@good] 0 {0}"));
+        assert!(formatted.contains("[!0] 1"));
+    }
+
+    #[test]
+    fn drop_tool_headers_removes_the_tool_line() {
+        let options = FormatOptions {
+            alias_mode: AliasMode::Inline,
+            drop_tool_headers: true,
+        };
+        let formatted = format_hoa(sample(), &options).unwrap();
+        assert!(!formatted.contains("tool:"));
+    }
+
+    #[test]
+    fn properties_are_sorted_alphabetically() {
+        let options = FormatOptions::default();
+        let formatted = format_hoa(sample(), &options).unwrap();
+        assert!(formatted.contains("properties: deterministic trans-labels"));
+    }
+
+    #[test]
+    fn formatting_is_idempotent() {
+        let options = FormatOptions::default();
+        let once = format_hoa(sample(), &options).unwrap();
+        let twice = format_hoa(&once, &options).unwrap();
+        assert_eq!(once, twice);
+    }
+}
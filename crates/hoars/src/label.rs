@@ -0,0 +1,182 @@
+//! The boolean expression that labels a transition in the `--BODY--` section, e.g.
+//! `[0 & !1 | @a]`. Unlike [`crate::AcceptanceCondition`] -- which avoids a `Not` node
+//! because `Fin`/`Inf` are already mutual negations of each other -- a label's atoms are
+//! plain atomic-proposition indices (or alias references), so negation has nothing to be
+//! pushed into and [`LabelExpr`] keeps it as an ordinary node.
+
+use std::collections::HashMap;
+
+use chumsky::prelude::*;
+
+use crate::lexer::Token;
+use crate::{AliasName, Id};
+
+/// A parsed, not-yet-alias-resolved transition label.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LabelExpr {
+    /// A reference to atomic proposition `id` by its index into the `AP:` header.
+    Ap(Id),
+    /// A reference to an `@name` alias, substituted away by [`resolve_aliases`].
+    Alias(AliasName),
+    /// Negates the wrapped expression.
+    Not(Box<LabelExpr>),
+    /// Conjunction of two label expressions.
+    And(Box<LabelExpr>, Box<LabelExpr>),
+    /// Disjunction of two label expressions.
+    Or(Box<LabelExpr>, Box<LabelExpr>),
+    /// A constant boolean value (`t`/`f`).
+    Boolean(bool),
+}
+
+impl LabelExpr {
+    /// Evaluates `self` against a fixed valuation of the atomic propositions, indexed by
+    /// [`Id`]. Panics if `self` still contains an [`LabelExpr::Alias`] node; resolve those
+    /// with [`resolve_aliases`] first.
+    pub fn evaluate(&self, valuation: &[bool]) -> bool {
+        match self {
+            Self::Ap(id) => valuation[*id as usize],
+            Self::Alias(name) => panic!("unresolved alias @{} in label expression", name.0),
+            Self::Not(inner) => !inner.evaluate(valuation),
+            Self::And(left, right) => left.evaluate(valuation) && right.evaluate(valuation),
+            Self::Or(left, right) => left.evaluate(valuation) || right.evaluate(valuation),
+            Self::Boolean(value) => *value,
+        }
+    }
+}
+
+/// Substitutes every `@name` reference in `expr` with its definition in `aliases`,
+/// recursively, so the result contains no [`LabelExpr::Alias`] nodes. An alias that is
+/// itself undefined, or whose definition (transitively) refers back to itself, is left as
+/// `Boolean(false)` rather than recursing forever or panicking, since a malformed
+/// `Alias:` header shouldn't take down the whole document.
+pub fn resolve_aliases(expr: &LabelExpr, aliases: &HashMap<AliasName, LabelExpr>) -> LabelExpr {
+    fn go(
+        expr: &LabelExpr,
+        aliases: &HashMap<AliasName, LabelExpr>,
+        seen: &mut Vec<AliasName>,
+    ) -> LabelExpr {
+        match expr {
+            LabelExpr::Alias(name) => {
+                if seen.contains(name) {
+                    return LabelExpr::Boolean(false);
+                }
+                let Some(definition) = aliases.get(name) else {
+                    return LabelExpr::Boolean(false);
+                };
+                seen.push(name.clone());
+                let resolved = go(definition, aliases, seen);
+                seen.pop();
+                resolved
+            }
+            LabelExpr::Not(inner) => LabelExpr::Not(Box::new(go(inner, aliases, seen))),
+            LabelExpr::And(left, right) => LabelExpr::And(
+                Box::new(go(left, aliases, seen)),
+                Box::new(go(right, aliases, seen)),
+            ),
+            LabelExpr::Or(left, right) => LabelExpr::Or(
+                Box::new(go(left, aliases, seen)),
+                Box::new(go(right, aliases, seen)),
+            ),
+            other @ (LabelExpr::Ap(_) | LabelExpr::Boolean(_)) => other.clone(),
+        }
+    }
+    go(expr, aliases, &mut Vec::new())
+}
+
+/// Parses a bracketed label body (the inside of `[...]`), with the usual `!` > `&` > `|`
+/// precedence and full parenthesization, over [`Token::Int`] (an AP index),
+/// [`Token::Alias`], and the `t`/`f` constants.
+pub fn label_expr_parser() -> impl Parser<Token, LabelExpr, Error = Simple<Token>> + Clone {
+    recursive(|expr| {
+        let ap = select! { Token::Int(value) => value }
+            .map(|value: String| LabelExpr::Ap(value.parse::<Id>().unwrap_or_default()));
+
+        let alias = select! { Token::Alias(name) => LabelExpr::Alias(AliasName(name)) };
+
+        let constant = select! {
+            Token::Identifier(name) if name == "t" => LabelExpr::Boolean(true),
+            Token::Identifier(name) if name == "f" => LabelExpr::Boolean(false),
+        };
+
+        let parenthesized = expr.delimited_by(just(Token::Paren('(')), just(Token::Paren(')')));
+
+        let primary = ap.or(alias).or(constant).or(parenthesized);
+
+        let unary = recursive(|unary| {
+            just(Token::Op('!'))
+                .ignore_then(unary)
+                .map(|inner| LabelExpr::Not(Box::new(inner)))
+                .or(primary)
+        });
+
+        let conjunction = unary
+            .clone()
+            .then(just(Token::Op('&')).ignore_then(unary).repeated())
+            .foldl(|left, right| LabelExpr::And(Box::new(left), Box::new(right)));
+
+        conjunction
+            .clone()
+            .then(just(Token::Op('|')).ignore_then(conjunction).repeated())
+            .foldl(|left, right| LabelExpr::Or(Box::new(left), Box::new(right)))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(src: &str) -> LabelExpr {
+        let tokens = crate::lexer::tokenizer().parse(src).unwrap();
+        let length = src.chars().count();
+        label_expr_parser()
+            .parse(chumsky::Stream::from_iter(
+                length..length + 1,
+                tokens.into_iter(),
+            ))
+            .unwrap()
+    }
+
+    #[test]
+    fn parses_literals_and_precedence() {
+        assert_eq!(
+            parse("0 & !1 | 2"),
+            LabelExpr::Or(
+                Box::new(LabelExpr::And(
+                    Box::new(LabelExpr::Ap(0)),
+                    Box::new(LabelExpr::Not(Box::new(LabelExpr::Ap(1))))
+                )),
+                Box::new(LabelExpr::Ap(2))
+            )
+        );
+    }
+
+    #[test]
+    fn resolves_aliases_recursively() {
+        let mut aliases = HashMap::new();
+        aliases.insert(AliasName("a".into()), LabelExpr::Ap(0));
+        aliases.insert(
+            AliasName("b".into()),
+            LabelExpr::And(
+                Box::new(LabelExpr::Alias(AliasName("a".into()))),
+                Box::new(LabelExpr::Ap(1)),
+            ),
+        );
+        let expr = LabelExpr::Not(Box::new(LabelExpr::Alias(AliasName("b".into()))));
+        assert_eq!(
+            resolve_aliases(&expr, &aliases),
+            LabelExpr::Not(Box::new(LabelExpr::And(
+                Box::new(LabelExpr::Ap(0)),
+                Box::new(LabelExpr::Ap(1))
+            )))
+        );
+    }
+
+    #[test]
+    fn undefined_alias_resolves_to_false() {
+        let expr = LabelExpr::Alias(AliasName("missing".into()));
+        assert_eq!(
+            resolve_aliases(&expr, &HashMap::new()),
+            LabelExpr::Boolean(false)
+        );
+    }
+}
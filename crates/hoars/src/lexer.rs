@@ -70,7 +70,7 @@ pub fn tokenizer() -> impl Parser<char, Vec<(Token, Span)>, Error = Simple<char>
 
     let body = just("--BODY--").to(Token::BodyStart);
     let end = just("--END--").to(Token::BodyEnd);
-    let abort = just("--ABORT--").to(Token::BodyEnd);
+    let abort = just("--ABORT--").to(Token::Abort);
 
     let token = int
         .or(abort)
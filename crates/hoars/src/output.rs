@@ -2,20 +2,138 @@ use std::fmt::Display;
 
 use itertools::Itertools;
 
+use crate::label::LabelExpr;
 use crate::{
     AcceptanceAtom, AcceptanceCondition, AcceptanceInfo, AcceptanceName, AcceptanceSignature,
     AliasName, Edge, HeaderItem, HoaBool, HoaRepresentation, Label, Property, State,
     StateConjunction,
 };
 
+/// Binding strength of a boolean-expression operator, shared by the [`AcceptanceCondition`] and
+/// [`LabelExpr`] printers below: `&` binds tighter than `|`, and (for [`LabelExpr`] only, which
+/// has an explicit [`LabelExpr::Not`] node) unary `!` binds tighter than both. Both binary
+/// operators are left-associative.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) enum Precedence {
+    Or,
+    And,
+    Not,
+}
+
+/// Implemented by the two boolean-expression trees that share the minimal-parenthesization
+/// printer below, so it can ask either one for its own top-level operator without matching on
+/// two unrelated enums. Also reused by [`crate::pretty`] to decide where [`to_hoa_pretty`]-style
+/// layout needs parentheses.
+///
+/// [`to_hoa_pretty`]: crate::pretty::to_hoa_pretty
+pub(crate) trait Fixity {
+    /// The precedence of this node's own top-level operator, or `None` for an atom (nothing
+    /// that printing it could ever need parentheses around).
+    fn precedence(&self) -> Option<Precedence>;
+}
+
+impl Fixity for AcceptanceCondition {
+    fn precedence(&self) -> Option<Precedence> {
+        match self {
+            Self::And(..) => Some(Precedence::And),
+            Self::Or(..) => Some(Precedence::Or),
+            Self::Fin(_) | Self::Inf(_) | Self::Boolean(_) => None,
+        }
+    }
+}
+
+impl Fixity for LabelExpr {
+    fn precedence(&self) -> Option<Precedence> {
+        match self {
+            Self::And(..) => Some(Precedence::And),
+            Self::Or(..) => Some(Precedence::Or),
+            Self::Ap(_) | Self::Alias(_) | Self::Not(_) | Self::Boolean(_) => None,
+        }
+    }
+}
+
+/// Writes `left <op> right`, parenthesizing a child only when omitting the parens would change
+/// its meaning: the left child needs them when its own precedence is strictly lower than `prec`
+/// (it would otherwise bind looser than the parent expects), and the right child needs them
+/// whenever its precedence is not strictly higher, since a same-precedence right child would
+/// silently re-associate under this operator's left-associativity.
+fn fmt_left_assoc<T: Display + Fixity>(
+    f: &mut std::fmt::Formatter<'_>,
+    prec: Precedence,
+    op: &str,
+    left: &T,
+    right: &T,
+) -> std::fmt::Result {
+    if left.precedence().is_some_and(|p| p < prec) {
+        write!(f, "({left})")?;
+    } else {
+        write!(f, "{left}")?;
+    }
+    write!(f, " {op} ")?;
+    if right.precedence().is_some_and(|p| p <= prec) {
+        write!(f, "({right})")?;
+    } else {
+        write!(f, "{right}")?;
+    }
+    Ok(())
+}
+
+/// Writes `!inner`, parenthesizing `inner` only if it binds looser than unary negation (i.e. is
+/// an `And`/`Or` node); an atom or another `Not` never needs parens here.
+fn fmt_not<T: Display + Fixity>(f: &mut std::fmt::Formatter<'_>, inner: &T) -> std::fmt::Result {
+    write!(f, "!")?;
+    if inner.precedence().is_some_and(|p| p < Precedence::Not) {
+        write!(f, "({inner})")
+    } else {
+        write!(f, "{inner}")
+    }
+}
+
+/// Writes a single `\n`-separated item (a header line, `--BODY--`/`--END--`, or a state and its
+/// edges) through `scratch` rather than allocating a fresh `String` per item: `scratch` is
+/// cleared and reused on every call.
+fn write_item<W: std::io::Write>(
+    w: &mut W,
+    scratch: &mut String,
+    first: &mut bool,
+    item: &dyn Display,
+) -> std::io::Result<()> {
+    if *first {
+        *first = false;
+    } else {
+        w.write_all(b"\n")?;
+    }
+    scratch.clear();
+    write!(scratch, "{item}").expect("writing to a String never fails");
+    w.write_all(scratch.as_bytes())
+}
+
+/// Streams `aut` to `w` in the HOA text format, the same way [`to_hoa`] does, but without ever
+/// building the whole document as one `String`: each header line, `--BODY--`/`--END--`, and
+/// state (with its edges) is formatted into a single reused scratch buffer and written out
+/// immediately, so memory use stays bounded regardless of how many states or transitions `aut`
+/// has.
+pub fn write_hoa<W: std::io::Write>(aut: &HoaRepresentation, w: &mut W) -> std::io::Result<()> {
+    let mut scratch = String::new();
+    let mut first = true;
+
+    for header_item in aut.header().into_iter() {
+        write_item(w, &mut scratch, &mut first, &header_item)?;
+    }
+    write_item(w, &mut scratch, &mut first, &"--BODY--")?;
+    for state in aut.body().into_iter() {
+        write_item(w, &mut scratch, &mut first, &state)?;
+    }
+    write_item(w, &mut scratch, &mut first, &"--END--")?;
+    Ok(())
+}
+
+/// Serializes `aut` to the HOA text format. A thin wrapper around [`write_hoa`] for callers who
+/// just want the whole document as a `String`.
 pub fn to_hoa(aut: &HoaRepresentation) -> String {
-    aut.header()
-        .into_iter()
-        .map(|header_item| header_item.to_string())
-        .chain(std::iter::once("--BODY--".to_string()))
-        .chain(aut.body().into_iter().map(|state| state.to_string()))
-        .chain(std::iter::once("--END--".to_string()))
-        .join("\n")
+    let mut buf = Vec::new();
+    write_hoa(aut, &mut buf).expect("writing into a Vec<u8> never fails");
+    String::from_utf8(buf).expect("HOA output is always valid UTF-8")
 }
 
 impl Display for HeaderItem {
@@ -129,13 +247,34 @@ impl Display for AcceptanceCondition {
         match self {
             Self::Fin(id) => write!(f, "Fin({})", id),
             Self::Inf(id) => write!(f, "Inf({})", id),
-            Self::And(left, right) => write!(f, "({} & {})", left, right),
-            Self::Or(left, right) => write!(f, "({} | {})", left, right),
+            Self::And(left, right) => {
+                fmt_left_assoc(f, Precedence::And, "&", left.as_ref(), right.as_ref())
+            }
+            Self::Or(left, right) => {
+                fmt_left_assoc(f, Precedence::Or, "|", left.as_ref(), right.as_ref())
+            }
             Self::Boolean(val) => write!(f, "{}", val),
         }
     }
 }
 
+impl Display for LabelExpr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Ap(id) => write!(f, "{}", id),
+            Self::Alias(name) => write!(f, "{}", name),
+            Self::Not(inner) => fmt_not(f, inner.as_ref()),
+            Self::And(left, right) => {
+                fmt_left_assoc(f, Precedence::And, "&", left.as_ref(), right.as_ref())
+            }
+            Self::Or(left, right) => {
+                fmt_left_assoc(f, Precedence::Or, "|", left.as_ref(), right.as_ref())
+            }
+            Self::Boolean(value) => write!(f, "{}", if *value { "t" } else { "f" }),
+        }
+    }
+}
+
 impl Display for AliasName {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "@{}", self.0)
@@ -150,7 +289,7 @@ impl Display for StateConjunction {
 
 impl Display for Label {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "[{:?}]", self.0)
+        write!(f, "[{}]", self.0)
     }
 }
 
@@ -182,3 +321,49 @@ impl Display for State {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::AcceptanceCondition;
+    use crate::label::LabelExpr;
+
+    #[test]
+    fn acceptance_condition_omits_redundant_parens() {
+        let cond = AcceptanceCondition::id_inf(0).or(AcceptanceCondition::id_fin(1)
+            .and(AcceptanceCondition::id_inf(2)));
+        assert_eq!(cond.to_string(), "Inf(0) | Fin(1) & Inf(2)");
+
+        let cond = AcceptanceCondition::id_inf(0)
+            .or(AcceptanceCondition::id_inf(1))
+            .or(AcceptanceCondition::id_inf(2));
+        assert_eq!(cond.to_string(), "Inf(0) | Inf(1) | Inf(2)");
+    }
+
+    #[test]
+    fn acceptance_condition_parenthesizes_right_leaning_same_precedence() {
+        let cond = AcceptanceCondition::id_inf(0)
+            .or(AcceptanceCondition::id_inf(1).or(AcceptanceCondition::id_inf(2)));
+        assert_eq!(cond.to_string(), "Inf(0) | (Inf(1) | Inf(2))");
+    }
+
+    #[test]
+    fn label_expr_omits_redundant_parens() {
+        let expr = LabelExpr::Or(
+            Box::new(LabelExpr::And(
+                Box::new(LabelExpr::Ap(0)),
+                Box::new(LabelExpr::Not(Box::new(LabelExpr::Ap(1)))),
+            )),
+            Box::new(LabelExpr::Ap(2)),
+        );
+        assert_eq!(expr.to_string(), "0 & !1 | 2");
+    }
+
+    #[test]
+    fn label_expr_parenthesizes_negated_compound() {
+        let expr = LabelExpr::Not(Box::new(LabelExpr::And(
+            Box::new(LabelExpr::Ap(0)),
+            Box::new(LabelExpr::Ap(1)),
+        )));
+        assert_eq!(expr.to_string(), "!(0 & 1)");
+    }
+}
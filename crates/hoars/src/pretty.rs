@@ -0,0 +1,352 @@
+//! A width-aware, line-wrapping alternative to [`crate::output::to_hoa`], modeled on the classic
+//! box/break pretty-printer: a [`Doc::Group`] is laid out flat on one line if it fits within the
+//! configured width budget, and is broken onto indented lines only when it doesn't. `to_hoa` (the
+//! existing single-line-per-record format) is unaffected -- this module only adds an overlapping
+//! entry point, [`to_hoa_pretty`], that serializes the same semantic HOA document.
+
+use itertools::Itertools;
+
+use crate::label::LabelExpr;
+use crate::output::{Fixity, Precedence};
+use crate::{AcceptanceCondition, Edge, HeaderItem, HoaRepresentation, State};
+
+/// Settings for [`to_hoa_pretty`]. `max_width` is the column budget a [`Doc::Group`] tries to
+/// stay under before it resorts to breaking; `indent` is how many extra spaces each level of
+/// breaking (a wrapped acceptance condition, or a state's edge list) adds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PrettyConfig {
+    pub max_width: usize,
+    pub indent: usize,
+}
+
+impl Default for PrettyConfig {
+    fn default() -> Self {
+        Self {
+            max_width: 80,
+            indent: 2,
+        }
+    }
+}
+
+/// A tiny intermediate document tree. A [`Doc::Group`] is the unit of breaking decisions: it is
+/// rendered flat (every [`Doc::Break`] becomes a single space) if it fits in the remaining line
+/// budget, and otherwise broken according to its [`BreakMode`]. `Consistent` breaks every
+/// [`Doc::Break`] it directly contains once it breaks at all; `Inconsistent` fills as many
+/// children per line as fit, breaking only where it actually has to.
+#[derive(Debug, Clone)]
+enum Doc {
+    Text(String),
+    Break,
+    Group(BreakMode, Vec<Doc>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BreakMode {
+    Consistent,
+    Inconsistent,
+}
+
+impl Doc {
+    fn text(s: impl Into<String>) -> Self {
+        Doc::Text(s.into())
+    }
+
+    fn flat_width(&self) -> usize {
+        match self {
+            Doc::Text(s) => s.chars().count(),
+            Doc::Break => 1,
+            Doc::Group(_, docs) => docs.iter().map(Doc::flat_width).sum(),
+        }
+    }
+}
+
+/// Lays a [`Doc`] tree out into a string, tracking the current column and indentation level so
+/// nested [`Doc::Group`]s can each independently decide whether they still fit.
+struct Printer<'c> {
+    config: &'c PrettyConfig,
+    out: String,
+    col: usize,
+    indent: usize,
+}
+
+impl<'c> Printer<'c> {
+    fn new(config: &'c PrettyConfig, start_col: usize) -> Self {
+        Self {
+            config,
+            out: String::new(),
+            col: start_col,
+            indent: config.indent,
+        }
+    }
+
+    fn text(&mut self, s: &str) {
+        self.out.push_str(s);
+        self.col += s.chars().count();
+    }
+
+    fn newline(&mut self) {
+        self.out.push('\n');
+        self.out.push_str(&" ".repeat(self.indent));
+        self.col = self.indent;
+    }
+
+    fn render_flat(&mut self, doc: &Doc) {
+        match doc {
+            Doc::Text(s) => self.text(s),
+            Doc::Break => self.text(" "),
+            Doc::Group(_, docs) => docs.iter().for_each(|d| self.render_flat(d)),
+        }
+    }
+
+    fn render(&mut self, doc: &Doc) {
+        match doc {
+            Doc::Text(s) => self.text(s),
+            Doc::Break => self.text(" "),
+            Doc::Group(mode, docs) => self.render_group(*mode, docs),
+        }
+    }
+
+    fn render_group(&mut self, mode: BreakMode, docs: &[Doc]) {
+        let flat_width: usize = docs.iter().map(Doc::flat_width).sum();
+        if self.col + flat_width <= self.config.max_width {
+            docs.iter().for_each(|d| self.render_flat(d));
+            return;
+        }
+        match mode {
+            BreakMode::Consistent => {
+                for d in docs {
+                    match d {
+                        Doc::Break => self.newline(),
+                        other => self.render(other),
+                    }
+                }
+            }
+            BreakMode::Inconsistent => {
+                for (i, d) in docs.iter().enumerate() {
+                    match d {
+                        Doc::Break => {
+                            let next_width = docs.get(i + 1).map(Doc::flat_width).unwrap_or(0);
+                            if self.col + 1 + next_width > self.config.max_width {
+                                self.newline();
+                            } else {
+                                self.text(" ");
+                            }
+                        }
+                        other => self.render(other),
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn render_doc_at(doc: &Doc, config: &PrettyConfig, start_col: usize) -> String {
+    let mut printer = Printer::new(config, start_col);
+    printer.render(doc);
+    printer.out
+}
+
+/// Builds the [`Doc`] for an acceptance condition, breaking consistently at the top-level
+/// operator of every `And`/`Or` node and parenthesizing a child exactly when
+/// [`crate::output::Fixity`]-based minimal parenthesization would: this is the same rule
+/// `Display for AcceptanceCondition` uses, just emitting a breakable [`Doc`] instead of writing
+/// straight into a `Formatter`.
+fn acceptance_doc(cond: &AcceptanceCondition) -> Doc {
+    fn child_doc(child: &AcceptanceCondition, parent_prec: Precedence, is_right: bool) -> Doc {
+        let needs_parens = match child.precedence() {
+            Some(p) if p < parent_prec => true,
+            Some(p) if p == parent_prec && is_right => true,
+            _ => false,
+        };
+        let doc = acceptance_doc(child);
+        if needs_parens {
+            Doc::Group(
+                BreakMode::Consistent,
+                vec![Doc::text("("), doc, Doc::text(")")],
+            )
+        } else {
+            doc
+        }
+    }
+
+    match cond {
+        AcceptanceCondition::Fin(id) => Doc::text(format!("Fin({id})")),
+        AcceptanceCondition::Inf(id) => Doc::text(format!("Inf({id})")),
+        AcceptanceCondition::Boolean(val) => Doc::text(val.to_string()),
+        AcceptanceCondition::And(left, right) => Doc::Group(
+            BreakMode::Consistent,
+            vec![
+                child_doc(left, Precedence::And, false),
+                Doc::text(" &"),
+                Doc::Break,
+                child_doc(right, Precedence::And, true),
+            ],
+        ),
+        AcceptanceCondition::Or(left, right) => Doc::Group(
+            BreakMode::Consistent,
+            vec![
+                child_doc(left, Precedence::Or, false),
+                Doc::text(" |"),
+                Doc::Break,
+                child_doc(right, Precedence::Or, true),
+            ],
+        ),
+    }
+}
+
+/// Builds the [`Doc`] for a transition label expression, using the same parenthesization rule as
+/// `Display for LabelExpr`. Unlike [`acceptance_doc`], the binary operators break
+/// [`BreakMode::Inconsistent`]ly: a label's atoms are just bare AP indices or aliases, so packing
+/// as many as fit per line (fill layout) reads better than forcing every single `&`/`|` in a long
+/// explicit-label conjunction onto its own line.
+fn label_expr_doc(expr: &LabelExpr) -> Doc {
+    fn child_doc(child: &LabelExpr, parent_prec: Precedence, is_right: bool) -> Doc {
+        let needs_parens = match child.precedence() {
+            Some(p) if p < parent_prec => true,
+            Some(p) if p == parent_prec && is_right => true,
+            _ => false,
+        };
+        let doc = label_expr_doc(child);
+        if needs_parens {
+            Doc::Group(
+                BreakMode::Inconsistent,
+                vec![Doc::text("("), doc, Doc::text(")")],
+            )
+        } else {
+            doc
+        }
+    }
+
+    match expr {
+        LabelExpr::Ap(id) => Doc::text(id.to_string()),
+        LabelExpr::Alias(name) => Doc::text(name.to_string()),
+        LabelExpr::Boolean(value) => Doc::text(if *value { "t" } else { "f" }),
+        LabelExpr::Not(inner) => {
+            let needs_parens = matches!(inner.precedence(), Some(p) if p < Precedence::Not);
+            let doc = label_expr_doc(inner);
+            let doc = if needs_parens {
+                Doc::Group(
+                    BreakMode::Inconsistent,
+                    vec![Doc::text("("), doc, Doc::text(")")],
+                )
+            } else {
+                doc
+            };
+            Doc::Group(BreakMode::Inconsistent, vec![Doc::text("!"), doc])
+        }
+        LabelExpr::And(left, right) => Doc::Group(
+            BreakMode::Inconsistent,
+            vec![
+                child_doc(left, Precedence::And, false),
+                Doc::text(" &"),
+                Doc::Break,
+                child_doc(right, Precedence::And, true),
+            ],
+        ),
+        LabelExpr::Or(left, right) => Doc::Group(
+            BreakMode::Inconsistent,
+            vec![
+                child_doc(left, Precedence::Or, false),
+                Doc::text(" |"),
+                Doc::Break,
+                child_doc(right, Precedence::Or, true),
+            ],
+        ),
+    }
+}
+
+fn render_header_item_pretty(item: &HeaderItem, config: &PrettyConfig) -> String {
+    match item {
+        HeaderItem::Acceptance(number_sets, condition) => {
+            let prefix = format!("Acceptance: {number_sets} ");
+            let col = prefix.chars().count();
+            format!("{prefix}{}", render_doc_at(&acceptance_doc(condition), config, col))
+        }
+        other => other.to_string(),
+    }
+}
+
+/// Renders a state and its edge list the way [`crate::output::to_hoa`] does, except every edge is
+/// indented one [`PrettyConfig::indent`] level under the `State:` line instead of starting at
+/// column zero, and each edge's own label wraps if it's too wide for that indented line.
+fn render_state_pretty(state: &State, config: &PrettyConfig) -> String {
+    let mut out = String::new();
+    if let Some(acc) = &state.1 {
+        out.push_str(&format!("State: {} \"{}\"\n", state.0, acc));
+    } else {
+        out.push_str(&format!("State: {}\n", state.0));
+    }
+    let pad = " ".repeat(config.indent);
+    for edge in &state.2 {
+        out.push_str(&pad);
+        out.push_str(&render_edge_pretty(edge, config, config.indent));
+        out.push('\n');
+    }
+    out
+}
+
+fn render_edge_pretty(edge: &Edge, config: &PrettyConfig, base_col: usize) -> String {
+    let label_doc = Doc::Group(
+        BreakMode::Inconsistent,
+        vec![Doc::text("["), label_expr_doc(&(edge.0).0), Doc::text("]")],
+    );
+    let label = render_doc_at(&label_doc, config, base_col);
+    format!("{label} {} {}", edge.1, edge.2)
+}
+
+/// Serializes `aut` to the HOA text format like [`crate::output::to_hoa`], but lays acceptance
+/// conditions out with minimal, width-aware line wrapping and indents each state's edges one
+/// level, per `config`.
+pub fn to_hoa_pretty(aut: &HoaRepresentation, config: &PrettyConfig) -> String {
+    aut.header()
+        .into_iter()
+        .map(|header_item| render_header_item_pretty(&header_item, config))
+        .chain(std::iter::once("--BODY--".to_string()))
+        .chain(
+            aut.body()
+                .into_iter()
+                .map(|state| render_state_pretty(&state, config)),
+        )
+        .chain(std::iter::once("--END--".to_string()))
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::HoaBool;
+
+    #[test]
+    fn short_acceptance_condition_stays_on_one_line() {
+        let config = PrettyConfig::default();
+        let cond = AcceptanceCondition::id_inf(0).or(AcceptanceCondition::id_fin(1));
+        let item = HeaderItem::Acceptance(2, cond);
+        assert_eq!(
+            render_header_item_pretty(&item, &config),
+            "Acceptance: 2 Inf(0) | Fin(1)"
+        );
+    }
+
+    #[test]
+    fn long_acceptance_condition_wraps_at_top_level_operator() {
+        let config = PrettyConfig {
+            max_width: 30,
+            indent: 2,
+        };
+        let cond = AcceptanceCondition::id_fin(0)
+            .and(AcceptanceCondition::id_inf(1))
+            .or(AcceptanceCondition::id_fin(2).and(AcceptanceCondition::id_inf(3)));
+        let item = HeaderItem::Acceptance(4, cond);
+        assert_eq!(
+            render_header_item_pretty(&item, &config),
+            "Acceptance: 4 Fin(0) & Inf(1) |\n  Fin(2) & Inf(3)"
+        );
+    }
+
+    #[test]
+    fn boolean_constant_is_an_atom() {
+        let config = PrettyConfig::default();
+        let item = HeaderItem::Acceptance(0, AcceptanceCondition::Boolean(HoaBool(true)));
+        assert_eq!(render_header_item_pretty(&item, &config), "Acceptance: 0 t");
+    }
+}
@@ -0,0 +1,608 @@
+//! A generic, serde-friendly value model for HOA documents, sitting alongside
+//! [`crate::formatter`] as a second consumer of [`crate::document::ParsedDocument`]: where
+//! `formatter` re-serializes a document back to HOA text, this module maps it into [`Value`] --
+//! a small tree of atoms (`Bool`/`Int`/`Str`/`Symbol`) and compounds (`Record`/`Sequence`/
+//! `Dictionary`) -- so it can round-trip through `serde` into JSON, CBOR, or any other format
+//! `serde` supports, without HOA's own text grammar in the way.
+//!
+//! [`AcceptanceCondition`] and [`LabelExpr`] become tagged [`Value::Record`]s (`Fin`/`Inf`/
+//! `And`/`Or`/`Bool`, and `Ap`/`Alias`/`Not`/`And`/`Or`/`Bool` respectively); a state or edge
+//! becomes a [`Value::Dictionary`] keyed by field name, with absent optional fields (a state's
+//! implicit label, an edge's acceptance signature) simply omitted rather than written as some
+//! null placeholder. The invariant this module exists for: `from_value(&to_value(&doc)) ==
+//! Ok(doc)`, and feeding that round-tripped document through [`crate::formatter::format_hoa`]
+//! reproduces the same HOA text as the original.
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::document::{ParsedBody, ParsedDocument, ParsedEdge, ParsedHeader, ParsedState};
+use crate::label::LabelExpr;
+use crate::{
+    AcceptanceAtom, AcceptanceCondition, AcceptanceName, AcceptanceSignature, AliasName, HoaBool,
+    Id, Property, StateConjunction,
+};
+
+/// A small, self-describing value tree: atoms and compounds, serializable through `serde` into
+/// any format it supports. `Record` is a tagged compound (the tag is the first field, e.g. the
+/// enum variant name being modeled); `Dictionary` is untagged, for plain field/value structures
+/// like a state or an edge.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Value {
+    Bool(bool),
+    Int(i64),
+    Str(String),
+    Symbol(String),
+    Record(String, Vec<(String, Value)>),
+    Sequence(Vec<Value>),
+    Dictionary(Vec<(String, Value)>),
+}
+
+/// Why [`from_value`] failed to reconstruct a [`ParsedDocument`] from a [`Value`]: the value
+/// came from somewhere other than a prior [`to_value`] call, or was hand-edited into a shape
+/// this module doesn't recognize.
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum ValueError {
+    #[error("expected a {expected} value, found {found:?}")]
+    WrongShape { expected: &'static str, found: Value },
+    #[error("missing field \"{0}\"")]
+    MissingField(&'static str),
+    #[error("unknown record tag \"{0}\"")]
+    UnknownTag(String),
+}
+
+fn record(tag: &str, fields: Vec<(&str, Value)>) -> Value {
+    Value::Record(
+        tag.to_string(),
+        fields.into_iter().map(|(k, v)| (k.to_string(), v)).collect(),
+    )
+}
+
+fn dict(fields: Vec<(&str, Value)>) -> Value {
+    Value::Dictionary(fields.into_iter().map(|(k, v)| (k.to_string(), v)).collect())
+}
+
+fn field<'a>(fields: &'a [(String, Value)], name: &'static str) -> Result<&'a Value, ValueError> {
+    fields
+        .iter()
+        .find(|(key, _)| key == name)
+        .map(|(_, value)| value)
+        .ok_or(ValueError::MissingField(name))
+}
+
+fn optional_field<'a>(fields: &'a [(String, Value)], name: &str) -> Option<&'a Value> {
+    fields.iter().find(|(key, _)| key == name).map(|(_, value)| value)
+}
+
+fn as_record(value: &Value) -> Result<(&str, &[(String, Value)]), ValueError> {
+    match value {
+        Value::Record(tag, fields) => Ok((tag.as_str(), fields.as_slice())),
+        other => Err(ValueError::WrongShape {
+            expected: "record",
+            found: other.clone(),
+        }),
+    }
+}
+
+fn as_dictionary(value: &Value) -> Result<&[(String, Value)], ValueError> {
+    match value {
+        Value::Dictionary(fields) => Ok(fields.as_slice()),
+        other => Err(ValueError::WrongShape {
+            expected: "dictionary",
+            found: other.clone(),
+        }),
+    }
+}
+
+fn as_sequence(value: &Value) -> Result<&[Value], ValueError> {
+    match value {
+        Value::Sequence(items) => Ok(items.as_slice()),
+        other => Err(ValueError::WrongShape {
+            expected: "sequence",
+            found: other.clone(),
+        }),
+    }
+}
+
+fn as_int(value: &Value) -> Result<i64, ValueError> {
+    match value {
+        Value::Int(n) => Ok(*n),
+        other => Err(ValueError::WrongShape {
+            expected: "int",
+            found: other.clone(),
+        }),
+    }
+}
+
+fn as_bool(value: &Value) -> Result<bool, ValueError> {
+    match value {
+        Value::Bool(b) => Ok(*b),
+        other => Err(ValueError::WrongShape {
+            expected: "bool",
+            found: other.clone(),
+        }),
+    }
+}
+
+fn as_str(value: &Value) -> Result<&str, ValueError> {
+    match value {
+        Value::Str(s) | Value::Symbol(s) => Ok(s.as_str()),
+        other => Err(ValueError::WrongShape {
+            expected: "str",
+            found: other.clone(),
+        }),
+    }
+}
+
+fn acceptance_atom_to_value(atom: &AcceptanceAtom) -> Value {
+    match atom {
+        AcceptanceAtom::Positive(id) => record("Positive", vec![("id", Value::Int(*id as i64))]),
+        AcceptanceAtom::Negative(id) => record("Negative", vec![("id", Value::Int(*id as i64))]),
+    }
+}
+
+fn acceptance_atom_from_value(value: &Value) -> Result<AcceptanceAtom, ValueError> {
+    let (tag, fields) = as_record(value)?;
+    let id = as_int(field(fields, "id")?)? as Id;
+    match tag {
+        "Positive" => Ok(AcceptanceAtom::Positive(id)),
+        "Negative" => Ok(AcceptanceAtom::Negative(id)),
+        other => Err(ValueError::UnknownTag(other.to_string())),
+    }
+}
+
+fn acceptance_condition_to_value(condition: &AcceptanceCondition) -> Value {
+    match condition {
+        AcceptanceCondition::Fin(atom) => {
+            record("Fin", vec![("atom", acceptance_atom_to_value(atom))])
+        }
+        AcceptanceCondition::Inf(atom) => {
+            record("Inf", vec![("atom", acceptance_atom_to_value(atom))])
+        }
+        AcceptanceCondition::And(left, right) => record(
+            "And",
+            vec![
+                ("left", acceptance_condition_to_value(left)),
+                ("right", acceptance_condition_to_value(right)),
+            ],
+        ),
+        AcceptanceCondition::Or(left, right) => record(
+            "Or",
+            vec![
+                ("left", acceptance_condition_to_value(left)),
+                ("right", acceptance_condition_to_value(right)),
+            ],
+        ),
+        AcceptanceCondition::Boolean(value) => {
+            record("Bool", vec![("value", Value::Bool(value.0))])
+        }
+    }
+}
+
+fn acceptance_condition_from_value(value: &Value) -> Result<AcceptanceCondition, ValueError> {
+    let (tag, fields) = as_record(value)?;
+    match tag {
+        "Fin" => Ok(AcceptanceCondition::Fin(acceptance_atom_from_value(
+            field(fields, "atom")?,
+        )?)),
+        "Inf" => Ok(AcceptanceCondition::Inf(acceptance_atom_from_value(
+            field(fields, "atom")?,
+        )?)),
+        "And" => Ok(AcceptanceCondition::And(
+            Box::new(acceptance_condition_from_value(field(fields, "left")?)?),
+            Box::new(acceptance_condition_from_value(field(fields, "right")?)?),
+        )),
+        "Or" => Ok(AcceptanceCondition::Or(
+            Box::new(acceptance_condition_from_value(field(fields, "left")?)?),
+            Box::new(acceptance_condition_from_value(field(fields, "right")?)?),
+        )),
+        "Bool" => Ok(AcceptanceCondition::Boolean(HoaBool(as_bool(
+            field(fields, "value")?,
+        )?))),
+        other => Err(ValueError::UnknownTag(other.to_string())),
+    }
+}
+
+fn label_expr_to_value(expr: &LabelExpr) -> Value {
+    match expr {
+        LabelExpr::Ap(id) => record("Ap", vec![("id", Value::Int(*id as i64))]),
+        LabelExpr::Alias(name) => record("Alias", vec![("name", Value::Str(name.0.clone()))]),
+        LabelExpr::Not(inner) => record("Not", vec![("inner", label_expr_to_value(inner))]),
+        LabelExpr::And(left, right) => record(
+            "And",
+            vec![
+                ("left", label_expr_to_value(left)),
+                ("right", label_expr_to_value(right)),
+            ],
+        ),
+        LabelExpr::Or(left, right) => record(
+            "Or",
+            vec![
+                ("left", label_expr_to_value(left)),
+                ("right", label_expr_to_value(right)),
+            ],
+        ),
+        LabelExpr::Boolean(value) => record("Bool", vec![("value", Value::Bool(*value))]),
+    }
+}
+
+fn label_expr_from_value(value: &Value) -> Result<LabelExpr, ValueError> {
+    let (tag, fields) = as_record(value)?;
+    match tag {
+        "Ap" => Ok(LabelExpr::Ap(as_int(field(fields, "id")?)? as Id)),
+        "Alias" => Ok(LabelExpr::Alias(AliasName(
+            as_str(field(fields, "name")?)?.to_string(),
+        ))),
+        "Not" => Ok(LabelExpr::Not(Box::new(label_expr_from_value(field(
+            fields, "inner",
+        )?)?))),
+        "And" => Ok(LabelExpr::And(
+            Box::new(label_expr_from_value(field(fields, "left")?)?),
+            Box::new(label_expr_from_value(field(fields, "right")?)?),
+        )),
+        "Or" => Ok(LabelExpr::Or(
+            Box::new(label_expr_from_value(field(fields, "left")?)?),
+            Box::new(label_expr_from_value(field(fields, "right")?)?),
+        )),
+        "Bool" => Ok(LabelExpr::Boolean(as_bool(field(fields, "value")?)?)),
+        other => Err(ValueError::UnknownTag(other.to_string())),
+    }
+}
+
+fn id_sequence_to_value(ids: &[Id]) -> Value {
+    Value::Sequence(ids.iter().map(|id| Value::Int(*id as i64)).collect())
+}
+
+fn id_sequence_from_value(value: &Value) -> Result<Vec<Id>, ValueError> {
+    as_sequence(value)?
+        .iter()
+        .map(|item| Ok(as_int(item)? as Id))
+        .collect()
+}
+
+fn state_conjunction_to_value(conjunction: &StateConjunction) -> Value {
+    id_sequence_to_value(&conjunction.0)
+}
+
+fn state_conjunction_from_value(value: &Value) -> Result<StateConjunction, ValueError> {
+    Ok(StateConjunction(id_sequence_from_value(value)?))
+}
+
+fn acceptance_signature_to_value(signature: &AcceptanceSignature) -> Value {
+    id_sequence_to_value(&signature.0)
+}
+
+fn acceptance_signature_from_value(value: &Value) -> Result<AcceptanceSignature, ValueError> {
+    Ok(AcceptanceSignature(id_sequence_from_value(value)?))
+}
+
+fn edge_to_value(edge: &ParsedEdge) -> Value {
+    let mut fields = vec![("targets", state_conjunction_to_value(&edge.targets))];
+    if let Some(label) = &edge.label {
+        fields.push(("label", label_expr_to_value(label)));
+    }
+    if let Some(acceptance) = &edge.acceptance {
+        fields.push(("acceptance", acceptance_signature_to_value(acceptance)));
+    }
+    dict(fields)
+}
+
+fn edge_from_value(value: &Value) -> Result<ParsedEdge, ValueError> {
+    let fields = as_dictionary(value)?;
+    Ok(ParsedEdge {
+        label: optional_field(fields, "label")
+            .map(label_expr_from_value)
+            .transpose()?,
+        targets: state_conjunction_from_value(field(fields, "targets")?)?,
+        acceptance: optional_field(fields, "acceptance")
+            .map(acceptance_signature_from_value)
+            .transpose()?,
+    })
+}
+
+fn state_to_value(state: &ParsedState) -> Value {
+    let mut fields = vec![("index", Value::Int(state.index as i64))];
+    if let Some(label) = &state.label {
+        fields.push(("label", label_expr_to_value(label)));
+    }
+    if let Some(name) = &state.name {
+        fields.push(("name", Value::Str(name.clone())));
+    }
+    if let Some(acceptance) = &state.acceptance {
+        fields.push(("acceptance", acceptance_signature_to_value(acceptance)));
+    }
+    fields.push((
+        "edges",
+        Value::Sequence(state.edges.iter().map(edge_to_value).collect()),
+    ));
+    dict(fields)
+}
+
+fn state_from_value(value: &Value) -> Result<ParsedState, ValueError> {
+    let fields = as_dictionary(value)?;
+    Ok(ParsedState {
+        label: optional_field(fields, "label")
+            .map(label_expr_from_value)
+            .transpose()?,
+        index: as_int(field(fields, "index")?)? as Id,
+        name: optional_field(fields, "name")
+            .map(|value| as_str(value).map(str::to_string))
+            .transpose()?,
+        acceptance: optional_field(fields, "acceptance")
+            .map(acceptance_signature_from_value)
+            .transpose()?,
+        edges: as_sequence(field(fields, "edges")?)?
+            .iter()
+            .map(edge_from_value)
+            .collect::<Result<_, _>>()?,
+    })
+}
+
+/// Maps a [`crate::lexer::Token`] -- as kept verbatim in [`ParsedHeader::Other`] for headers
+/// this crate doesn't otherwise model -- into [`Value`], preserving its exact variant rather
+/// than flattening it through [`Display`](std::fmt::Display) the way [`crate::formatter`] does,
+/// since [`from_value`] needs to reconstruct the original `Token`, not just its printed form.
+fn token_to_value(token: &crate::lexer::Token) -> Value {
+    use crate::lexer::Token;
+    match token {
+        Token::Bool(value) => record("Bool", vec![("value", Value::Bool(*value))]),
+        Token::Int(value) => record("Int", vec![("value", Value::Str(value.clone()))]),
+        Token::Text(value) => record("Text", vec![("value", Value::Str(value.clone()))]),
+        Token::Identifier(value) => record("Identifier", vec![("value", Value::Str(value.clone()))]),
+        Token::Alias(value) => record("Alias", vec![("value", Value::Str(value.clone()))]),
+        Token::Header(value) => record("Header", vec![("value", Value::Str(value.clone()))]),
+        Token::Op(c) => record("Op", vec![("value", Value::Str(c.to_string()))]),
+        Token::Paren(c) => record("Paren", vec![("value", Value::Str(c.to_string()))]),
+        Token::BodyStart => record("BodyStart", vec![]),
+        Token::BodyEnd => record("BodyEnd", vec![]),
+        Token::Abort => record("Abort", vec![]),
+        Token::Fin => record("Fin", vec![]),
+        Token::Inf => record("Inf", vec![]),
+    }
+}
+
+fn token_from_value(value: &Value) -> Result<crate::lexer::Token, ValueError> {
+    use crate::lexer::Token;
+    let (tag, fields) = as_record(value)?;
+    let char_field = |fields: &[(String, Value)]| -> Result<char, ValueError> {
+        as_str(field(fields, "value")?)?
+            .chars()
+            .next()
+            .ok_or(ValueError::MissingField("value"))
+    };
+    match tag {
+        "Bool" => Ok(Token::Bool(as_bool(field(fields, "value")?)?)),
+        "Int" => Ok(Token::Int(as_str(field(fields, "value")?)?.to_string())),
+        "Text" => Ok(Token::Text(as_str(field(fields, "value")?)?.to_string())),
+        "Identifier" => Ok(Token::Identifier(as_str(field(fields, "value")?)?.to_string())),
+        "Alias" => Ok(Token::Alias(as_str(field(fields, "value")?)?.to_string())),
+        "Header" => Ok(Token::Header(as_str(field(fields, "value")?)?.to_string())),
+        "Op" => Ok(Token::Op(char_field(fields)?)),
+        "Paren" => Ok(Token::Paren(char_field(fields)?)),
+        "BodyStart" => Ok(Token::BodyStart),
+        "BodyEnd" => Ok(Token::BodyEnd),
+        "Abort" => Ok(Token::Abort),
+        "Fin" => Ok(Token::Fin),
+        "Inf" => Ok(Token::Inf),
+        other => Err(ValueError::UnknownTag(other.to_string())),
+    }
+}
+
+fn header_to_value(header: &ParsedHeader) -> Value {
+    match header {
+        ParsedHeader::Hoa(version) => record("Hoa", vec![("version", Value::Str(version.clone()))]),
+        ParsedHeader::States(count) => record("States", vec![("count", Value::Int(*count as i64))]),
+        ParsedHeader::Start(conjunction) => {
+            record("Start", vec![("states", state_conjunction_to_value(conjunction))])
+        }
+        ParsedHeader::AtomicPropositions(aps) => record(
+            "AtomicPropositions",
+            vec![(
+                "names",
+                Value::Sequence(aps.iter().map(|ap| Value::Str(ap.clone())).collect()),
+            )],
+        ),
+        ParsedHeader::Alias(name, expr) => record(
+            "Alias",
+            vec![
+                ("name", Value::Str(name.0.clone())),
+                ("definition", label_expr_to_value(expr)),
+            ],
+        ),
+        ParsedHeader::Acceptance(count, condition) => record(
+            "Acceptance",
+            vec![
+                ("count", Value::Int(*count as i64)),
+                ("condition", acceptance_condition_to_value(condition)),
+            ],
+        ),
+        ParsedHeader::AcceptanceName(name) => record(
+            "AcceptanceName",
+            vec![("name", Value::Symbol(name.to_string()))],
+        ),
+        ParsedHeader::Properties(properties) => record(
+            "Properties",
+            vec![(
+                "names",
+                Value::Sequence(
+                    properties
+                        .iter()
+                        .map(|property| Value::Symbol(property.to_string()))
+                        .collect(),
+                ),
+            )],
+        ),
+        ParsedHeader::Name(name) => record("Name", vec![("value", Value::Str(name.clone()))]),
+        ParsedHeader::Tool(name, version) => record(
+            "Tool",
+            vec![
+                ("name", Value::Str(name.clone())),
+                (
+                    "version",
+                    match version {
+                        Some(version) => Value::Str(version.clone()),
+                        None => Value::Sequence(vec![]),
+                    },
+                ),
+            ],
+        ),
+        ParsedHeader::Other(name, tokens) => record(
+            "Other",
+            vec![
+                ("name", Value::Str(name.clone())),
+                ("tokens", Value::Sequence(tokens.iter().map(token_to_value).collect())),
+            ],
+        ),
+    }
+}
+
+fn header_from_value(value: &Value) -> Result<ParsedHeader, ValueError> {
+    let (tag, fields) = as_record(value)?;
+    match tag {
+        "Hoa" => Ok(ParsedHeader::Hoa(as_str(field(fields, "version")?)?.to_string())),
+        "States" => Ok(ParsedHeader::States(as_int(field(fields, "count")?)? as Id)),
+        "Start" => Ok(ParsedHeader::Start(state_conjunction_from_value(field(
+            fields, "states",
+        )?)?)),
+        "AtomicPropositions" => Ok(ParsedHeader::AtomicPropositions(
+            as_sequence(field(fields, "names")?)?
+                .iter()
+                .map(|item| Ok(as_str(item)?.to_string()))
+                .collect::<Result<_, ValueError>>()?,
+        )),
+        "Alias" => Ok(ParsedHeader::Alias(
+            AliasName(as_str(field(fields, "name")?)?.to_string()),
+            label_expr_from_value(field(fields, "definition")?)?,
+        )),
+        "Acceptance" => Ok(ParsedHeader::Acceptance(
+            as_int(field(fields, "count")?)? as u32,
+            acceptance_condition_from_value(field(fields, "condition")?)?,
+        )),
+        "AcceptanceName" => {
+            let name = as_str(field(fields, "name")?)?.to_string();
+            AcceptanceName::try_from(name.clone())
+                .map(ParsedHeader::AcceptanceName)
+                .map_err(|_| ValueError::UnknownTag(name))
+        }
+        "Properties" => Ok(ParsedHeader::Properties(
+            as_sequence(field(fields, "names")?)?
+                .iter()
+                .filter_map(|item| as_str(item).ok().and_then(|name| Property::try_from(name.to_string()).ok()))
+                .collect(),
+        )),
+        "Name" => Ok(ParsedHeader::Name(as_str(field(fields, "value")?)?.to_string())),
+        "Tool" => {
+            let name = as_str(field(fields, "name")?)?.to_string();
+            let version = match field(fields, "version")? {
+                Value::Sequence(empty) if empty.is_empty() => None,
+                other => Some(as_str(other)?.to_string()),
+            };
+            Ok(ParsedHeader::Tool(name, version))
+        }
+        "Other" => {
+            let name = as_str(field(fields, "name")?)?.to_string();
+            let tokens = as_sequence(field(fields, "tokens")?)?
+                .iter()
+                .map(token_from_value)
+                .collect::<Result<_, ValueError>>()?;
+            Ok(ParsedHeader::Other(name, tokens))
+        }
+        other => Err(ValueError::UnknownTag(other.to_string())),
+    }
+}
+
+/// Maps `document` into the generic [`Value`] model; see the module docs for the invariant this
+/// is meant to uphold together with [`from_value`].
+pub fn to_value(document: &ParsedDocument) -> Value {
+    let aliases = document
+        .aliases
+        .iter()
+        .map(|(name, expr)| (name.0.clone(), label_expr_to_value(expr)))
+        .collect();
+    dict(vec![
+        (
+            "headers",
+            Value::Sequence(document.headers.iter().map(header_to_value).collect()),
+        ),
+        ("aliases", Value::Dictionary(aliases)),
+        (
+            "body",
+            dict(vec![(
+                "states",
+                Value::Sequence(document.body.states.iter().map(state_to_value).collect()),
+            )]),
+        ),
+    ])
+}
+
+/// Reconstructs a [`ParsedDocument`] from a [`Value`] previously produced by [`to_value`].
+/// Fails with [`ValueError`] if `value` doesn't have the shape `to_value` produces -- e.g. it
+/// was hand-written, or came from a different, incompatible version of this module.
+pub fn from_value(value: &Value) -> Result<ParsedDocument, ValueError> {
+    let fields = as_dictionary(value)?;
+    let headers = as_sequence(field(fields, "headers")?)?
+        .iter()
+        .map(header_from_value)
+        .collect::<Result<Vec<_>, _>>()?;
+    let aliases = as_dictionary(field(fields, "aliases")?)?
+        .iter()
+        .map(|(name, expr)| Ok((AliasName(name.clone()), label_expr_from_value(expr)?)))
+        .collect::<Result<HashMap<_, _>, ValueError>>()?;
+    let body_fields = as_dictionary(field(fields, "body")?)?;
+    let states = as_sequence(field(body_fields, "states")?)?
+        .iter()
+        .map(state_from_value)
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(ParsedDocument {
+        headers,
+        aliases,
+        body: ParsedBody { states },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::document::parse_document;
+
+    fn sample() -> &'static str {
+        "HOA: v1\nStates: 2\nStart: 0\nAP: 1 \"a\"\ntool: \"ralf\" \"1.0\"\nAlias: @good 0\nAcceptance: 1 Inf(0)\nproperties: trans-labels deterministic\n--BODY--\nState: 0\n[@good] 0 {0}\n[!@good] 1\nState: 1\n[t] 1\n--END--\n"
+    }
+
+    #[test]
+    fn round_trips_a_parsed_document_through_value() {
+        let document = parse_document(sample()).unwrap();
+        let value = to_value(&document);
+        assert_eq!(from_value(&value).unwrap(), document);
+    }
+
+    #[test]
+    fn round_trips_a_parsed_document_through_json() {
+        let document = parse_document(sample()).unwrap();
+        let value = to_value(&document);
+        let json = serde_json::to_string(&value).unwrap();
+        let decoded: Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(from_value(&decoded).unwrap(), document);
+    }
+
+    #[test]
+    fn formatting_the_round_tripped_document_is_unchanged() {
+        let document = parse_document(sample()).unwrap();
+        let round_tripped = from_value(&to_value(&document)).unwrap();
+        let options = crate::formatter::FormatOptions::default();
+        assert_eq!(
+            crate::formatter::format_hoa(sample(), &options).unwrap(),
+            crate::formatter::render_document(&round_tripped, &options)
+        );
+    }
+
+    #[test]
+    fn reports_unknown_record_tags() {
+        let bogus = record("NotARealTag", vec![]);
+        assert!(matches!(
+            acceptance_condition_from_value(&bogus),
+            Err(ValueError::UnknownTag(_))
+        ));
+    }
+}
@@ -355,3 +355,277 @@ impl<Q: Clone, C: Clone> TSBuilder<Q, C> {
         ts
     }
 }
+
+/// Marker type for [`CheckedTSBuilder`] indicating that determinism of the built
+/// transitions has not (yet) been asserted or validated.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Unasserted;
+
+/// Marker type for [`CheckedTSBuilder`] indicating that determinism has either been
+/// asserted by the caller (via [`CheckedTSBuilder::assert_deterministic`]) or
+/// validated (via [`CheckedTSBuilder::checked_deterministic`]).
+#[derive(Debug, Clone, Copy, Default)]
+pub struct AssertedDeterministic;
+
+/// Marker type for [`CheckedTSBuilder`] indicating that no initial state has been
+/// chosen yet.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoInitial;
+
+/// Marker type for [`CheckedTSBuilder`] indicating that an initial state has been
+/// fixed via [`CheckedTSBuilder::with_initial`].
+#[derive(Debug, Clone, Copy)]
+pub struct HasInitial(usize);
+
+/// Returned by [`CheckedTSBuilder::checked_deterministic`] when the edges added so
+/// far contain two outgoing edges from the same state on the same symbol.
+#[derive(Debug, Clone)]
+pub struct NotDeterministicError {
+    /// The offending source state.
+    pub source: usize,
+    /// The symbol for which more than one outgoing edge was found.
+    pub symbol: char,
+}
+
+/// Marker type for [`CheckedTSBuilder`] indicating that it is not (yet) known whether
+/// every state that will be built has a color.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Uncolored;
+
+/// Marker type for [`CheckedTSBuilder`] indicating that every state is known to have a
+/// color, either because coloring has been asserted by the caller (via
+/// [`CheckedTSBuilder::assert_colors_complete`]) or validated (via
+/// [`CheckedTSBuilder::checked_colors_complete`]).
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Colored;
+
+/// Returned by [`CheckedTSBuilder::checked_colors_complete`] when some state that
+/// would be built from the edges added so far has neither an explicit color nor a
+/// default to fall back on.
+#[derive(Debug, Clone)]
+pub struct MissingColorError {
+    /// A state with no explicit color and no default to fall back on.
+    pub state: usize,
+}
+
+/// A typestate-guarded counterpart of [`TSBuilder`] that turns the panics of
+/// `into_dfa`/`into_dba`/`into_dpa`/`into_mealy`/`into_moore` into compile-time
+/// errors. `Det` tracks whether determinism has been asserted/validated, `Init` tracks
+/// whether an initial state has been fixed and `Colors` tracks whether every state is
+/// known to have a color; the `into_*` conversions are only implemented once
+/// `Det = `[`AssertedDeterministic`], `Init = `[`HasInitial`] and
+/// `Colors = `[`Colored`].
+///
+/// Obtain one via [`TSBuilder::checked`]. Callers who want the old, panicking
+/// behavior can keep using [`TSBuilder`] directly (its methods are sometimes referred
+/// to as the `unchecked` entry point).
+///
+/// # Example
+/// ```
+/// use automata::prelude::*;
+///
+/// let dfa = TSBuilder::default()
+///     .with_state_colors([true, false])
+///     .with_transitions([(0, 'a', Void, 0), (0, 'b', Void, 1), (1, 'a', Void, 1), (1, 'b', Void, 0)])
+///     .checked()
+///     .checked_deterministic()
+///     .expect("transitions are deterministic")
+///     .checked_colors_complete()
+///     .expect("every state has a color")
+///     .with_initial(0)
+///     .into_dfa();
+/// ```
+pub struct CheckedTSBuilder<Q = Void, C = Void, Det = Unasserted, Init = NoInitial, Colors = Uncolored>
+{
+    inner: TSBuilder<Q, C>,
+    init: Init,
+    _det: std::marker::PhantomData<Det>,
+    _colors: std::marker::PhantomData<Colors>,
+}
+
+impl<Q, C> TSBuilder<Q, C> {
+    /// Switches into the typestate-guarded [`CheckedTSBuilder`] API. The colors,
+    /// edges and default color accumulated so far are carried over unchanged.
+    pub fn checked(self) -> CheckedTSBuilder<Q, C, Unasserted, NoInitial, Uncolored> {
+        CheckedTSBuilder {
+            inner: self,
+            init: NoInitial,
+            _det: std::marker::PhantomData,
+            _colors: std::marker::PhantomData,
+        }
+    }
+}
+
+impl<Q: Clone, C: Clone, Det, Init, Colors> CheckedTSBuilder<Q, C, Det, Init, Colors> {
+    /// See [`TSBuilder::default_color`].
+    pub fn default_color(mut self, color: Q) -> Self {
+        self.inner = self.inner.default_color(color);
+        self
+    }
+
+    /// See [`TSBuilder::with_state_colors`].
+    pub fn with_state_colors<I: IntoIterator<Item = Q>>(mut self, iter: I) -> Self {
+        self.inner = self.inner.with_state_colors(iter);
+        self
+    }
+
+    /// See [`TSBuilder::color`].
+    pub fn color(mut self, idx: usize, color: Q) -> Self {
+        self.inner = self.inner.color(idx, color);
+        self
+    }
+
+    /// See [`TSBuilder::with_alphabet_symbols`].
+    pub fn with_alphabet_symbols<I: IntoIterator<Item = char>>(mut self, symbols: I) -> Self {
+        self.inner = self.inner.with_alphabet_symbols(symbols);
+        self
+    }
+
+    /// See [`TSBuilder::with_transitions`].
+    pub fn with_transitions<
+        E: IntoEdgeTuple<LinkedListTransitionSystem<CharAlphabet, Q, C>>,
+        T: IntoIterator<Item = E>,
+    >(
+        mut self,
+        iter: T,
+    ) -> Self {
+        self.inner = self.inner.with_transitions(iter);
+        self
+    }
+
+    /// See [`TSBuilder::with_edges`].
+    pub fn with_edges<
+        E: IntoEdgeTuple<LinkedListTransitionSystem<CharAlphabet, Q, C>>,
+        I: IntoIterator<Item = E>,
+    >(
+        mut self,
+        iter: I,
+    ) -> Self {
+        self.inner = self.inner.with_edges(iter);
+        self
+    }
+
+    /// Asserts, without checking, that the edges added so far are deterministic
+    /// (at most one outgoing edge per state/symbol pair). Prefer
+    /// [`Self::checked_deterministic`] unless this has already been established by
+    /// construction.
+    pub fn assert_deterministic(self) -> CheckedTSBuilder<Q, C, AssertedDeterministic, Init, Colors> {
+        CheckedTSBuilder {
+            inner: self.inner,
+            init: self.init,
+            _det: std::marker::PhantomData,
+            _colors: std::marker::PhantomData,
+        }
+    }
+
+    /// Validates that the edges added so far are deterministic, returning the
+    /// offending `(source, symbol)` pair as an error on the first duplicate found.
+    pub fn checked_deterministic(
+        self,
+    ) -> Result<CheckedTSBuilder<Q, C, AssertedDeterministic, Init, Colors>, NotDeterministicError>
+    {
+        let mut seen: Set<(usize, char)> = Set::default();
+        for (source, symbol, _, _) in &self.inner.edges {
+            if !seen.insert((*source, *symbol)) {
+                return Err(NotDeterministicError {
+                    source: *source,
+                    symbol: *symbol,
+                });
+            }
+        }
+        Ok(CheckedTSBuilder {
+            inner: self.inner,
+            init: self.init,
+            _det: std::marker::PhantomData,
+            _colors: std::marker::PhantomData,
+        })
+    }
+
+    /// Asserts, without checking, that every state that will be built has a color
+    /// (either explicit or via a default). Prefer [`Self::checked_colors_complete`]
+    /// unless this has already been established by construction.
+    pub fn assert_colors_complete(self) -> CheckedTSBuilder<Q, C, Det, Init, Colored> {
+        CheckedTSBuilder {
+            inner: self.inner,
+            init: self.init,
+            _det: std::marker::PhantomData,
+            _colors: std::marker::PhantomData,
+        }
+    }
+
+    /// Validates that every state that would be built from the edges added so far has
+    /// either an explicit color or a default to fall back on, returning the first
+    /// uncolored state as an error otherwise.
+    pub fn checked_colors_complete(
+        self,
+    ) -> Result<CheckedTSBuilder<Q, C, Det, Init, Colored>, MissingColorError> {
+        if self.inner.default.is_none() {
+            let num_states = self
+                .inner
+                .edges
+                .iter()
+                .flat_map(|(q, _, _, p)| [*p, *q])
+                .unique()
+                .count();
+            for state in 0..num_states {
+                if self.inner.colors.iter().all(|(q, _)| *q != state) {
+                    return Err(MissingColorError { state });
+                }
+            }
+        }
+        Ok(CheckedTSBuilder {
+            inner: self.inner,
+            init: self.init,
+            _det: std::marker::PhantomData,
+            _colors: std::marker::PhantomData,
+        })
+    }
+}
+
+impl<Q: Clone, C: Clone, Det, Colors> CheckedTSBuilder<Q, C, Det, NoInitial, Colors> {
+    /// Fixes the initial state, flipping the typestate marker so that the `into_*`
+    /// conversions below become callable without taking a separate `initial`
+    /// argument.
+    pub fn with_initial(self, initial: usize) -> CheckedTSBuilder<Q, C, Det, HasInitial, Colors> {
+        CheckedTSBuilder {
+            inner: self.inner,
+            init: HasInitial(initial),
+            _det: std::marker::PhantomData,
+            _colors: std::marker::PhantomData,
+        }
+    }
+}
+
+impl CheckedTSBuilder<bool, Void, AssertedDeterministic, HasInitial, Colored> {
+    /// See [`TSBuilder::into_dfa`]; only callable once determinism, an initial state
+    /// and complete coloring have all been established.
+    pub fn into_dfa(self) -> DFA<CharAlphabet> {
+        self.inner.into_dfa(self.init.0)
+    }
+}
+
+impl CheckedTSBuilder<Void, bool, AssertedDeterministic, HasInitial, Colored> {
+    /// See [`TSBuilder::into_dba`].
+    pub fn into_dba(self) -> DBA<CharAlphabet> {
+        self.inner.into_dba(self.init.0)
+    }
+}
+
+impl CheckedTSBuilder<Void, usize, AssertedDeterministic, HasInitial, Colored> {
+    /// See [`TSBuilder::into_dpa`].
+    pub fn into_dpa(self) -> DPA<CharAlphabet> {
+        self.inner.into_dpa(self.init.0)
+    }
+
+    /// See [`TSBuilder::into_mealy`].
+    pub fn into_mealy(self) -> MealyMachine<CharAlphabet> {
+        self.inner.into_mealy(self.init.0)
+    }
+}
+
+impl CheckedTSBuilder<usize, Void, AssertedDeterministic, HasInitial, Colored> {
+    /// See [`TSBuilder::into_moore`].
+    pub fn into_moore(self) -> MooreMachine<CharAlphabet> {
+        self.inner.into_moore(self.init.0)
+    }
+}